@@ -1,6 +1,7 @@
 use alloy_rpc_types_engine::ExecutionPayloadV3;
 use bytes::Bytes;
 use color_eyre::eyre::{self, eyre, OptionExt};
+use emerald_core::{wait_for_sync, BlockValidity, SyncError};
 use malachitebft_app_channel::app::engine::host::Next;
 use malachitebft_app_channel::app::streaming::StreamContent;
 use malachitebft_app_channel::app::types::core::{Round, Validity};
@@ -15,7 +16,6 @@ use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use crate::bootstrap::{initialize_state_from_existing_block, initialize_state_from_genesis};
-use crate::payload::validate_execution_payload;
 use crate::state::{decode_value, State};
 use crate::sync_handler::get_decided_value_for_sync;
 use crate::validators::read_validators_from_contract;
@@ -42,6 +42,23 @@ pub async fn on_consensus_ready(
     // Check compatibility with execution client
     engine.check_capabilities().await?;
 
+    match engine.eth.client_version().await {
+        Ok(client_version) => info!(%client_version, "Connected to execution client"),
+        Err(e) => warn!(error = %e, "Failed to get execution client version"),
+    }
+
+    // Do not start consensus on top of an execution client that is still catching up
+    let sync_timeout = emerald_config.retry_config.max_elapsed_time;
+    match wait_for_sync(engine, sync_timeout, |current, highest| {
+        info!(current, highest, "Waiting for execution client to sync");
+    })
+    .await
+    {
+        Ok(()) => {}
+        Err(SyncError::Client(e)) => return Err(e),
+        Err(e) => return Err(eyre!("{e}")),
+    }
+
     // Get latest decided height from local store
     let latest_height_from_store = state.store.max_decided_value_height().await;
     match latest_height_from_store {
@@ -392,19 +409,24 @@ pub async fn on_decided(
         .block_hash;
     assert_eq!(latest_block_hash, parent_block_hash);
 
-    // Validate the execution payload (uses cache internally)
-    let validity = validate_execution_payload(
-        state.validated_cache_mut(),
-        &block_bytes,
-        height,
-        round,
-        engine,
-        &emerald_config.retry_config,
-    )
-    .await?;
+    // Validate the execution payload (uses cache internally). The block is decided,
+    // so it is not checked against the local clock.
+    let validity = state
+        .validate_decided_block(
+            &block_bytes,
+            height,
+            round,
+            engine,
+            &emerald_config.retry_config,
+        )
+        .await?;
 
-    if validity == Validity::Invalid {
-        return Err(eyre!("Block validation failed for hash: {}", block_hash));
+    if let BlockValidity::Invalid(reason) = validity {
+        return Err(eyre!(
+            "Block validation failed for hash {}: {}",
+            block_hash,
+            reason
+        ));
     }
 
     debug!(
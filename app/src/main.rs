@@ -1,10 +1,14 @@
-use color_eyre::eyre::{eyre, Result};
+use std::process::ExitCode;
+
+use color_eyre::eyre::{Result, WrapErr};
 use emerald::node::App;
 use malachitebft_app_channel::app::node::Node;
 use malachitebft_eth_cli::args::{Args, Commands};
 use malachitebft_eth_cli::cmd::init::InitCmd;
+use malachitebft_eth_cli::cmd::show_identity::ShowIdentityCmd;
 use malachitebft_eth_cli::cmd::start::StartCmd;
 use malachitebft_eth_cli::cmd::testnet::TestnetCmd;
+use malachitebft_eth_cli::error::Error as CliError;
 use malachitebft_eth_cli::{config, logging, runtime};
 use malachitebft_eth_types::Height;
 use tracing::{info, trace};
@@ -17,9 +21,27 @@ use tracing::{info, trace};
 /// - Initializes logging system
 /// - Sets up error handling
 /// - Creates and runs the application node
-fn main() -> Result<()> {
-    color_eyre::install()?;
+///
+/// CLI failures exit with the stable code of their category, see
+/// [`malachitebft_eth_cli::error::exit_code`], anything else with 1.
+fn main() -> ExitCode {
+    if let Err(error) = color_eyre::install() {
+        eprintln!("Error: {error:?}");
+        return ExitCode::FAILURE;
+    }
+
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error:?}");
+            error
+                .downcast_ref::<CliError>()
+                .map_or(ExitCode::FAILURE, ExitCode::from)
+        }
+    }
+}
 
+fn run() -> Result<()> {
     // Load command-line arguments and possible configuration file.
     let args = Args::new();
 
@@ -44,6 +66,7 @@ fn main() -> Result<()> {
         Commands::Init(cmd) => init(&args, cmd, logging),
         Commands::Testnet(cmd) => testnet(&args, cmd, logging),
         Commands::ShowPubkey(cmd) => cmd.run(),
+        Commands::ShowIdentity(cmd) => show_identity(&args, cmd),
         _ => unimplemented!(),
     }
 }
@@ -52,10 +75,10 @@ fn start(args: &Args, cmd: &StartCmd, logging: config::LoggingConfig) -> Result<
     // Load configuration file if it exists. Some commands do not require a configuration file.
     let config_file = args
         .get_config_file_path()
-        .map_err(|error| eyre!("Failed to get configuration file path: {error}"))?;
+        .wrap_err("Failed to get configuration file path")?;
 
-    let mut config = config::load_config(&config_file, None)
-        .map_err(|error| eyre!("Failed to load configuration file: {error}"))?;
+    let mut config =
+        config::load_config(&config_file, None).wrap_err("Failed to load configuration file")?;
 
     config.logging = logging;
 
@@ -80,7 +103,7 @@ fn start(args: &Args, cmd: &StartCmd, logging: config::LoggingConfig) -> Result<
 
     // Start the node
     rt.block_on(app.run())
-        .map_err(|error| eyre!("Failed to run the application node: {error}"))
+        .wrap_err("Failed to run the application node")
 }
 
 fn init(args: &Args, cmd: &InitCmd, logging: config::LoggingConfig) -> Result<()> {
@@ -101,7 +124,7 @@ fn init(args: &Args, cmd: &InitCmd, logging: config::LoggingConfig) -> Result<()
         &args.get_priv_validator_key_file_path()?,
         logging,
     )
-    .map_err(|error| eyre!("Failed to run init command {error:?}"))
+    .wrap_err("Failed to run init command")
 }
 
 fn testnet(args: &Args, cmd: &TestnetCmd, logging: config::LoggingConfig) -> Result<()> {
@@ -116,5 +139,15 @@ fn testnet(args: &Args, cmd: &TestnetCmd, logging: config::LoggingConfig) -> Res
     };
 
     cmd.run(&app, &args.get_home_dir()?, logging)
-        .map_err(|error| eyre!("Failed to run testnet command {:?}", error))
+        .wrap_err("Failed to run testnet command")
+}
+
+fn show_identity(args: &Args, cmd: &ShowIdentityCmd) -> Result<()> {
+    let config_file = args
+        .get_config_file_path()
+        .wrap_err("Failed to get configuration file path")?;
+    let config =
+        config::load_config(&config_file, None).wrap_err("Failed to load configuration file")?;
+
+    cmd.run(&config.moniker)
 }
@@ -100,7 +100,13 @@ impl App {
             tokio::spawn(metrics::serve(config.metrics.listen_addr));
         }
 
-        let store = Store::open(self.get_home_dir().join("store.db"), metrics.db.clone()).await?;
+        let emerald_config = self.load_emerald_config()?;
+        let home_dir = self.get_home_dir();
+        emerald_config.validate(&home_dir)?;
+
+        let data_dir = emerald_config.data_dir(&home_dir);
+        fs::create_dir_all(&data_dir)?;
+        let store = Store::open(data_dir.join("store.db"), metrics.db.clone()).await?;
         let start_height = self.start_height.unwrap_or_default();
 
         // Load cumulative metrics from database for crash recovery
@@ -117,7 +123,6 @@ impl App {
             metrics,
         };
 
-        let emerald_config = self.load_emerald_config()?;
         let engine: Engine = {
             let engine_url = Url::parse(&emerald_config.ethereum_config.engine_authrpc_address)?;
             let jwt_path = PathBuf::from_str(&emerald_config.ethereum_config.jwt_token_path)?;
@@ -128,23 +133,6 @@ impl App {
             )
         };
 
-        // Check the validity of the configuration parameters
-        let num_certificates_to_retain = emerald_config.num_certificates_to_retain;
-        let num_temp_blocks_retained = emerald_config.num_temp_blocks_retained;
-
-        if num_certificates_to_retain < num_temp_blocks_retained {
-            return Err(eyre::eyre!(
-                "num_certificates_to_retain has to be >= than num_temp_blocks_retained."
-            ));
-        }
-
-        let prune_at_block_interval = emerald_config.prune_at_block_interval;
-
-        assert!(
-            prune_at_block_interval != 0,
-            "prune block interval cannot be 0"
-        );
-
         let state = State::new(
             genesis,
             ctx,
@@ -1,11 +1,17 @@
 //! Execution payload utilities for validation, caching, and manipulation.
 
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy_consensus::proofs::calculate_withdrawals_root;
+use alloy_primitives::B256;
 use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
 use bytes::Bytes;
 use caches::lru::AdaptiveCache;
 use caches::Cache;
 use color_eyre::eyre::{self, eyre};
-use malachitebft_app_channel::app::types::core::{Round, Validity};
+use emerald_core::{BlockValidity, InvalidBlockReason};
+use malachitebft_app_channel::app::types::core::Round;
 use malachitebft_eth_engine::engine::Engine;
 use malachitebft_eth_engine::json_structures::ExecutionPayloadBodyV1;
 use malachitebft_eth_types::{Block, BlockHash, Height, RetryConfig};
@@ -13,9 +19,9 @@ use ssz::Decode;
 use tracing::{debug, error, warn};
 
 /// Cache for tracking recently validated execution payloads to avoid redundant validation.
-/// Stores both the block hash and its validity result (Valid or Invalid).
+/// Stores both the block hash and its validity result, including the rejection reason.
 pub struct ValidatedPayloadCache {
-    cache: AdaptiveCache<BlockHash, Validity>,
+    cache: AdaptiveCache<BlockHash, BlockValidity>,
 }
 
 impl ValidatedPayloadCache {
@@ -27,22 +33,44 @@ impl ValidatedPayloadCache {
     }
 
     /// Check if a block hash has been validated and return its cached validity
-    pub fn get(&mut self, block_hash: &BlockHash) -> Option<Validity> {
+    pub fn get(&mut self, block_hash: &BlockHash) -> Option<BlockValidity> {
         self.cache.get(block_hash).copied()
     }
 
     /// Insert a block hash and its validity result into the cache
-    pub fn insert(&mut self, block_hash: BlockHash, validity: Validity) {
+    pub fn insert(&mut self, block_hash: BlockHash, validity: BlockValidity) {
         self.cache.put(block_hash, validity);
     }
 }
 
+/// Checks the header fields of a payload that can be validated without the execution
+/// engine: when `max_future_drift` is given, its timestamp must not be more than that
+/// ahead of `now`, the local unix time in seconds.
+///
+/// The drift only applies to proposals: a decided block is certified by a quorum, so
+/// a node with a skewed clock must follow it rather than reject it.
+pub fn precheck_header(
+    payload: &ExecutionPayloadV1,
+    now: u64,
+    max_future_drift: Option<Duration>,
+) -> Result<(), InvalidBlockReason> {
+    if let Some(max_future_drift) = max_future_drift {
+        if payload.timestamp > now.saturating_add(max_future_drift.as_secs()) {
+            return Err(InvalidBlockReason::TimestampTooFarFuture);
+        }
+    }
+
+    Ok(())
+}
+
 /// Validates execution payload bytes with the execution engine.
-/// Decodes the payload, extracts versioned hashes, and validates.
+/// Decodes the payload, checks its header with [`precheck_header`] against the local
+/// clock if `max_future_drift` is given, extracts versioned hashes, and validates.
 /// Uses cache to avoid duplicate validation calls.
 ///
-/// Returns `Ok(Validity::Invalid)` if decoding fails or payload is invalid,
-/// `Ok(Validity::Valid)` if valid, or `Err` for engine communication failures.
+/// Returns `Ok(BlockValidity::Invalid(reason))` if decoding fails, the header precheck
+/// fails, or the engine rejects it, `Ok(BlockValidity::Valid)` if valid,
+/// or `Err` for engine communication failures.
 pub async fn validate_execution_payload(
     cache: &mut ValidatedPayloadCache,
     data: &Bytes,
@@ -50,7 +78,8 @@ pub async fn validate_execution_payload(
     round: Round,
     engine: &Engine,
     retry_config: &RetryConfig,
-) -> eyre::Result<Validity> {
+    max_future_drift: Option<Duration>,
+) -> eyre::Result<BlockValidity> {
     // Decode execution payload
     let execution_payload = match ExecutionPayloadV3::from_ssz_bytes(data) {
         Ok(payload) => payload,
@@ -61,11 +90,26 @@ pub async fn validate_execution_payload(
                 error = ?e,
                 "Proposal has invalid ExecutionPayloadV3 encoding"
             );
-            return Ok(Validity::Invalid);
+            return Ok(BlockValidity::Invalid(InvalidBlockReason::Malformed));
         }
     };
 
-    let block_hash = execution_payload.payload_inner.payload_inner.block_hash;
+    let payload_inner = &execution_payload.payload_inner.payload_inner;
+    let block_hash = payload_inner.block_hash;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    if let Err(reason) = precheck_header(payload_inner, now, max_future_drift) {
+        warn!(
+            %height, %round, %block_hash, %reason,
+            timestamp = payload_inner.timestamp,
+            now,
+            "Payload header precheck failed"
+        );
+        return Ok(BlockValidity::Invalid(reason));
+    }
 
     // Check if we've already validated this block
     if let Some(cached_validity) = cache.get(&block_hash) {
@@ -86,7 +130,7 @@ pub async fn validate_execution_payload(
                 error = ?e,
                 "Failed to convert ExecutionPayloadV3 to Block"
             );
-            return Ok(Validity::Invalid);
+            return Ok(BlockValidity::Invalid(InvalidBlockReason::Malformed));
         }
     };
     let versioned_hashes: Vec<BlockHash> =
@@ -106,13 +150,13 @@ pub async fn validate_execution_payload(
         })?;
 
     let validity = if payload_status.status.is_valid() {
-        Validity::Valid
+        BlockValidity::Valid
     } else {
         // INVALID or ACCEPTED - both are treated as invalid
         // INVALID: malicious block
         // ACCEPTED: Non-canonical payload - should not happen with instant finality
         error!(%height, %round, "Block validation failed: {}", payload_status.status);
-        Validity::Invalid
+        BlockValidity::Invalid(InvalidBlockReason::Rejected)
     };
 
     cache.insert(block_hash, validity);
@@ -136,6 +180,13 @@ pub fn extract_block_header(payload: &ExecutionPayloadV3) -> ExecutionPayloadV3
     }
 }
 
+/// Computes the withdrawals trie root committed to by the block header of `payload`.
+///
+/// V3 payloads are always post-Shanghai, so the root is defined even with no withdrawals.
+pub fn withdrawals_root(payload: &ExecutionPayloadV3) -> B256 {
+    calculate_withdrawals_root(&payload.payload_inner.withdrawals)
+}
+
 /// Reconstructs a complete ExecutionPayloadV3 from a block header and payload body.
 ///
 /// Takes a header (ExecutionPayloadV3 with empty transactions/withdrawals) and combines it
@@ -155,3 +206,82 @@ pub fn reconstruct_execution_payload(
         ..header
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{b256, Address, Bloom, Bytes as AlloyBytes, U256};
+    use alloy_rpc_types_eth::Withdrawal;
+
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+    const DRIFT: Duration = Duration::from_secs(15);
+
+    fn payload(timestamp: u64) -> ExecutionPayloadV1 {
+        ExecutionPayloadV1 {
+            parent_hash: B256::repeat_byte(1),
+            fee_recipient: Address::ZERO,
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            logs_bloom: Bloom::ZERO,
+            prev_randao: B256::ZERO,
+            block_number: 10,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp,
+            extra_data: AlloyBytes::new(),
+            base_fee_per_gas: U256::from(7),
+            block_hash: B256::repeat_byte(2),
+            transactions: vec![],
+        }
+    }
+
+    #[test]
+    fn timestamp_within_drift_is_accepted() {
+        assert_eq!(precheck_header(&payload(NOW), NOW, Some(DRIFT)), Ok(()));
+        assert_eq!(
+            precheck_header(&payload(NOW + 15), NOW, Some(DRIFT)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn timestamp_beyond_drift_is_rejected() {
+        assert_eq!(
+            precheck_header(&payload(NOW + 16), NOW, Some(DRIFT)),
+            Err(InvalidBlockReason::TimestampTooFarFuture)
+        );
+    }
+
+    #[test]
+    fn decided_blocks_skip_the_drift_check() {
+        assert_eq!(precheck_header(&payload(NOW + 3600), NOW, None), Ok(()));
+    }
+
+    #[test]
+    fn withdrawals_root_commits_to_withdrawals() {
+        let mut payload = ExecutionPayloadV3 {
+            payload_inner: ExecutionPayloadV2 {
+                payload_inner: payload(NOW),
+                withdrawals: vec![],
+            },
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        };
+        assert_eq!(
+            withdrawals_root(&payload),
+            b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+        );
+
+        payload.payload_inner.withdrawals.push(Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address::repeat_byte(0x11),
+            amount: 3,
+        });
+        assert_eq!(
+            withdrawals_root(&payload),
+            b256!("aa34cc7ef97fe513688f9658d4a390e6090011d647fe95091bf1cc0feb14ff95")
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //! A regular application would have mempool implemented, a proper database and input methods like RPC.
 
 use core::str::FromStr;
+use core::time::Duration;
 use std::path::PathBuf;
 use std::{fmt, fs};
 
@@ -9,6 +10,7 @@ use alloy_genesis::{ChainConfig, Genesis as EvmGenesis};
 use alloy_rpc_types_engine::ExecutionPayloadV3;
 use bytes::Bytes;
 use color_eyre::eyre;
+use emerald_core::BlockValidity;
 use malachitebft_app_channel::app::streaming::{StreamContent, StreamId, StreamMessage};
 use malachitebft_app_channel::app::types::codec::Codec;
 use malachitebft_app_channel::app::types::core::{CommitCertificate, Context, Round, Validity};
@@ -34,7 +36,7 @@ use tracing::{debug, error, info, warn};
 use crate::metrics::Metrics;
 use crate::payload::{extract_block_header, validate_execution_payload, ValidatedPayloadCache};
 use crate::store::Store;
-use crate::streaming::{PartStreamsMap, ProposalParts};
+use crate::streaming::{DataPartError, PartStreamsMap, ProposalParts};
 
 pub struct StateMetrics {
     pub txs_count: u64,
@@ -47,9 +49,6 @@ pub struct StateMetrics {
 #[allow(dead_code)]
 const BLOCK_SIZE: usize = 10 * 1024 * 1024; // 10 MiB
 
-/// Size of chunks in which the data is split for streaming
-const CHUNK_SIZE: usize = 128 * 1024; // 128 KiB
-
 /// Represents the internal state of the application node
 /// Contains information about current height, round, proposals and blocks
 pub struct State {
@@ -69,6 +68,7 @@ pub struct State {
     /// prune_at_block_interval
     /// num_temp_blocks_retained
     /// min_block_time
+    /// proposal_part_size
     /// ethereum_config : EthereumConfig (path to eth genesis and EL relevant information)
     pub emerald_config: EmeraldConfig,
 
@@ -143,6 +143,8 @@ pub enum ProposalValidationError {
     Signature(SignatureVerificationError),
     /// Validator set not found for the given height
     ValidatorSetNotFound { height: Height },
+    /// Data parts do not match the init part of an indexed proposal
+    DataParts(DataPartError),
 }
 
 impl fmt::Display for ProposalValidationError {
@@ -157,6 +159,9 @@ impl fmt::Display for ProposalValidationError {
             Self::ValidatorSetNotFound { height } => {
                 write!(f, "Validator set not found for height {height}")
             }
+            Self::DataParts(err) => {
+                write!(f, "Invalid data parts: {err}")
+            }
         }
     }
 }
@@ -337,6 +342,10 @@ impl State {
             });
         }
 
+        parts
+            .check_data_parts()
+            .map_err(ProposalValidationError::DataParts)?;
+
         // If proposer is correct, verify the signature
         self.verify_proposal_parts_signature(parts)
             .map_err(ProposalValidationError::Signature)?;
@@ -360,13 +369,13 @@ impl State {
             .ok_or(SignatureVerificationError::MissingFinPart)?;
 
         let hash = {
-            hasher.update(init.height.as_u64().to_be_bytes());
-            hasher.update(init.round.as_i64().to_be_bytes());
+            init.hash_into(|bytes| hasher.update(bytes));
 
             // The correctness of the hash computation relies on the parts being ordered by sequence
-            // number, which is guaranteed by the `PartStreamsMap`.
+            // number, which is guaranteed by the `PartStreamsMap`. Indexed proposals also bind
+            // each part's index, checked by `ProposalParts::check_data_parts`.
             for part in parts.parts.iter().filter_map(|part| part.as_data()) {
-                hasher.update(part.bytes.as_ref());
+                part.hash_into(init.version, |bytes| hasher.update(bytes));
             }
 
             hasher.finalize()
@@ -393,6 +402,69 @@ impl State {
         Ok(())
     }
 
+    /// Validates proposed block bytes with the execution engine, rejecting
+    /// timestamps more than `max_future_drift` ahead of the local clock.
+    ///
+    /// Returns the [`BlockValidity`] of the block, including the rejection reason
+    /// when it is invalid, or an error for engine communication failures.
+    pub async fn validate_block(
+        &mut self,
+        data: &Bytes,
+        height: Height,
+        round: Round,
+        engine: &Engine,
+        retry_config: &RetryConfig,
+    ) -> eyre::Result<BlockValidity> {
+        let max_future_drift = self.emerald_config.max_future_drift;
+        self.validate_block_with_drift(
+            data,
+            height,
+            round,
+            engine,
+            retry_config,
+            Some(max_future_drift),
+        )
+        .await
+    }
+
+    /// Validates decided block bytes with the execution engine.
+    ///
+    /// Unlike [`Self::validate_block`], the timestamp is not checked against the
+    /// local clock: the block is certified, so a node with a skewed clock has to
+    /// follow it instead of halting.
+    pub async fn validate_decided_block(
+        &mut self,
+        data: &Bytes,
+        height: Height,
+        round: Round,
+        engine: &Engine,
+        retry_config: &RetryConfig,
+    ) -> eyre::Result<BlockValidity> {
+        self.validate_block_with_drift(data, height, round, engine, retry_config, None)
+            .await
+    }
+
+    async fn validate_block_with_drift(
+        &mut self,
+        data: &Bytes,
+        height: Height,
+        round: Round,
+        engine: &Engine,
+        retry_config: &RetryConfig,
+        max_future_drift: Option<Duration>,
+    ) -> eyre::Result<BlockValidity> {
+        validate_execution_payload(
+            &mut self.validated_payload_cache,
+            data,
+            height,
+            round,
+            engine,
+            retry_config,
+            max_future_drift,
+        )
+        .await
+    }
+
     /// Processes complete proposal parts: validates, stores, and returns the proposed value.
     ///
     /// Returns `Ok(Some(ProposedValue))` if the proposal is valid and stored,
@@ -426,21 +498,16 @@ impl State {
             "Proposal data"
         );
 
-        // Validate the execution payload with the execution engine
-        let validity = validate_execution_payload(
-            &mut self.validated_payload_cache,
-            &data,
-            value.height,
-            value.round,
-            engine,
-            retry_config,
-        )
-        .await?;
+        // Validate the execution payload against the latest block and the execution engine
+        let validity = self
+            .validate_block(&data, value.height, value.round, engine, retry_config)
+            .await?;
 
-        if validity == Validity::Invalid {
+        if let BlockValidity::Invalid(reason) = validity {
             warn!(
                 height = %parts.height,
                 round = %parts.round,
+                %reason,
                 "Proposal has invalid execution payload, rejecting"
             );
             return Ok(None);
@@ -776,25 +843,30 @@ impl State {
         let mut hasher = sha3::Keccak256::new();
         let mut parts = Vec::new();
 
+        let chunks: Vec<ProposalData> =
+            ProposalData::split(&data, self.emerald_config.proposal_part_size).collect();
+
         // Init
-        {
-            parts.push(ProposalPart::Init(ProposalInit::new(
-                value.height,
-                value.round,
-                pol_round,
-                self.address,
-            )));
-
-            hasher.update(value.height.as_u64().to_be_bytes().as_slice());
-            hasher.update(value.round.as_i64().to_be_bytes().as_slice());
-        }
+        let init = {
+            let init = ProposalInit::new(value.height, value.round, pol_round, self.address);
+            let init = if self.emerald_config.proposal_parts_version >= ProposalInit::INDEXED {
+                let total_parts =
+                    u32::try_from(chunks.len()).expect("proposal part count fits in u32");
+                init.with_total_parts(total_parts)
+            } else {
+                init
+            };
+
+            init.hash_into(|bytes| hasher.update(bytes));
+            parts.push(ProposalPart::Init(init.clone()));
+            init
+        };
 
         // Data
         {
-            for chunk in data.chunks(CHUNK_SIZE) {
-                let chunk_data = ProposalData::new(Bytes::copy_from_slice(chunk));
+            for chunk_data in chunks {
+                chunk_data.hash_into(init.version, |bytes| hasher.update(bytes));
                 parts.push(ProposalPart::Data(chunk_data));
-                hasher.update(chunk);
             }
         }
 
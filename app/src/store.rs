@@ -13,7 +13,7 @@ use malachitebft_app_channel::app::types::sync::RawDecidedValue;
 use malachitebft_app_channel::app::types::ProposedValue;
 use malachitebft_eth_types::codec::proto as codec;
 use malachitebft_eth_types::codec::proto::ProtobufCodec;
-use malachitebft_eth_types::{proto, EmeraldContext, Height, Value, ValueId};
+use malachitebft_eth_types::{proto, EmeraldContext, Height, ProposalInit, Value, ValueId};
 use malachitebft_proto::{Error as ProtoError, Protobuf};
 use prost::Message;
 use redb::ReadableTable;
@@ -387,10 +387,13 @@ impl Db {
         hasher.update(parts.round.as_i64().to_be_bytes());
         hasher.update(parts.proposer.into_inner());
 
-        // Hash all the proposal parts content
+        // Hash all the proposal parts content, with their index for indexed proposals
+        let version = parts
+            .init()
+            .map_or(ProposalInit::LEGACY, |init| init.version);
         for part in &parts.parts {
             if let Some(data) = part.as_data() {
-                hasher.update(data.bytes.as_ref());
+                data.hash_into(version, |bytes| hasher.update(bytes));
             }
         }
 
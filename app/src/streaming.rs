@@ -1,4 +1,5 @@
 use core::cmp::Ordering;
+use core::fmt;
 use std::collections::{BTreeMap, BinaryHeap, HashSet};
 
 use malachitebft_app_channel::app::streaming::{Sequence, StreamId, StreamMessage};
@@ -122,6 +123,51 @@ impl ProposalParts {
     pub fn fin(&self) -> Option<&ProposalFin> {
         self.parts.iter().find_map(|p| p.as_fin())
     }
+
+    /// Check that the data parts of an [`ProposalInit::INDEXED`] proposal are in
+    /// order and as many as announced by its init part. Legacy proposals carry
+    /// no usable index, so they always pass.
+    pub fn check_data_parts(&self) -> Result<(), DataPartError> {
+        let Some(init) = self.init().filter(|init| init.is_indexed()) else {
+            return Ok(());
+        };
+
+        let mut count = 0;
+        for index in self.parts.iter().filter_map(ProposalPart::part_index) {
+            if count >= init.total_parts {
+                return Err(DataPartError::Unexpected { index });
+            }
+            if index != count {
+                return Err(DataPartError::Missing { index: count });
+            }
+            count += 1;
+        }
+
+        if count < init.total_parts {
+            return Err(DataPartError::Missing { index: count });
+        }
+        Ok(())
+    }
+}
+
+/// Why the data parts of an indexed proposal do not match its init part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataPartError {
+    /// The data part with this index is missing or out of order
+    Missing { index: u32 },
+    /// A data part beyond the number announced by the init part
+    Unexpected { index: u32 },
+}
+
+impl fmt::Display for DataPartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { index } => write!(f, "missing or out of order data part {index}"),
+            Self::Unexpected { index } => {
+                write!(f, "data part {index} beyond the announced number of parts")
+            }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -187,7 +233,7 @@ mod tests {
             Round::Nil,
             address,
         ));
-        let data = ProposalPart::Data(ProposalData::new(Bytes::new()));
+        let data = ProposalPart::Data(ProposalData::new(0, Bytes::new()));
         let fin = ProposalPart::Fin(ProposalFin::new(signature));
 
         let part0 = StreamMessage::new(stream_id.clone(), 0, StreamContent::Data(init));
@@ -210,4 +256,56 @@ mod tests {
             "streams map must drop complete streams"
         );
     }
+
+    #[test]
+    fn test_check_data_parts() {
+        let address = Address::new([0; 20]);
+        let init = |total_parts| {
+            ProposalPart::Init(
+                ProposalInit::new(Height::new(1), Round::Some(0), Round::Nil, address)
+                    .with_total_parts(total_parts),
+            )
+        };
+        let chunk = |index| ProposalPart::Data(ProposalData::new(index, Bytes::new()));
+        let parts = |parts| ProposalParts {
+            height: Height::new(1),
+            round: Round::Some(0),
+            proposer: address,
+            parts,
+        };
+
+        assert_eq!(
+            parts(vec![init(3), chunk(0), chunk(1), chunk(2)]).check_data_parts(),
+            Ok(())
+        );
+        assert_eq!(
+            parts(vec![init(3), chunk(0), chunk(2)]).check_data_parts(),
+            Err(DataPartError::Missing { index: 1 })
+        );
+        assert_eq!(
+            parts(vec![init(2), chunk(1), chunk(0)]).check_data_parts(),
+            Err(DataPartError::Missing { index: 0 })
+        );
+        // Truncated tail
+        assert_eq!(
+            parts(vec![init(3), chunk(0), chunk(1)]).check_data_parts(),
+            Err(DataPartError::Missing { index: 2 })
+        );
+        assert_eq!(
+            parts(vec![init(1), chunk(0), chunk(1)]).check_data_parts(),
+            Err(DataPartError::Unexpected { index: 1 })
+        );
+
+        // Legacy peers send no index, which decodes as 0 for every chunk
+        let legacy = ProposalPart::Init(ProposalInit::new(
+            Height::new(1),
+            Round::Some(0),
+            Round::Nil,
+            address,
+        ));
+        assert_eq!(
+            parts(vec![legacy, chunk(0), chunk(0), chunk(0)]).check_data_parts(),
+            Ok(())
+        );
+    }
 }
@@ -50,7 +50,7 @@ pub async fn read_validators_from_contract(
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::U256;
+    use alloy_primitives::{Address, U256};
 
     use super::*;
 
@@ -66,6 +66,7 @@ mod tests {
                 y: U256::from_str_radix(y_hex, 16).unwrap(),
             },
             power,
+            feeRecipient: Address::ZERO,
         }
     }
 
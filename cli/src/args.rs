@@ -14,6 +14,7 @@ use malachitebft_config::{LogFormat, LogLevel};
 
 use crate::cmd::distributed_testnet::DistributedTestnetCmd;
 use crate::cmd::init::InitCmd;
+use crate::cmd::show_identity::ShowIdentityCmd;
 use crate::cmd::show_pubkey::ShowPubkeyCmd;
 use crate::cmd::start::StartCmd;
 use crate::cmd::testnet::TestnetCmd;
@@ -63,6 +64,9 @@ pub enum Commands {
 
     /// Extract secp256k1 public key from a file containing a Secp256k1 private key
     ShowPubkey(ShowPubkeyCmd),
+
+    /// Print the node identity from the configured moniker and the node's keys
+    ShowIdentity(ShowIdentityCmd),
 }
 
 impl Default for Commands {
@@ -1,5 +1,6 @@
 pub mod distributed_testnet;
 pub mod init;
+pub mod show_identity;
 pub mod show_pubkey;
 pub mod start;
 pub mod testnet;
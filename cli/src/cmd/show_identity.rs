@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use color_eyre::eyre::{eyre, Context, Result};
+use malachitebft_eth_types::bls12381::{MinPk, PrivateKey as BlsPrivateKey};
+use malachitebft_eth_types::secp256k1::PrivateKey;
+use malachitebft_eth_types::NodeIdentity;
+
+/// Print the identity of a node: its moniker, EVM address and BLS public key
+#[derive(Args, Clone, Debug)]
+pub struct ShowIdentityCmd {
+    /// Path to priv_validator_key.json file
+    #[clap(value_name = "KEY_FILE")]
+    pub key_file: PathBuf,
+
+    /// Path to a file containing the hex-encoded BLS private key
+    #[clap(long, value_name = "BLS_KEY_FILE")]
+    pub bls_key_file: PathBuf,
+
+    /// Print the identity as JSON instead of a single line
+    #[clap(long)]
+    pub json: bool,
+}
+
+impl ShowIdentityCmd {
+    pub fn run(&self, moniker: &str) -> Result<()> {
+        let contents = std::fs::read_to_string(&self.key_file)
+            .with_context(|| format!("Failed to read key file: {}", self.key_file.display()))?;
+        let private_key: PrivateKey = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse JSON from: {}", self.key_file.display()))?;
+
+        let bls_contents = std::fs::read_to_string(&self.bls_key_file).with_context(|| {
+            format!(
+                "Failed to read BLS key file: {}",
+                self.bls_key_file.display()
+            )
+        })?;
+        let bls_bytes = hex::decode(bls_contents.trim().trim_start_matches("0x"))
+            .with_context(|| format!("Invalid hex in: {}", self.bls_key_file.display()))?;
+        let bls_key = BlsPrivateKey::<MinPk>::from_bytes(&bls_bytes)
+            .map_err(|e| eyre!("Invalid BLS private key: {e:?}"))?;
+
+        let identity = NodeIdentity::new(moniker, &private_key.public_key(), &bls_key);
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&identity)?);
+        } else {
+            println!("{identity}");
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
+use alloy_primitives::{keccak256, B256};
 use color_eyre::eyre;
 use malachitebft_app::node::NodeConfig;
 pub use malachitebft_config::{
@@ -7,8 +9,12 @@ pub use malachitebft_config::{
     MempoolLoadConfig, MetricsConfig, P2pConfig, PubSubProtocol, RuntimeConfig, ScoringStrategy,
     Selector, TestConfig, TimeoutConfig, TransportProtocol, ValuePayload, ValueSyncConfig,
 };
-use malachitebft_eth_types::{Address, RetryConfig};
+use malachitebft_eth_types::{
+    Address, ProposalInit, RetryConfig, DEFAULT_PROPOSAL_PART_SIZE, MAX_PROPOSAL_PART_SIZE,
+    MIN_PROPOSAL_PART_SIZE,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tokio::time::Duration;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -64,6 +70,12 @@ pub struct EmeraldConfig {
     #[serde(with = "humantime_serde", default = "default_min_block_time")]
     pub min_block_time: Duration,
 
+    /// How far ahead of the local clock the timestamp of a proposed block
+    /// may be before the block is rejected.
+    /// Default: 15s
+    #[serde(with = "humantime_serde", default = "default_max_future_drift")]
+    pub max_future_drift: Duration,
+
     // Address used to receive fees
     pub fee_recipient: Address,
 
@@ -78,12 +90,101 @@ pub struct EmeraldConfig {
     /// Default: 10
     #[serde(default = "default_num_temp_blocks_retained")]
     pub num_temp_blocks_retained: u64,
+
+    /// Directory holding the node's persistent data, such as the block store.
+    /// Relative paths are resolved against the node home directory.
+    /// Default: "." (the home directory itself)
+    #[serde(default = "default_data_dir")]
+    pub data_dir: PathBuf,
+
+    /// Size in bytes of the data parts a proposal is split into for streaming.
+    /// Has to be between 1 KiB and 1 MiB.
+    /// Default: 131072 (128 KiB)
+    #[serde(default = "default_proposal_part_size")]
+    pub proposal_part_size: usize,
+
+    /// Layout of the proposal parts this node sends: 0 for legacy parts, 1 for
+    /// indexed parts whose order and number are covered by the proposer's
+    /// signature. Both are always accepted from peers.
+    /// Only raise to 1 once every validator runs a release that accepts it.
+    /// Default: 0
+    #[serde(default)]
+    pub proposal_parts_version: u32,
+}
+
+impl EmeraldConfig {
+    /// Data directory of a node whose home directory is `home_dir`
+    pub fn data_dir(&self, home_dir: &Path) -> PathBuf {
+        home_dir.join(&self.data_dir)
+    }
+
+    /// Check the consistency of the configuration parameters of a node whose
+    /// home directory is `home_dir`
+    pub fn validate(&self, home_dir: &Path) -> eyre::Result<()> {
+        if self.num_certificates_to_retain < self.num_temp_blocks_retained {
+            return Err(eyre::eyre!(
+                "num_certificates_to_retain has to be >= than num_temp_blocks_retained."
+            ));
+        }
+
+        if self.prune_at_block_interval == 0 {
+            return Err(eyre::eyre!("prune block interval cannot be 0"));
+        }
+
+        let multiplier = self.retry_config.multiplier;
+        if multiplier.is_nan() || multiplier < 1.0 {
+            return Err(eyre::eyre!(
+                "retry_config.multiplier has to be >= 1.0, got {multiplier}"
+            ));
+        }
+
+        if !(MIN_PROPOSAL_PART_SIZE..=MAX_PROPOSAL_PART_SIZE).contains(&self.proposal_part_size) {
+            return Err(eyre::eyre!(
+                "proposal_part_size has to be between {MIN_PROPOSAL_PART_SIZE} and {MAX_PROPOSAL_PART_SIZE} bytes, got {}",
+                self.proposal_part_size
+            ));
+        }
+
+        if self.proposal_parts_version > ProposalInit::INDEXED {
+            return Err(eyre::eyre!(
+                "proposal_parts_version has to be at most {}, got {}",
+                ProposalInit::INDEXED,
+                self.proposal_parts_version
+            ));
+        }
+
+        if self.data_dir.as_os_str().is_empty() {
+            return Err(eyre::eyre!("data_dir cannot be empty"));
+        }
+
+        // The data directory is created on startup, so it must either be a
+        // directory already or have a directory as its closest existing ancestor
+        let data_dir = self.data_dir(home_dir);
+        let existing = data_dir
+            .ancestors()
+            .find(|path| !path.as_os_str().is_empty() && path.exists());
+        if let Some(path) = existing {
+            if !path.is_dir() {
+                return Err(eyre::eyre!(
+                    "data_dir `{}` cannot be created: `{}` is not a directory",
+                    data_dir.display(),
+                    path.display()
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn default_min_block_time() -> Duration {
     Duration::from_millis(500)
 }
 
+fn default_max_future_drift() -> Duration {
+    Duration::from_secs(15)
+}
+
 fn default_num_certificates_to_retain() -> u64 {
     u64::MAX
 }
@@ -95,6 +196,14 @@ fn default_num_temp_blocks_retained() -> u64 {
     10
 }
 
+fn default_data_dir() -> PathBuf {
+    PathBuf::from(".")
+}
+
+fn default_proposal_part_size() -> usize {
+    DEFAULT_PROPOSAL_PART_SIZE
+}
+
 fn default_eth_gensesis_path() -> String {
     "./assets/genesis.json".to_string()
 }
@@ -141,6 +250,61 @@ pub struct Config {
     pub test: TestConfig,
 }
 
+/// Fields of [`ConsensusConfig`] that only describe the local node, such as
+/// listen addresses and peers, and are left out of the consensus fingerprint
+const NODE_LOCAL_CONSENSUS_FIELDS: &[&str] = &["p2p"];
+
+impl Config {
+    /// Hash of the consensus-relevant part of the config and of `emerald`,
+    /// which must be equal on all nodes of a network. Covers the consensus
+    /// parameters (except the node-local p2p section), the value sync parameters
+    /// and the block timing and proposal streaming parameters of `emerald`, and
+    /// ignores node specific settings such as the moniker, metrics and logging.
+    ///
+    /// `proposal_parts_version` is left out, as it is raised one node at a time
+    /// while every node accepts both layouts.
+    pub fn consensus_fingerprint(&self, emerald: &EmeraldConfig) -> B256 {
+        let mut consensus =
+            serde_json::to_value(&self.consensus).expect("consensus config serializes to JSON");
+        if let Some(fields) = consensus.as_object_mut() {
+            for field in NODE_LOCAL_CONSENSUS_FIELDS {
+                fields.remove(*field);
+            }
+        }
+
+        let value_sync =
+            serde_json::to_value(&self.value_sync).expect("value sync config serializes to JSON");
+
+        let emerald = serde_json::json!({
+            "min_block_time_ms": emerald.min_block_time.as_millis() as u64,
+            "max_future_drift_ms": emerald.max_future_drift.as_millis() as u64,
+            "proposal_part_size": emerald.proposal_part_size,
+        });
+
+        let subset = serde_json::json!({
+            "consensus": consensus,
+            "value_sync": value_sync,
+            "emerald": emerald,
+        });
+
+        keccak256(canonicalize(subset).to_string())
+    }
+}
+
+/// Sort object keys recursively so the serialized form does not depend on
+/// field declaration or insertion order
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> =
+                map.into_iter().map(|(k, v)| (k, canonicalize(v))).collect();
+            Value::Object(sorted.into_iter().collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
 impl NodeConfig for Config {
     fn moniker(&self) -> &str {
         &self.moniker
@@ -173,3 +337,129 @@ pub fn load_config(path: impl AsRef<Path>, prefix: Option<&str>) -> eyre::Result
         .try_deserialize()
         .map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consensus_fingerprint_ignores_node_local_fields() {
+        let config = Config {
+            moniker: "node-0".to_string(),
+            ..Default::default()
+        };
+        let emerald = emerald_config(default_data_dir());
+        let fingerprint = config.consensus_fingerprint(&emerald);
+
+        let mut renamed = config.clone();
+        renamed.moniker = "node-1".to_string();
+        renamed.consensus.p2p.persistent_peers.clear();
+        assert_eq!(renamed.consensus_fingerprint(&emerald), fingerprint);
+
+        let mut relocated = emerald.clone();
+        relocated.data_dir = PathBuf::from("data");
+        relocated.proposal_parts_version = ProposalInit::INDEXED;
+        assert_eq!(config.consensus_fingerprint(&relocated), fingerprint);
+
+        let mut slower = config.clone();
+        slower.consensus.timeouts.timeout_propose += Duration::from_secs(1);
+        assert_ne!(slower.consensus_fingerprint(&emerald), fingerprint);
+
+        let mut lenient = emerald.clone();
+        lenient.max_future_drift += Duration::from_secs(1);
+        assert_ne!(config.consensus_fingerprint(&lenient), fingerprint);
+
+        let mut chunked = emerald;
+        chunked.proposal_part_size = MIN_PROPOSAL_PART_SIZE;
+        assert_ne!(config.consensus_fingerprint(&chunked), fingerprint);
+    }
+
+    fn emerald_config(data_dir: PathBuf) -> EmeraldConfig {
+        EmeraldConfig {
+            moniker: "node-0".to_string(),
+            ethereum_config: EthereumConfig {
+                execution_authrpc_address: "http://localhost:8545".to_string(),
+                engine_authrpc_address: "http://localhost:8551".to_string(),
+                jwt_token_path: "./jwt.hex".to_string(),
+                eth_genesis_path: default_eth_gensesis_path(),
+            },
+            retry_config: RetryConfig::default(),
+            el_node_type: ElNodeType::default(),
+            num_certificates_to_retain: default_num_certificates_to_retain(),
+            prune_at_block_interval: prune_at_interval_default(),
+            min_block_time: default_min_block_time(),
+            max_future_drift: default_max_future_drift(),
+            fee_recipient: Address::repeat_byte(0),
+            num_temp_blocks_retained: default_num_temp_blocks_retained(),
+            data_dir,
+            proposal_part_size: default_proposal_part_size(),
+            proposal_parts_version: ProposalInit::LEGACY,
+        }
+    }
+
+    #[test]
+    fn data_dir_is_resolved_against_home() {
+        let home = std::env::temp_dir();
+
+        let config = emerald_config(default_data_dir());
+        config.validate(&home).unwrap();
+        assert_eq!(config.data_dir(&home), home.join("."));
+
+        let config = emerald_config(home.join("emerald-node-1").join("data"));
+        config.validate(&home).unwrap();
+        assert_eq!(config.data_dir(Path::new("/unused")), config.data_dir);
+    }
+
+    #[test]
+    fn data_dir_under_a_file_is_rejected() {
+        let file = std::env::temp_dir().join(format!("emerald-data-dir-{}", std::process::id()));
+        std::fs::write(&file, b"").unwrap();
+
+        let home = std::env::temp_dir();
+        let absolute = emerald_config(file.join("data")).validate(&home);
+        let relative =
+            emerald_config(PathBuf::from(file.file_name().unwrap()).join("data")).validate(&home);
+        std::fs::remove_file(&file).unwrap();
+        assert!(absolute.is_err());
+        assert!(relative.is_err());
+
+        assert!(emerald_config(PathBuf::new()).validate(&home).is_err());
+    }
+
+    #[test]
+    fn retry_multiplier_below_one_is_rejected() {
+        let mut config = emerald_config(default_data_dir());
+        config.retry_config.multiplier = 1.0;
+        config.validate(Path::new(".")).unwrap();
+
+        config.retry_config.multiplier = 0.5;
+        assert!(config.validate(Path::new(".")).is_err());
+
+        config.retry_config.multiplier = f64::NAN;
+        assert!(config.validate(Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn proposal_part_size_out_of_range_is_rejected() {
+        let mut config = emerald_config(default_data_dir());
+        for size in [MIN_PROPOSAL_PART_SIZE, MAX_PROPOSAL_PART_SIZE] {
+            config.proposal_part_size = size;
+            config.validate(Path::new(".")).unwrap();
+        }
+
+        for size in [0, MIN_PROPOSAL_PART_SIZE - 1, MAX_PROPOSAL_PART_SIZE + 1] {
+            config.proposal_part_size = size;
+            assert!(config.validate(Path::new(".")).is_err());
+        }
+    }
+
+    #[test]
+    fn unknown_proposal_parts_version_is_rejected() {
+        let mut config = emerald_config(default_data_dir());
+        config.proposal_parts_version = ProposalInit::INDEXED;
+        config.validate(Path::new(".")).unwrap();
+
+        config.proposal_parts_version = ProposalInit::INDEXED + 1;
+        assert!(config.validate(Path::new(".")).is_err());
+    }
+}
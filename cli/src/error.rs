@@ -1,6 +1,18 @@
 //! Custom error messages for CLI helper functions.
 //! This low level implementation allows the developer to choose their own error handling library.
 use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Process exit codes reported for [`Error`], stable across releases so that
+/// scripts can tell failure categories apart
+pub mod exit_code {
+    /// Filesystem or thread failure
+    pub const IO: u8 = 10;
+    /// Configuration file that cannot be decoded, or data that cannot be encoded
+    pub const PARSE: u8 = 11;
+    /// Configuration that decodes but is not valid
+    pub const INVALID_CONFIG: u8 = 12;
+}
 
 /// Error messages for commands
 #[derive(Debug, thiserror::Error)]
@@ -45,3 +57,68 @@ pub enum Error {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 }
+
+impl Error {
+    /// Process exit code for this error, see [`exit_code`]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::ParentDir(_)
+            | Self::OpenFile(_)
+            | Self::WriteFile(_)
+            | Self::LoadFile(_, _)
+            | Self::DirPath
+            | Self::Join => exit_code::IO,
+            Self::ToJSON(_) | Self::FromTOML(_) => exit_code::PARSE,
+            Self::MissingPath(_) | Self::MissingMoniker(_) | Self::InvalidConfig(_) => {
+                exit_code::INVALID_CONFIG
+            }
+        }
+    }
+}
+
+impl From<&Error> for ExitCode {
+    fn from(error: &Error) -> Self {
+        Self::from(error.exit_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn exit_codes_follow_categories() {
+        let path = PathBuf::from("config.toml");
+        let toml_error = toml::from_str::<toml::Table>("=").unwrap_err();
+        let cases = [
+            (Error::ParentDir(path.clone()), exit_code::IO),
+            (Error::OpenFile(path.clone()), exit_code::IO),
+            (Error::WriteFile(path.clone()), exit_code::IO),
+            (
+                Error::LoadFile(path, io::Error::from(io::ErrorKind::NotFound)),
+                exit_code::IO,
+            ),
+            (Error::DirPath, exit_code::IO),
+            (Error::Join, exit_code::IO),
+            (Error::ToJSON("invalid".to_string()), exit_code::PARSE),
+            (Error::FromTOML(toml_error), exit_code::PARSE),
+            (Error::MissingPath(0), exit_code::INVALID_CONFIG),
+            (Error::MissingMoniker(0), exit_code::INVALID_CONFIG),
+            (
+                Error::InvalidConfig("invalid".to_string()),
+                exit_code::INVALID_CONFIG,
+            ),
+        ];
+        for (error, code) in &cases {
+            assert_eq!(error.exit_code(), *code, "{error}");
+        }
+
+        let codes: BTreeSet<u8> =
+            [exit_code::IO, exit_code::PARSE, exit_code::INVALID_CONFIG].into();
+        assert_eq!(codes.len(), 3);
+        assert!(!codes.contains(&0) && !codes.contains(&1));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod system_contracts;
 pub mod validator_manager;
 
+pub use system_contracts::SystemContracts;
 pub use validator_manager::{
     ValidatorManager, GENESIS_ACCOUNT as GENESIS_VALIDATOR_MANAGER_ACCOUNT,
 };
@@ -0,0 +1,93 @@
+use alloy_primitives::{Address, U160};
+
+use crate::validator_manager::GENESIS_ACCOUNT;
+
+/// Reserved addresses of the system contracts written at genesis.
+///
+/// Contracts are assigned sequential addresses from a base: the validator
+/// manager at the base, then one address kept free for the next system
+/// contract, so prefunded accounts cannot take it before it is added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SystemContracts {
+    base: Address,
+}
+
+impl SystemContracts {
+    /// Number of addresses reserved from the base.
+    pub const COUNT: usize = 2;
+
+    pub fn from_base(base: Address) -> Self {
+        Self { base }
+    }
+
+    /// Address of the validator manager, as called by users and the node.
+    pub fn validator_manager(&self) -> Address {
+        self.reserved(0)
+    }
+
+    /// All reserved addresses, in order.
+    pub fn addresses(&self) -> [Address; Self::COUNT] {
+        core::array::from_fn(|index| self.reserved(index))
+    }
+
+    /// Returns the first of `accounts` that uses a reserved address.
+    pub fn find_collision<'a>(
+        &self,
+        accounts: impl IntoIterator<Item = &'a Address>,
+    ) -> Option<Address> {
+        let reserved = self.addresses();
+        accounts
+            .into_iter()
+            .find(|account| reserved.contains(account))
+            .copied()
+    }
+
+    fn reserved(&self, index: usize) -> Address {
+        Address::from(U160::from(self.base) + U160::from(index))
+    }
+}
+
+impl Default for SystemContracts {
+    fn default() -> Self {
+        Self::from_base(GENESIS_ACCOUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::address;
+
+    use super::*;
+
+    #[test]
+    fn addresses_are_sequential_from_base() {
+        let contracts =
+            SystemContracts::from_base(address!("0x0000000000000000000000000000000000002000"));
+
+        assert_eq!(
+            contracts.validator_manager(),
+            address!("0x0000000000000000000000000000000000002000")
+        );
+        assert_eq!(
+            contracts.addresses(),
+            [
+                address!("0x0000000000000000000000000000000000002000"),
+                address!("0x0000000000000000000000000000000000002001"),
+            ]
+        );
+        assert_eq!(contracts, SystemContracts::default());
+    }
+
+    #[test]
+    fn find_collision_reports_reserved_accounts() {
+        let contracts = SystemContracts::default();
+        let funded = address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+        assert_eq!(contracts.find_collision(&[funded]), None);
+        let reserved = contracts.addresses()[1];
+        assert_eq!(
+            contracts.find_collision(&[funded, reserved]),
+            Some(reserved)
+        );
+    }
+}
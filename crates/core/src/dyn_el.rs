@@ -0,0 +1,313 @@
+use core::time::Duration;
+use std::boxed::Box;
+use std::string::String;
+use std::vec::Vec;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+
+use crate::{
+    Block, BlockValidity, ExecutionLayer, FeeHistory, FinalizeBatchError, SetTransitionValidity,
+    SyncError, TxReceipt,
+};
+
+/// Type-erased error of a [`DynEl`].
+pub type BoxError = Box<dyn core::error::Error + Send + Sync>;
+
+/// Object-safe counterpart of [`ExecutionLayer`] for blocks of type `B` and
+/// validator sets of type `VS`, so the host can hold a `Box<dyn DynEl<B, VS>>`
+/// and choose the implementation at runtime.
+///
+/// Only the error type is erased, to [`BoxError`]. The block and validator set
+/// types stay parameters rather than being fixed to Emerald's, as this crate
+/// does not depend on them: the node picks them once when naming the boxed
+/// type, and every implementation behind the box must use the same ones.
+///
+/// The progress callback of `wait_for_sync` is passed by reference. Implemented
+/// for any execution layer by wrapping it in [`DynExecutionLayer`].
+#[async_trait::async_trait]
+pub trait DynEl<B: Block, VS: Send + Sync>: Send + Sync {
+    async fn genesis_block(&self) -> Result<B, BoxError>;
+
+    async fn build_block(&self, parent: &B, timestamp: u64) -> Result<B, BoxError>;
+
+    async fn cancel_build(&self) -> Result<(), BoxError>;
+
+    async fn validate_block(&self, block: &B) -> Result<BlockValidity, BoxError>;
+
+    async fn validate_block_range(&self, blocks: &[B]) -> Result<Vec<BlockValidity>, BoxError>;
+
+    async fn validate_set_transition(
+        &self,
+        prev: &B,
+        next: &B,
+    ) -> Result<SetTransitionValidity, BoxError>;
+
+    async fn finalize_block(&self, block: &B) -> Result<B::Id, BoxError>;
+
+    async fn finalize_blocks(&self, blocks: &[B]) -> Result<B::Id, FinalizeBatchError<BoxError>>;
+
+    async fn validator_set(&self, block: &B) -> Result<VS, BoxError>;
+
+    async fn validator_set_commitment(&self, block: &B) -> Result<B256, BoxError>;
+
+    async fn latest_block_height(&self) -> Result<Option<u64>, BoxError>;
+
+    async fn finalized_height(&self) -> Result<Option<u64>, BoxError>;
+
+    async fn prunable_below(&self, retention: u64) -> Result<Option<u64>, BoxError>;
+
+    async fn get_block_by_height(&self, height: u64) -> Result<Option<B>, BoxError>;
+
+    async fn earliest_available_height(&self) -> Result<u64, BoxError>;
+
+    async fn latest_block(&self) -> Result<Option<B>, BoxError>;
+
+    async fn is_canonical(&self, id: &B::Id) -> Result<bool, BoxError>;
+
+    async fn is_syncing(&self) -> Result<(bool, u64), BoxError>;
+
+    async fn peer_count(&self) -> Result<usize, BoxError>;
+
+    async fn wait_for_sync(
+        &self,
+        timeout: Duration,
+        on_progress: &mut (dyn FnMut(u64, u64) + Send),
+    ) -> Result<(), SyncError<BoxError>>;
+
+    async fn fee_history(&self, block_count: usize, newest: u64) -> Result<FeeHistory, BoxError>;
+
+    async fn balance_of(&self, address: Address, block: &B) -> Result<U256, BoxError>;
+
+    async fn account_nonce(&self, address: Address) -> Result<u64, BoxError>;
+
+    async fn send_raw_transaction(&self, raw: Bytes) -> Result<B::Id, BoxError>;
+
+    async fn send_and_await(&self, raw: Bytes, confirmations: u64) -> Result<TxReceipt, BoxError>;
+
+    async fn client_version(&self) -> Result<String, BoxError>;
+
+    async fn shutdown(&self) -> Result<(), BoxError>;
+}
+
+/// Adapter exposing an [`ExecutionLayer`] as a [`DynEl`] over its own block and
+/// validator set types.
+pub struct DynExecutionLayer<E>(pub E);
+
+impl<E: ExecutionLayer> DynExecutionLayer<E>
+where
+    E::Error: 'static,
+{
+    pub fn boxed(el: E) -> Box<dyn DynEl<E::Block, E::ValidatorSet>>
+    where
+        E: 'static,
+    {
+        Box::new(Self(el))
+    }
+}
+
+#[async_trait::async_trait]
+impl<E: ExecutionLayer> DynEl<E::Block, E::ValidatorSet> for DynExecutionLayer<E>
+where
+    E::Error: 'static,
+{
+    async fn genesis_block(&self) -> Result<E::Block, BoxError> {
+        Ok(self.0.genesis_block().await?)
+    }
+
+    async fn build_block(&self, parent: &E::Block, timestamp: u64) -> Result<E::Block, BoxError> {
+        Ok(self.0.build_block(parent, timestamp).await?)
+    }
+
+    async fn cancel_build(&self) -> Result<(), BoxError> {
+        Ok(self.0.cancel_build().await?)
+    }
+
+    async fn validate_block(&self, block: &E::Block) -> Result<BlockValidity, BoxError> {
+        Ok(self.0.validate_block(block).await?)
+    }
+
+    async fn validate_block_range(
+        &self,
+        blocks: &[E::Block],
+    ) -> Result<Vec<BlockValidity>, BoxError> {
+        Ok(self.0.validate_block_range(blocks).await?)
+    }
+
+    async fn validate_set_transition(
+        &self,
+        prev: &E::Block,
+        next: &E::Block,
+    ) -> Result<SetTransitionValidity, BoxError> {
+        Ok(self.0.validate_set_transition(prev, next).await?)
+    }
+
+    async fn finalize_block(&self, block: &E::Block) -> Result<<E::Block as Block>::Id, BoxError> {
+        Ok(self.0.finalize_block(block).await?)
+    }
+
+    async fn finalize_blocks(
+        &self,
+        blocks: &[E::Block],
+    ) -> Result<<E::Block as Block>::Id, FinalizeBatchError<BoxError>> {
+        self.0
+            .finalize_blocks(blocks)
+            .await
+            .map_err(|error| match error {
+                FinalizeBatchError::Empty => FinalizeBatchError::Empty,
+                FinalizeBatchError::NotContiguous { index } => {
+                    FinalizeBatchError::NotContiguous { index }
+                }
+                FinalizeBatchError::Block { index, source } => FinalizeBatchError::Block {
+                    index,
+                    source: source.into(),
+                },
+            })
+    }
+
+    async fn validator_set(&self, block: &E::Block) -> Result<E::ValidatorSet, BoxError> {
+        Ok(self.0.validator_set(block).await?)
+    }
+
+    async fn validator_set_commitment(&self, block: &E::Block) -> Result<B256, BoxError> {
+        Ok(self.0.validator_set_commitment(block).await?)
+    }
+
+    async fn latest_block_height(&self) -> Result<Option<u64>, BoxError> {
+        Ok(self.0.latest_block_height().await?)
+    }
+
+    async fn finalized_height(&self) -> Result<Option<u64>, BoxError> {
+        Ok(self.0.finalized_height().await?)
+    }
+
+    async fn prunable_below(&self, retention: u64) -> Result<Option<u64>, BoxError> {
+        Ok(self.0.prunable_below(retention).await?)
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> Result<Option<E::Block>, BoxError> {
+        Ok(self.0.get_block_by_height(height).await?)
+    }
+
+    async fn earliest_available_height(&self) -> Result<u64, BoxError> {
+        Ok(self.0.earliest_available_height().await?)
+    }
+
+    async fn latest_block(&self) -> Result<Option<E::Block>, BoxError> {
+        Ok(self.0.latest_block().await?)
+    }
+
+    async fn is_canonical(&self, id: &<E::Block as Block>::Id) -> Result<bool, BoxError> {
+        Ok(self.0.is_canonical(id).await?)
+    }
+
+    async fn is_syncing(&self) -> Result<(bool, u64), BoxError> {
+        Ok(self.0.is_syncing().await?)
+    }
+
+    async fn peer_count(&self) -> Result<usize, BoxError> {
+        Ok(self.0.peer_count().await?)
+    }
+
+    async fn wait_for_sync(
+        &self,
+        timeout: Duration,
+        on_progress: &mut (dyn FnMut(u64, u64) + Send),
+    ) -> Result<(), SyncError<BoxError>> {
+        self.0
+            .wait_for_sync(timeout, on_progress)
+            .await
+            .map_err(|error| match error {
+                SyncError::SyncTimeout(timeout) => SyncError::SyncTimeout(timeout),
+                SyncError::NoPeers(timeout) => SyncError::NoPeers(timeout),
+                SyncError::Client(source) => SyncError::Client(source.into()),
+            })
+    }
+
+    async fn fee_history(&self, block_count: usize, newest: u64) -> Result<FeeHistory, BoxError> {
+        Ok(self.0.fee_history(block_count, newest).await?)
+    }
+
+    async fn balance_of(&self, address: Address, block: &E::Block) -> Result<U256, BoxError> {
+        Ok(self.0.balance_of(address, block).await?)
+    }
+
+    async fn account_nonce(&self, address: Address) -> Result<u64, BoxError> {
+        Ok(self.0.account_nonce(address).await?)
+    }
+
+    async fn send_raw_transaction(&self, raw: Bytes) -> Result<<E::Block as Block>::Id, BoxError> {
+        Ok(self.0.send_raw_transaction(raw).await?)
+    }
+
+    async fn send_and_await(&self, raw: Bytes, confirmations: u64) -> Result<TxReceipt, BoxError> {
+        Ok(self.0.send_and_await(raw, confirmations).await?)
+    }
+
+    async fn client_version(&self) -> Result<String, BoxError> {
+        Ok(self.0.client_version().await?)
+    }
+
+    async fn shutdown(&self) -> Result<(), BoxError> {
+        Ok(self.0.shutdown().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroUsize;
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::mock::{MockBlock, MockExecutionLayer, MockValidatorSet};
+    use crate::FinalizedCache;
+
+    #[tokio::test]
+    async fn boxed_execution_layers_are_interchangeable() {
+        let mock = || MockExecutionLayer::new(MockValidatorSet(vec![100])).with_syncing(2, 10);
+        let els: Vec<Box<dyn DynEl<MockBlock, MockValidatorSet>>> = vec![
+            DynExecutionLayer::boxed(mock()),
+            DynExecutionLayer::boxed(FinalizedCache::new(mock(), NonZeroUsize::new(4).unwrap())),
+        ];
+
+        for el in &els {
+            assert_eq!(el.latest_block_height().await.unwrap(), Some(8));
+            assert_eq!(el.genesis_block().await.unwrap(), MockBlock(0));
+            assert_eq!(el.client_version().await.unwrap(), "mock-el/v1.2.3");
+            assert_eq!(el.validator_set(&MockBlock(8)).await.unwrap().0, vec![100]);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_sync_is_forwarded() {
+        let el = DynExecutionLayer::boxed(
+            MockExecutionLayer::new(MockValidatorSet(vec![100])).with_syncing(2, 10),
+        );
+
+        let mut progress = Vec::new();
+        el.wait_for_sync(Duration::from_secs(60), &mut |current, highest| {
+            progress.push((current, highest));
+        })
+        .await
+        .unwrap();
+        assert_eq!(progress, vec![(9, 10), (10, 10)]);
+
+        let stuck = DynExecutionLayer::boxed(
+            MockExecutionLayer::new(MockValidatorSet(vec![100]))
+                .with_syncing(1_000, 10)
+                .with_peers(0),
+        );
+        let result = stuck
+            .wait_for_sync(Duration::from_secs(5), &mut |_, _| {})
+            .await;
+        assert!(matches!(result, Err(SyncError::NoPeers(_))));
+    }
+
+    #[tokio::test]
+    async fn errors_are_boxed() {
+        let el = DynExecutionLayer::boxed(MockExecutionLayer::new(MockValidatorSet(vec![100])));
+
+        let err = el.is_canonical(&42).await.unwrap_err();
+        assert_eq!(err.to_string(), "mock error");
+    }
+}
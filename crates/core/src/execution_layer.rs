@@ -1,7 +1,13 @@
 use core::error::Error;
 use core::fmt;
+use core::time::Duration;
+use std::string::String;
 use std::vec::Vec;
 
+use alloy_primitives::{Address, Bytes, B256, U256};
+
+use crate::{BlockValidity, InvalidBlockReason, SetTransitionValidity};
+
 pub trait Block: Send + Sync + Clone {
     type Id: Send + Sync + Clone + Eq + fmt::Debug;
     type Error: Error + Send + Sync;
@@ -11,12 +17,153 @@ pub trait Block: Send + Sync + Clone {
     fn height(&self) -> u64;
     fn encode(&self) -> Vec<u8>;
     fn decode(bytes: &[u8]) -> Result<Self, Self::Error>;
+
+    /// Root of the block's withdrawals trie, or `None` for pre-Shanghai blocks.
+    fn withdrawals_root(&self) -> Option<B256> {
+        None
+    }
+}
+
+/// Delay before the first re-poll of a syncing EL in `wait_for_sync`, doubled up to the maximum.
+const SYNC_POLL_INITIAL_DELAY: Duration = Duration::from_millis(100);
+const SYNC_POLL_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Error returned by [`ExecutionLayer::wait_for_sync`].
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError<E> {
+    #[error("execution layer still syncing after {0:?}")]
+    SyncTimeout(Duration),
+    /// The EL was still syncing at the timeout and has no peers to sync from.
+    #[error("execution layer has no peers to sync from after {0:?}")]
+    NoPeers(Duration),
+    #[error(transparent)]
+    Client(E),
+}
+
+/// Sync status of a client, for waiting on it with [`wait_for_sync`] without
+/// implementing the whole [`ExecutionLayer`].
+#[async_trait::async_trait]
+pub trait SyncProbe: Sync {
+    type Error: Send;
+
+    /// Returns `(is_syncing, highest_known_height)`.
+    async fn is_syncing(&self) -> Result<(bool, u64), Self::Error>;
+
+    /// Returns `None` when the client has no blocks yet (pre-genesis).
+    async fn latest_block_height(&self) -> Result<Option<u64>, Self::Error>;
+
+    async fn peer_count(&self) -> Result<usize, Self::Error>;
+}
+
+/// Polls `is_syncing` with exponential backoff until the client is synced,
+/// reporting `(current, highest)` heights to `on_progress` while it is not.
+/// On timeout, fails with [`SyncError::NoPeers`] if the client has no peers.
+pub async fn wait_for_sync<P, F>(
+    probe: &P,
+    timeout: Duration,
+    mut on_progress: F,
+) -> Result<(), SyncError<P::Error>>
+where
+    P: SyncProbe + ?Sized,
+    F: FnMut(u64, u64) + Send,
+{
+    let poll = async {
+        let mut delay = SYNC_POLL_INITIAL_DELAY;
+        loop {
+            let (syncing, highest) = probe.is_syncing().await.map_err(SyncError::Client)?;
+            if !syncing {
+                return Ok(());
+            }
+
+            let current = probe
+                .latest_block_height()
+                .await
+                .map_err(SyncError::Client)?
+                .unwrap_or_default();
+            on_progress(current, highest);
+
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(SYNC_POLL_MAX_DELAY);
+        }
+    };
+
+    let Ok(result) = tokio::time::timeout(timeout, poll).await else {
+        let peers = probe.peer_count().await.map_err(SyncError::Client)?;
+        return Err(if peers == 0 {
+            SyncError::NoPeers(timeout)
+        } else {
+            SyncError::SyncTimeout(timeout)
+        });
+    };
+    result
+}
+
+/// [`SyncProbe`] over an execution layer, backing [`ExecutionLayer::wait_for_sync`].
+struct ElSyncProbe<'a, T: ?Sized>(&'a T);
+
+#[async_trait::async_trait]
+impl<T: ExecutionLayer + ?Sized> SyncProbe for ElSyncProbe<'_, T> {
+    type Error = T::Error;
+
+    async fn is_syncing(&self) -> Result<(bool, u64), Self::Error> {
+        self.0.is_syncing().await
+    }
+
+    async fn latest_block_height(&self) -> Result<Option<u64>, Self::Error> {
+        self.0.latest_block_height().await
+    }
+
+    async fn peer_count(&self) -> Result<usize, Self::Error> {
+        self.0.peer_count().await
+    }
+}
+
+/// Error returned by [`ExecutionLayer::finalize_blocks`].
+#[derive(Debug, thiserror::Error)]
+pub enum FinalizeBatchError<E> {
+    #[error("no blocks to finalize")]
+    Empty,
+    #[error("block {index} of the batch does not extend the previous one")]
+    NotContiguous { index: usize },
+    #[error("failed to finalize block {index} of the batch: {source}")]
+    Block {
+        index: usize,
+        #[source]
+        source: E,
+    },
+}
+
+/// Base fees and gas usage of a range of blocks, as returned by `eth_feeHistory`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeHistory {
+    /// Height of the first block in the range.
+    pub oldest_block: u64,
+    /// Base fee of each block in the range, followed by the base fee of the next block.
+    pub base_fee_per_gas: Vec<u128>,
+    /// Gas used over gas limit of each block in the range.
+    pub gas_used_ratio: Vec<f64>,
+}
+
+/// Outcome of a transaction included in a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxReceipt {
+    pub transaction_hash: B256,
+    /// Height of the including block.
+    pub block_height: u64,
+    /// Whether execution succeeded rather than reverted.
+    pub success: bool,
+    pub gas_used: u64,
+}
+
+/// Deterministic 32-byte commitment to a value's contents.
+pub trait Checksum {
+    fn checksum(&self) -> B256;
 }
 
 #[async_trait::async_trait]
 pub trait ExecutionLayer: Send + Sync {
     type Block: Block;
-    type ValidatorSet: Send + Sync + Clone;
+    type ValidatorSet: Send + Sync + Clone + Checksum;
     type Error: Error + Send + Sync;
 
     async fn genesis_block(&self) -> Result<Self::Block, Self::Error>;
@@ -28,7 +175,45 @@ pub trait ExecutionLayer: Send + Sync {
         timestamp: u64,
     ) -> Result<Self::Block, Self::Error>;
 
-    async fn validate_block(&self, block: &Self::Block) -> Result<bool, Self::Error>;
+    /// Aborts any outstanding `build_block`, e.g. when consensus is no longer the
+    /// proposer. Does nothing when no build is in progress.
+    async fn cancel_build(&self) -> Result<(), Self::Error>;
+
+    async fn validate_block(&self, block: &Self::Block) -> Result<BlockValidity, Self::Error>;
+
+    /// Validates a run of consecutive blocks in order, e.g. during sync, stopping
+    /// after the first invalid one. Returns the outcome of each block checked, so
+    /// the last outcome is the invalid one if any. A block that does not extend its
+    /// predecessor in `blocks` is invalid with [`InvalidBlockReason::BadParent`].
+    ///
+    /// Implementations may pipeline the engine calls.
+    async fn validate_block_range(
+        &self,
+        blocks: &[Self::Block],
+    ) -> Result<Vec<BlockValidity>, Self::Error> {
+        let mut outcomes = Vec::with_capacity(blocks.len());
+        for (index, block) in blocks.iter().enumerate() {
+            let validity = if index > 0 && block.parent_id() != blocks[index - 1].id() {
+                BlockValidity::Invalid(InvalidBlockReason::BadParent)
+            } else {
+                self.validate_block(block).await?
+            };
+            let valid = validity.is_valid();
+            outcomes.push(validity);
+            if !valid {
+                break;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Whether the validator set at `next` is a legal successor of the one at its
+    /// parent `prev`, see [`SetTransitionValidity::from_power_delta`].
+    async fn validate_set_transition(
+        &self,
+        prev: &Self::Block,
+        next: &Self::Block,
+    ) -> Result<SetTransitionValidity, Self::Error>;
 
     /// Returns the EL-confirmed head id so consensus can verify agreement on the tip.
     async fn finalize_block(
@@ -36,15 +221,413 @@ pub trait ExecutionLayer: Send + Sync {
         block: &Self::Block,
     ) -> Result<<Self::Block as Block>::Id, Self::Error>;
 
+    /// Finalizes a contiguous run of blocks in order, e.g. during catch-up, and
+    /// returns the EL-confirmed id of the last one. Stops at the first block that
+    /// fails, reporting its index in `blocks`.
+    async fn finalize_blocks(
+        &self,
+        blocks: &[Self::Block],
+    ) -> Result<<Self::Block as Block>::Id, FinalizeBatchError<Self::Error>> {
+        let mut head = None;
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 && block.parent_id() != blocks[index - 1].id() {
+                return Err(FinalizeBatchError::NotContiguous { index });
+            }
+            let id = self
+                .finalize_block(block)
+                .await
+                .map_err(|source| FinalizeBatchError::Block { index, source })?;
+            head = Some(id);
+        }
+        head.ok_or(FinalizeBatchError::Empty)
+    }
+
     async fn validator_set(&self, block: &Self::Block) -> Result<Self::ValidatorSet, Self::Error>;
 
+    /// Checksum of the validator set active at `block`, so consensus can compare
+    /// committees with the EL without transferring the full set.
+    async fn validator_set_commitment(&self, block: &Self::Block) -> Result<B256, Self::Error> {
+        Ok(self.validator_set(block).await?.checksum())
+    }
+
     /// Returns `None` when the EL has no blocks yet (pre-genesis).
     async fn latest_block_height(&self) -> Result<Option<u64>, Self::Error>;
 
+    /// Height of the last block finalized in the EL, or `None` before the first
+    /// finalization.
+    async fn finalized_height(&self) -> Result<Option<u64>, Self::Error>;
+
+    /// Height below which blocks may be pruned while keeping the last `retention`
+    /// blocks up to the finalized head, or `None` when there is nothing to prune.
+    async fn prunable_below(&self, retention: u64) -> Result<Option<u64>, Self::Error> {
+        Ok(self
+            .finalized_height()
+            .await?
+            .and_then(|finalized| finalized.checked_sub(retention))
+            .filter(|height| *height > 0))
+    }
+
     async fn get_block_by_height(&self, height: u64) -> Result<Option<Self::Block>, Self::Error>;
 
+    /// Height of the oldest block the EL still stores. Blocks below it were
+    /// pruned, so peers should not request them. Defaults to genesis, for ELs
+    /// that never prune.
+    async fn earliest_available_height(&self) -> Result<u64, Self::Error> {
+        Ok(0)
+    }
+
+    /// The tip of the chain in a single round-trip, or `None` pre-genesis.
+    async fn latest_block(&self) -> Result<Option<Self::Block>, Self::Error>;
+
+    /// Whether the block `id` is on the canonical chain. Returns `false` for blocks
+    /// that were reorganized out, and an error for ids the EL has never seen.
+    async fn is_canonical(&self, id: &<Self::Block as Block>::Id) -> Result<bool, Self::Error>;
+
     /// Returns `(is_syncing, highest_known_height)`.
     async fn is_syncing(&self) -> Result<(bool, u64), Self::Error>;
 
+    /// Number of peers the EL is connected to, as reported by `net_peerCount`.
+    async fn peer_count(&self) -> Result<usize, Self::Error>;
+
+    /// Polls `is_syncing` with exponential backoff until the EL is synced,
+    /// reporting `(current, highest)` heights to `on_progress` while it is not.
+    /// On timeout, fails with [`SyncError::NoPeers`] if the EL has no peers.
+    async fn wait_for_sync<F>(
+        &self,
+        timeout: Duration,
+        on_progress: F,
+    ) -> Result<(), SyncError<Self::Error>>
+    where
+        F: FnMut(u64, u64) + Send,
+    {
+        wait_for_sync(&ElSyncProbe(self), timeout, on_progress).await
+    }
+
+    /// Fee history of up to `block_count` blocks ending at height `newest`, for
+    /// fee market monitoring.
+    async fn fee_history(&self, block_count: usize, newest: u64)
+        -> Result<FeeHistory, Self::Error>;
+
+    /// Balance of `address` in the state after `block`, zero for unknown accounts.
+    async fn balance_of(&self, address: Address, block: &Self::Block) -> Result<U256, Self::Error>;
+
+    /// Nonce for the next transaction from `address`, counting those still pending.
+    async fn account_nonce(&self, address: Address) -> Result<u64, Self::Error>;
+
+    /// Submits a signed transaction and returns the id of the block including it,
+    /// once mined.
+    async fn send_raw_transaction(
+        &self,
+        raw: Bytes,
+    ) -> Result<<Self::Block as Block>::Id, Self::Error>;
+
+    /// Submits a signed transaction and returns its receipt once the including
+    /// block has `confirmations` blocks on top of it, itself included.
+    async fn send_and_await(
+        &self,
+        raw: Bytes,
+        confirmations: u64,
+    ) -> Result<TxReceipt, Self::Error>;
+
+    /// Returns the EL client name and version, as reported by `web3_clientVersion`.
+    async fn client_version(&self) -> Result<String, Self::Error>;
+
     async fn shutdown(&self) -> Result<(), Self::Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::mock::{MockBlock, MockExecutionLayer, MockValidatorSet};
+
+    #[tokio::test]
+    async fn validator_set_commitment_matches_checksum() {
+        let validators = MockValidatorSet(vec![100, 200, 300]);
+        let el = MockExecutionLayer::new(validators.clone());
+
+        let commitment = el.validator_set_commitment(&MockBlock(1)).await.unwrap();
+        assert_eq!(commitment, validators.checksum());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_build_returns_promptly() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]))
+            .with_build_delay(Duration::from_secs(60));
+        let start = Instant::now();
+
+        let (cancelled, _) = tokio::join!(el.build_block(&MockBlock(1), 0), async {
+            tokio::task::yield_now().await;
+            el.cancel_build().await.unwrap();
+        });
+        assert!(cancelled.is_err());
+        assert!(start.elapsed() < Duration::from_secs(60));
+
+        // Cancelling while idle is a no-op and does not affect the next build
+        el.cancel_build().await.unwrap();
+        assert_eq!(
+            el.build_block(&MockBlock(1), 0).await.unwrap(),
+            MockBlock(2)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_sync_reports_progress() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_syncing(3, 10);
+
+        let mut progress = Vec::new();
+        el.wait_for_sync(Duration::from_secs(60), |current, highest| {
+            progress.push((current, highest));
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(progress, vec![(8, 10), (9, 10), (10, 10)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_sync_times_out() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_syncing(1_000, 10);
+
+        let result = el.wait_for_sync(Duration::from_secs(5), |_, _| {}).await;
+        assert!(matches!(result, Err(SyncError::SyncTimeout(_))));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_sync_reports_missing_peers() {
+        let stuck = MockExecutionLayer::new(MockValidatorSet(vec![100]))
+            .with_syncing(1_000, 10)
+            .with_peers(0);
+
+        let result = stuck.wait_for_sync(Duration::from_secs(5), |_, _| {}).await;
+        assert!(matches!(result, Err(SyncError::NoPeers(_))));
+
+        let syncing = MockExecutionLayer::new(MockValidatorSet(vec![100]))
+            .with_syncing(1_000, 10)
+            .with_peers(3);
+
+        let result = syncing
+            .wait_for_sync(Duration::from_secs(5), |_, _| {})
+            .await;
+        assert!(matches!(result, Err(SyncError::SyncTimeout(_))));
+    }
+
+    #[tokio::test]
+    async fn validator_set_churn_is_bounded() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100, 100, 100, 100]))
+            .with_validator_set_at(2, MockValidatorSet(vec![100, 100, 100, 130]))
+            .with_validator_set_at(3, MockValidatorSet(vec![0, 0, 0, 130, 100, 100, 100]));
+
+        let small = el
+            .validate_set_transition(&MockBlock(1), &MockBlock(2))
+            .await
+            .unwrap();
+        assert!(small.is_valid());
+
+        // Three of the four validators are swapped out for new ones
+        let swap = el
+            .validate_set_transition(&MockBlock(2), &MockBlock(3))
+            .await
+            .unwrap();
+        assert_eq!(
+            swap,
+            SetTransitionValidity::ExcessiveChurn {
+                power_delta: 600,
+                total_power: 430
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_block_range_stops_at_invalid_block() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_invalid_block(3);
+        let blocks: Vec<MockBlock> = (1..=5).map(MockBlock).collect();
+
+        assert_eq!(
+            el.validate_block_range(&blocks).await.unwrap(),
+            vec![
+                BlockValidity::Valid,
+                BlockValidity::Valid,
+                BlockValidity::Invalid(InvalidBlockReason::Rejected),
+            ]
+        );
+
+        assert_eq!(
+            el.validate_block_range(&[MockBlock(1), MockBlock(2)])
+                .await
+                .unwrap(),
+            vec![BlockValidity::Valid; 2]
+        );
+        assert_eq!(
+            el.validate_block_range(&[MockBlock(4), MockBlock(6), MockBlock(7)])
+                .await
+                .unwrap(),
+            vec![
+                BlockValidity::Valid,
+                BlockValidity::Invalid(InvalidBlockReason::BadParent),
+            ]
+        );
+        assert!(el.validate_block_range(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn finalize_blocks_returns_head() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+        let blocks: Vec<MockBlock> = (1..=5).map(MockBlock).collect();
+
+        assert_eq!(el.finalize_blocks(&blocks).await.unwrap(), 5);
+        assert!(matches!(
+            el.finalize_blocks(&[]).await,
+            Err(FinalizeBatchError::Empty)
+        ));
+        assert!(matches!(
+            el.finalize_blocks(&[MockBlock(1), MockBlock(3)]).await,
+            Err(FinalizeBatchError::NotContiguous { index: 1 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn prunable_below_keeps_retention_window() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+        assert_eq!(el.prunable_below(50).await.unwrap(), None);
+
+        el.finalize_block(&MockBlock(100)).await.unwrap();
+        assert_eq!(el.finalized_height().await.unwrap(), Some(100));
+        assert_eq!(el.prunable_below(50).await.unwrap(), Some(50));
+        assert_eq!(el.prunable_below(100).await.unwrap(), None);
+        assert_eq!(el.prunable_below(200).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn earliest_available_height_rises_after_pruning() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+        assert_eq!(el.earliest_available_height().await.unwrap(), 0);
+        assert_eq!(el.get_block_by_height(0).await.unwrap(), Some(MockBlock(0)));
+
+        el.prune_below(40);
+        assert_eq!(el.earliest_available_height().await.unwrap(), 40);
+        assert!(el.get_block_by_height(39).await.is_err());
+        assert_eq!(
+            el.get_block_by_height(40).await.unwrap(),
+            Some(MockBlock(40))
+        );
+
+        // Pruned blocks do not come back
+        el.prune_below(10);
+        assert_eq!(el.earliest_available_height().await.unwrap(), 40);
+    }
+
+    #[tokio::test]
+    async fn finalize_blocks_stops_at_invalid_block() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_invalid_block(3);
+        let blocks: Vec<MockBlock> = (1..=5).map(MockBlock).collect();
+
+        let result = el.finalize_blocks(&blocks).await;
+        assert!(matches!(
+            result,
+            Err(FinalizeBatchError::Block { index: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn send_and_await_returns_receipt() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+        let raw = Bytes::from_static(&[0x02, 0x01]);
+
+        assert_eq!(el.send_raw_transaction(raw.clone()).await.unwrap(), 1);
+        let receipt = el.send_and_await(raw.clone(), 1).await.unwrap();
+        assert_eq!(receipt.transaction_hash, alloy_primitives::keccak256(&raw));
+        assert_eq!(receipt.block_height, 2);
+        assert!(receipt.success);
+
+        assert!(el.send_raw_transaction(Bytes::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn reorged_blocks_are_not_canonical() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_chain(vec![0, 1, 2, 3]);
+        assert!(el.is_canonical(&3).await.unwrap());
+
+        // Blocks 2 and 3 are replaced by a fork with ids 20 and 30
+        el.reorg(2, vec![20, 30]);
+
+        assert!(el.is_canonical(&1).await.unwrap());
+        assert!(!el.is_canonical(&2).await.unwrap());
+        assert!(!el.is_canonical(&3).await.unwrap());
+        assert!(el.is_canonical(&20).await.unwrap());
+        assert!(el.is_canonical(&30).await.unwrap());
+        assert!(el.is_canonical(&99).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn fee_history_covers_requested_range() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+
+        let history = el.fee_history(4, 10).await.unwrap();
+        assert_eq!(history.oldest_block, 7);
+        assert_eq!(history.gas_used_ratio.len(), 4);
+        assert_eq!(history.base_fee_per_gas.len(), 5);
+
+        // Only blocks from genesis onwards are reported
+        let history = el.fee_history(4, 1).await.unwrap();
+        assert_eq!(history.oldest_block, 0);
+        assert_eq!(history.gas_used_ratio.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn latest_block_matches_two_step_lookup() {
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_syncing(0, 5);
+
+        let height = el.latest_block_height().await.unwrap().unwrap();
+        let two_step = el.get_block_by_height(height).await.unwrap();
+        assert_eq!(el.latest_block().await.unwrap(), two_step);
+        assert_eq!(two_step, Some(MockBlock(5)));
+
+        // No blocks yet, pre-genesis
+        let empty = MockExecutionLayer::new(MockValidatorSet(vec![100]));
+        assert_eq!(empty.latest_block().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn balance_of_reads_state_at_block() {
+        let funded = Address::repeat_byte(0x11);
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100]))
+            .with_balance_at(2, funded, U256::from(1_000))
+            .with_balance_at(5, funded, U256::from(400));
+
+        assert_eq!(
+            el.balance_of(funded, &MockBlock(1)).await.unwrap(),
+            U256::ZERO
+        );
+        assert_eq!(
+            el.balance_of(funded, &MockBlock(2)).await.unwrap(),
+            U256::from(1_000)
+        );
+        assert_eq!(
+            el.balance_of(funded, &MockBlock(7)).await.unwrap(),
+            U256::from(400)
+        );
+
+        // Unknown accounts have a zero balance
+        let unfunded = Address::repeat_byte(0x22);
+        assert_eq!(
+            el.balance_of(unfunded, &MockBlock(7)).await.unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn account_nonce_defaults_to_zero() {
+        let sender = Address::repeat_byte(0x11);
+        let el = MockExecutionLayer::new(MockValidatorSet(vec![100])).with_nonce(sender, 3);
+
+        assert_eq!(el.account_nonce(sender).await.unwrap(), 3);
+        assert_eq!(
+            el.account_nonce(Address::repeat_byte(0x22)).await.unwrap(),
+            0
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use core::num::NonZeroUsize;
+use std::collections::{HashMap, VecDeque};
+use std::string::String;
+use std::sync::{Mutex, MutexGuard};
+use std::vec::Vec;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use tokio::sync::broadcast;
+
+use crate::{Block, BlockValidity, ExecutionLayer, FeeHistory, SetTransitionValidity, TxReceipt};
+
+/// Finalized blocks buffered per subscriber by default, see
+/// [`FinalizedCache::subscribe_finalized`].
+const DEFAULT_SUBSCRIPTION_CAPACITY: usize = 64;
+
+/// [`ExecutionLayer`] wrapper that memoizes blocks at finalized heights.
+///
+/// Finalized blocks are immutable, so lookups at or below the last height
+/// passed to [`ExecutionLayer::finalize_block`] are served from a bounded LRU
+/// cache. Higher heights may still be reorganized and always reach the inner
+/// execution layer.
+pub struct FinalizedCache<E: ExecutionLayer> {
+    inner: E,
+    capacity: NonZeroUsize,
+    state: Mutex<CacheState<E::Block>>,
+    finalized: broadcast::Sender<E::Block>,
+}
+
+struct CacheState<B> {
+    finalized_height: Option<u64>,
+    blocks: HashMap<u64, B>,
+    /// Cached heights, least recently used first.
+    recency: VecDeque<u64>,
+}
+
+impl<B> CacheState<B> {
+    fn touch(&mut self, height: u64) {
+        if let Some(pos) = self.recency.iter().position(|h| *h == height) {
+            self.recency.remove(pos);
+        }
+        self.recency.push_back(height);
+    }
+}
+
+impl<E: ExecutionLayer> FinalizedCache<E> {
+    pub fn new(inner: E, capacity: NonZeroUsize) -> Self {
+        Self {
+            inner,
+            capacity,
+            state: Mutex::new(CacheState {
+                finalized_height: None,
+                blocks: HashMap::new(),
+                recency: VecDeque::new(),
+            }),
+            finalized: broadcast::Sender::new(DEFAULT_SUBSCRIPTION_CAPACITY),
+        }
+    }
+
+    /// Buffer up to `capacity` finalized blocks per subscriber. Subscriptions
+    /// taken before this call are closed.
+    pub fn with_subscription_capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.finalized = broadcast::Sender::new(capacity.get());
+        self
+    }
+
+    /// Subscribe to blocks finalized through this cache from now on.
+    ///
+    /// Finalization never waits for subscribers: when a subscriber's buffer is
+    /// full the oldest block is dropped, and its next `recv` returns
+    /// [`broadcast::error::RecvError::Lagged`] with the number of blocks missed
+    /// before resuming with the oldest block still buffered.
+    pub fn subscribe_finalized(&self) -> broadcast::Receiver<E::Block> {
+        self.finalized.subscribe()
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Height of the last block finalized through this cache, if any.
+    pub fn cached_finalized_height(&self) -> Option<u64> {
+        self.state().finalized_height
+    }
+
+    /// Drop cached blocks at `height` and above after a reorg, and lower the
+    /// finalized height below `height`.
+    pub fn invalidate_from(&self, height: u64) {
+        let mut state = self.state();
+        state.blocks.retain(|h, _| *h < height);
+        state.recency.retain(|h| *h < height);
+        if state.finalized_height.is_some_and(|h| h >= height) {
+            state.finalized_height = height.checked_sub(1);
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState<E::Block>> {
+        self.state.lock().expect("finalized cache lock poisoned")
+    }
+
+    fn cached(&self, height: u64) -> Option<E::Block> {
+        let mut state = self.state();
+        let block = state.blocks.get(&height).cloned()?;
+        state.touch(height);
+        Some(block)
+    }
+
+    fn insert(&self, height: u64, block: &E::Block) {
+        let mut state = self.state();
+        if state.finalized_height.is_none_or(|h| height > h) {
+            return;
+        }
+
+        state.blocks.insert(height, block.clone());
+        state.touch(height);
+        while state.recency.len() > self.capacity.get() {
+            if let Some(evicted) = state.recency.pop_front() {
+                state.blocks.remove(&evicted);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<E: ExecutionLayer> ExecutionLayer for FinalizedCache<E> {
+    type Block = E::Block;
+    type ValidatorSet = E::ValidatorSet;
+    type Error = E::Error;
+
+    async fn genesis_block(&self) -> Result<Self::Block, Self::Error> {
+        self.inner.genesis_block().await
+    }
+
+    async fn build_block(
+        &self,
+        parent: &Self::Block,
+        timestamp: u64,
+    ) -> Result<Self::Block, Self::Error> {
+        self.inner.build_block(parent, timestamp).await
+    }
+
+    async fn cancel_build(&self) -> Result<(), Self::Error> {
+        self.inner.cancel_build().await
+    }
+
+    async fn validate_block(&self, block: &Self::Block) -> Result<BlockValidity, Self::Error> {
+        self.inner.validate_block(block).await
+    }
+
+    async fn validate_block_range(
+        &self,
+        blocks: &[Self::Block],
+    ) -> Result<Vec<BlockValidity>, Self::Error> {
+        self.inner.validate_block_range(blocks).await
+    }
+
+    async fn validate_set_transition(
+        &self,
+        prev: &Self::Block,
+        next: &Self::Block,
+    ) -> Result<SetTransitionValidity, Self::Error> {
+        self.inner.validate_set_transition(prev, next).await
+    }
+
+    async fn finalize_block(
+        &self,
+        block: &Self::Block,
+    ) -> Result<<Self::Block as Block>::Id, Self::Error> {
+        let id = self.inner.finalize_block(block).await?;
+
+        let height = block.height();
+        {
+            let mut state = self.state();
+            state.finalized_height = Some(state.finalized_height.map_or(height, |h| h.max(height)));
+        }
+        self.insert(height, block);
+        // Only fails when there are no subscribers
+        let _ = self.finalized.send(block.clone());
+
+        Ok(id)
+    }
+
+    async fn validator_set(&self, block: &Self::Block) -> Result<Self::ValidatorSet, Self::Error> {
+        self.inner.validator_set(block).await
+    }
+
+    async fn validator_set_commitment(&self, block: &Self::Block) -> Result<B256, Self::Error> {
+        self.inner.validator_set_commitment(block).await
+    }
+
+    async fn latest_block_height(&self) -> Result<Option<u64>, Self::Error> {
+        self.inner.latest_block_height().await
+    }
+
+    async fn finalized_height(&self) -> Result<Option<u64>, Self::Error> {
+        self.inner.finalized_height().await
+    }
+
+    async fn prunable_below(&self, retention: u64) -> Result<Option<u64>, Self::Error> {
+        self.inner.prunable_below(retention).await
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> Result<Option<Self::Block>, Self::Error> {
+        if let Some(block) = self.cached(height) {
+            return Ok(Some(block));
+        }
+
+        let block = self.inner.get_block_by_height(height).await?;
+        if let Some(block) = &block {
+            self.insert(height, block);
+        }
+        Ok(block)
+    }
+
+    async fn earliest_available_height(&self) -> Result<u64, Self::Error> {
+        self.inner.earliest_available_height().await
+    }
+
+    /// Not cached, as the tip can still be reorganized out.
+    async fn latest_block(&self) -> Result<Option<Self::Block>, Self::Error> {
+        self.inner.latest_block().await
+    }
+
+    async fn is_canonical(&self, id: &<Self::Block as Block>::Id) -> Result<bool, Self::Error> {
+        self.inner.is_canonical(id).await
+    }
+
+    async fn is_syncing(&self) -> Result<(bool, u64), Self::Error> {
+        self.inner.is_syncing().await
+    }
+
+    async fn peer_count(&self) -> Result<usize, Self::Error> {
+        self.inner.peer_count().await
+    }
+
+    async fn fee_history(
+        &self,
+        block_count: usize,
+        newest: u64,
+    ) -> Result<FeeHistory, Self::Error> {
+        self.inner.fee_history(block_count, newest).await
+    }
+
+    async fn balance_of(&self, address: Address, block: &Self::Block) -> Result<U256, Self::Error> {
+        self.inner.balance_of(address, block).await
+    }
+
+    async fn account_nonce(&self, address: Address) -> Result<u64, Self::Error> {
+        self.inner.account_nonce(address).await
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        raw: Bytes,
+    ) -> Result<<Self::Block as Block>::Id, Self::Error> {
+        self.inner.send_raw_transaction(raw).await
+    }
+
+    async fn send_and_await(
+        &self,
+        raw: Bytes,
+        confirmations: u64,
+    ) -> Result<TxReceipt, Self::Error> {
+        self.inner.send_and_await(raw, confirmations).await
+    }
+
+    async fn client_version(&self) -> Result<String, Self::Error> {
+        self.inner.client_version().await
+    }
+
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        self.inner.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockBlock, MockExecutionLayer, MockValidatorSet};
+
+    fn cache(capacity: usize) -> FinalizedCache<MockExecutionLayer> {
+        FinalizedCache::new(
+            MockExecutionLayer::new(MockValidatorSet(vec![100])),
+            NonZeroUsize::new(capacity).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn finalized_heights_hit_the_cache() {
+        let el = cache(8);
+        el.finalize_block(&MockBlock(5)).await.unwrap();
+
+        assert_eq!(el.get_block_by_height(3).await.unwrap(), Some(MockBlock(3)));
+        assert_eq!(el.get_block_by_height(3).await.unwrap(), Some(MockBlock(3)));
+        assert_eq!(el.inner().block_lookups(), 1);
+
+        // Heights above the finalized head may still be reorganized
+        el.get_block_by_height(6).await.unwrap();
+        el.get_block_by_height(6).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 3);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_block_is_evicted() {
+        let el = cache(2);
+        el.finalize_block(&MockBlock(10)).await.unwrap();
+
+        el.get_block_by_height(1).await.unwrap();
+        el.get_block_by_height(2).await.unwrap();
+        el.get_block_by_height(2).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 2);
+
+        // Height 10 was cached on finalization, then evicted as least recently used
+        el.get_block_by_height(10).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 3);
+    }
+
+    #[tokio::test]
+    async fn slow_subscriber_lags_then_resumes() {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let el = cache(8).with_subscription_capacity(NonZeroUsize::new(2).unwrap());
+        let mut finalized = el.subscribe_finalized();
+
+        for height in 1..=5 {
+            el.finalize_block(&MockBlock(height)).await.unwrap();
+        }
+
+        // Blocks 1 to 3 were dropped to bound the subscriber's buffer
+        assert!(matches!(finalized.recv().await, Err(RecvError::Lagged(3))));
+        assert_eq!(finalized.recv().await.unwrap(), MockBlock(4));
+        assert_eq!(finalized.recv().await.unwrap(), MockBlock(5));
+
+        el.finalize_block(&MockBlock(6)).await.unwrap();
+        assert_eq!(finalized.recv().await.unwrap(), MockBlock(6));
+    }
+
+    #[tokio::test]
+    async fn reorg_invalidates_affected_heights() {
+        let el = cache(8);
+        el.finalize_block(&MockBlock(5)).await.unwrap();
+        el.get_block_by_height(3).await.unwrap();
+        el.get_block_by_height(5).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 1);
+
+        el.invalidate_from(4);
+        assert_eq!(el.cached_finalized_height(), Some(3));
+
+        el.get_block_by_height(3).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 1);
+
+        el.get_block_by_height(5).await.unwrap();
+        el.get_block_by_height(5).await.unwrap();
+        assert_eq!(el.inner().block_lookups(), 3);
+    }
+}
@@ -1,3 +1,14 @@
+mod dyn_el;
 mod execution_layer;
+mod finalized_cache;
+#[cfg(test)]
+mod mock;
+mod validity;
 
-pub use execution_layer::ExecutionLayer;
+pub use dyn_el::{BoxError, DynEl, DynExecutionLayer};
+pub use execution_layer::{
+    wait_for_sync, Block, Checksum, ExecutionLayer, FeeHistory, FinalizeBatchError, SyncError,
+    SyncProbe, TxReceipt,
+};
+pub use finalized_cache::FinalizedCache;
+pub use validity::{BlockValidity, InvalidBlockReason, SetTransitionValidity};
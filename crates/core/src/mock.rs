@@ -0,0 +1,375 @@
+//! In-memory [`ExecutionLayer`] used by the unit tests of this crate.
+
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use core::time::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use tokio::sync::Notify;
+
+use crate::{
+    Block, BlockValidity, Checksum, ExecutionLayer, FeeHistory, InvalidBlockReason,
+    SetTransitionValidity, TxReceipt,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("mock error")]
+pub(crate) struct MockError;
+
+/// Block identified by its height.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MockBlock(pub(crate) u64);
+
+impl Block for MockBlock {
+    type Id = u64;
+    type Error = MockError;
+
+    fn id(&self) -> u64 {
+        self.0
+    }
+
+    fn parent_id(&self) -> u64 {
+        self.0.saturating_sub(1)
+    }
+
+    fn height(&self) -> u64 {
+        self.0
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_be_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, MockError> {
+        let bytes = bytes.try_into().map_err(|_| MockError)?;
+        Ok(Self(u64::from_be_bytes(bytes)))
+    }
+}
+
+/// Validator powers in canonical order.
+#[derive(Clone)]
+pub(crate) struct MockValidatorSet(pub(crate) Vec<u64>);
+
+impl Checksum for MockValidatorSet {
+    fn checksum(&self) -> B256 {
+        keccak256(
+            self.0
+                .iter()
+                .flat_map(|p| p.to_be_bytes())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl MockValidatorSet {
+    /// Voting power added, removed or moved between two sets, position by position.
+    pub(crate) fn power_delta(&self, other: &Self) -> u64 {
+        let len = self.0.len().max(other.0.len());
+        (0..len)
+            .map(|i| {
+                let before = self.0.get(i).copied().unwrap_or_default();
+                let after = other.0.get(i).copied().unwrap_or_default();
+                before.abs_diff(after)
+            })
+            .sum()
+    }
+}
+
+/// Block ids known to the mock, for `is_canonical`.
+#[derive(Default)]
+struct MockChain {
+    /// Canonical block ids, indexed by height.
+    canonical: Vec<u64>,
+    /// Ids of blocks reorganized out of the canonical chain.
+    orphaned: HashSet<u64>,
+}
+
+pub(crate) struct MockExecutionLayer {
+    validators: MockValidatorSet,
+    /// Validator sets replacing `validators` from a given height onwards.
+    set_changes: BTreeMap<u64, MockValidatorSet>,
+    version: String,
+    /// Number of `get_block_by_height` calls served.
+    block_lookups: AtomicUsize,
+    /// Time taken by `build_block`, during which it can be cancelled.
+    build_delay: Duration,
+    cancel: Notify,
+    /// Number of `is_syncing` polls still reporting syncing, and the height being synced to.
+    sync_polls: AtomicU64,
+    sync_target: u64,
+    peers: usize,
+    chain: Mutex<MockChain>,
+    /// Height of the oldest block not pruned yet.
+    pruned_below: AtomicU64,
+    /// Highest height passed to `finalize_block`.
+    finalized: Mutex<Option<u64>>,
+    /// Ids of blocks that fail validation and finalization.
+    invalid: HashSet<u64>,
+    /// Submitted transactions, each mined in its own block from height 1.
+    transactions: Mutex<Vec<Bytes>>,
+    /// Account balances, keyed by the height from which each value applies.
+    balances: HashMap<Address, BTreeMap<u64, U256>>,
+    nonces: HashMap<Address, u64>,
+}
+
+impl MockExecutionLayer {
+    pub(crate) fn new(validators: MockValidatorSet) -> Self {
+        Self {
+            validators,
+            set_changes: BTreeMap::new(),
+            version: "mock-el/v1.2.3".to_string(),
+            block_lookups: AtomicUsize::new(0),
+            build_delay: Duration::ZERO,
+            cancel: Notify::new(),
+            sync_polls: AtomicU64::new(0),
+            sync_target: 0,
+            peers: 1,
+            chain: Mutex::default(),
+            pruned_below: AtomicU64::new(0),
+            finalized: Mutex::default(),
+            invalid: HashSet::new(),
+            transactions: Mutex::default(),
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+        }
+    }
+
+    /// Start with `ids` as the canonical chain, indexed by height.
+    pub(crate) fn with_chain(self, ids: Vec<u64>) -> Self {
+        self.chain.lock().unwrap().canonical = ids;
+        self
+    }
+
+    /// Replace the canonical blocks from `height` onwards with `ids`.
+    pub(crate) fn reorg(&self, height: usize, ids: Vec<u64>) {
+        let mut chain = self.chain.lock().unwrap();
+        let orphaned: Vec<u64> = chain.canonical.drain(height..).collect();
+        chain.orphaned.extend(orphaned);
+        chain.canonical.extend(ids);
+    }
+
+    /// Report syncing towards `target` for the next `polls` calls to `is_syncing`,
+    /// advancing one block per poll.
+    pub(crate) fn with_syncing(mut self, polls: u64, target: u64) -> Self {
+        self.sync_polls = AtomicU64::new(polls);
+        self.sync_target = target;
+        self
+    }
+
+    /// Reject the block with id `id` in `validate_block` and `finalize_block`.
+    pub(crate) fn with_invalid_block(mut self, id: u64) -> Self {
+        self.invalid.insert(id);
+        self
+    }
+
+    /// Use `set` as the validator set from `height` onwards.
+    pub(crate) fn with_validator_set_at(mut self, height: u64, set: MockValidatorSet) -> Self {
+        self.set_changes.insert(height, set);
+        self
+    }
+
+    /// Set the balance of `address` to `balance` from `height` onwards.
+    pub(crate) fn with_balance_at(mut self, height: u64, address: Address, balance: U256) -> Self {
+        self.balances
+            .entry(address)
+            .or_default()
+            .insert(height, balance);
+        self
+    }
+
+    pub(crate) fn with_nonce(mut self, address: Address, nonce: u64) -> Self {
+        self.nonces.insert(address, nonce);
+        self
+    }
+
+    pub(crate) fn with_peers(mut self, peers: usize) -> Self {
+        self.peers = peers;
+        self
+    }
+
+    pub(crate) fn with_build_delay(mut self, delay: Duration) -> Self {
+        self.build_delay = delay;
+        self
+    }
+
+    /// Drop the blocks below `height`, which `get_block_by_height` then rejects.
+    pub(crate) fn prune_below(&self, height: u64) {
+        self.pruned_below.fetch_max(height, Ordering::SeqCst);
+    }
+
+    pub(crate) fn block_lookups(&self) -> usize {
+        self.block_lookups.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl ExecutionLayer for MockExecutionLayer {
+    type Block = MockBlock;
+    type ValidatorSet = MockValidatorSet;
+    type Error = MockError;
+
+    async fn genesis_block(&self) -> Result<MockBlock, MockError> {
+        Ok(MockBlock(0))
+    }
+
+    async fn build_block(&self, parent: &MockBlock, _: u64) -> Result<MockBlock, MockError> {
+        tokio::select! {
+            () = tokio::time::sleep(self.build_delay) => Ok(MockBlock(parent.0 + 1)),
+            () = self.cancel.notified() => Err(MockError),
+        }
+    }
+
+    async fn cancel_build(&self) -> Result<(), MockError> {
+        // Only wakes builds in progress, so later builds are unaffected
+        self.cancel.notify_waiters();
+        Ok(())
+    }
+
+    async fn validate_block(&self, block: &MockBlock) -> Result<BlockValidity, MockError> {
+        if self.invalid.contains(&block.id()) {
+            return Ok(BlockValidity::Invalid(InvalidBlockReason::Rejected));
+        }
+        Ok(BlockValidity::Valid)
+    }
+
+    async fn finalize_block(&self, block: &MockBlock) -> Result<u64, MockError> {
+        if self.invalid.contains(&block.id()) {
+            return Err(MockError);
+        }
+        let mut finalized = self.finalized.lock().unwrap();
+        *finalized = Some(finalized.map_or(block.0, |height| height.max(block.0)));
+        Ok(block.id())
+    }
+
+    async fn validate_set_transition(
+        &self,
+        prev: &MockBlock,
+        next: &MockBlock,
+    ) -> Result<SetTransitionValidity, MockError> {
+        let prev = self.validator_set(prev).await?;
+        let next = self.validator_set(next).await?;
+        Ok(SetTransitionValidity::from_power_delta(
+            prev.power_delta(&next),
+            prev.0.iter().sum(),
+        ))
+    }
+
+    async fn validator_set(&self, block: &MockBlock) -> Result<MockValidatorSet, MockError> {
+        let set = self
+            .set_changes
+            .range(..=block.0)
+            .next_back()
+            .map_or(&self.validators, |(_, set)| set);
+        Ok(set.clone())
+    }
+
+    async fn latest_block_height(&self) -> Result<Option<u64>, MockError> {
+        let remaining = self.sync_polls.load(Ordering::SeqCst);
+        Ok(self
+            .sync_target
+            .checked_sub(remaining)
+            .filter(|height| *height > 0))
+    }
+
+    async fn finalized_height(&self) -> Result<Option<u64>, MockError> {
+        Ok(*self.finalized.lock().unwrap())
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> Result<Option<MockBlock>, MockError> {
+        self.block_lookups.fetch_add(1, Ordering::SeqCst);
+        if height < self.pruned_below.load(Ordering::SeqCst) {
+            return Err(MockError);
+        }
+        Ok(Some(MockBlock(height)))
+    }
+
+    async fn earliest_available_height(&self) -> Result<u64, MockError> {
+        Ok(self.pruned_below.load(Ordering::SeqCst))
+    }
+
+    async fn latest_block(&self) -> Result<Option<MockBlock>, MockError> {
+        Ok(self.latest_block_height().await?.map(MockBlock))
+    }
+
+    async fn is_canonical(&self, id: &u64) -> Result<bool, MockError> {
+        let chain = self.chain.lock().unwrap();
+        if chain.canonical.contains(id) {
+            Ok(true)
+        } else if chain.orphaned.contains(id) {
+            Ok(false)
+        } else {
+            Err(MockError)
+        }
+    }
+
+    async fn is_syncing(&self) -> Result<(bool, u64), MockError> {
+        let syncing = self
+            .sync_polls
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        Ok((syncing, if syncing { self.sync_target } else { 0 }))
+    }
+
+    async fn peer_count(&self) -> Result<usize, MockError> {
+        Ok(self.peers)
+    }
+
+    /// Every block has a base fee of 1 gwei per unit of height and is half full.
+    async fn fee_history(&self, block_count: usize, newest: u64) -> Result<FeeHistory, MockError> {
+        let count = u64::try_from(block_count).map_err(|_| MockError)?;
+        let oldest_block = (newest + 1).saturating_sub(count);
+        Ok(FeeHistory {
+            oldest_block,
+            base_fee_per_gas: (oldest_block..=newest + 1)
+                .map(|height| u128::from(height) * 1_000_000_000)
+                .collect(),
+            gas_used_ratio: (oldest_block..=newest).map(|_| 0.5).collect(),
+        })
+    }
+
+    async fn balance_of(&self, address: Address, block: &MockBlock) -> Result<U256, MockError> {
+        Ok(self
+            .balances
+            .get(&address)
+            .and_then(|history| history.range(..=block.height()).next_back())
+            .map(|(_, balance)| *balance)
+            .unwrap_or_default())
+    }
+
+    async fn account_nonce(&self, address: Address) -> Result<u64, MockError> {
+        Ok(self.nonces.get(&address).copied().unwrap_or_default())
+    }
+
+    /// Rejects empty payloads, and mines each transaction alone in the next
+    /// block, so the n-th transaction sent is included at height n.
+    async fn send_raw_transaction(&self, raw: Bytes) -> Result<u64, MockError> {
+        if raw.is_empty() {
+            return Err(MockError);
+        }
+        let mut transactions = self.transactions.lock().unwrap();
+        transactions.push(raw);
+        Ok(transactions.len() as u64)
+    }
+
+    async fn send_and_await(&self, raw: Bytes, _: u64) -> Result<TxReceipt, MockError> {
+        let transaction_hash = keccak256(&raw);
+        let block_height = self.send_raw_transaction(raw).await?;
+        Ok(TxReceipt {
+            transaction_hash,
+            block_height,
+            success: true,
+            gas_used: 21_000,
+        })
+    }
+
+    async fn client_version(&self) -> Result<String, MockError> {
+        Ok(self.version.clone())
+    }
+
+    async fn shutdown(&self) -> Result<(), MockError> {
+        Ok(())
+    }
+}
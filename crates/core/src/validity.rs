@@ -0,0 +1,77 @@
+use core::fmt;
+
+/// Outcome of validating a block, carrying the rejection reason when invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockValidity {
+    Valid,
+    Invalid(InvalidBlockReason),
+}
+
+impl BlockValidity {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+
+    /// Returns the rejection reason, or `None` if the block is valid.
+    pub fn reason(&self) -> Option<InvalidBlockReason> {
+        match self {
+            Self::Valid => None,
+            Self::Invalid(reason) => Some(*reason),
+        }
+    }
+}
+
+/// Outcome of checking that a block's validator set may follow its parent's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetTransitionValidity {
+    Valid,
+    /// More than a third of the parent set's voting power was added, removed or moved.
+    ExcessiveChurn {
+        power_delta: u64,
+        total_power: u64,
+    },
+}
+
+impl SetTransitionValidity {
+    /// Applies the churn bound: at most a third of `total_power`, the voting power of
+    /// the parent set, may change, so that the new set keeps an honest overlap with
+    /// the old one.
+    pub fn from_power_delta(power_delta: u64, total_power: u64) -> Self {
+        if u128::from(power_delta) * 3 > u128::from(total_power) {
+            Self::ExcessiveChurn {
+                power_delta,
+                total_power,
+            }
+        } else {
+            Self::Valid
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+/// Why a block was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidBlockReason {
+    /// The block bytes could not be decoded.
+    Malformed,
+    /// The block does not extend the current head.
+    BadParent,
+    /// The block timestamp is further ahead of the local clock than the allowed drift.
+    TimestampTooFarFuture,
+    /// The execution layer rejected the block.
+    Rejected,
+}
+
+impl fmt::Display for InvalidBlockReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "malformed block"),
+            Self::BadParent => write!(f, "parent does not match current head"),
+            Self::TimestampTooFarFuture => write!(f, "timestamp too far in the future"),
+            Self::Rejected => write!(f, "rejected by execution layer"),
+        }
+    }
+}
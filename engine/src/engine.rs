@@ -5,6 +5,7 @@ use alloy_rpc_types_engine::{
     ExecutionPayloadV3, ForkchoiceUpdated, PayloadAttributes, PayloadStatus, PayloadStatusEnum,
 };
 use color_eyre::eyre;
+use emerald_core::SyncProbe;
 use malachitebft_eth_types::{Address, BlockHash, RetryConfig, B256};
 use tracing::{debug, warn};
 
@@ -311,3 +312,20 @@ impl Engine {
             .as_secs()
     }
 }
+
+#[async_trait::async_trait]
+impl SyncProbe for Engine {
+    type Error = eyre::Report;
+
+    async fn is_syncing(&self) -> eyre::Result<(bool, u64)> {
+        Engine::is_syncing(self).await
+    }
+
+    async fn latest_block_height(&self) -> eyre::Result<Option<u64>> {
+        self.get_latest_block_number().await
+    }
+
+    async fn peer_count(&self) -> eyre::Result<usize> {
+        Ok(usize::try_from(self.eth.peer_count().await?)?)
+    }
+}
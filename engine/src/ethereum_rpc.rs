@@ -1,7 +1,9 @@
 use core::time::Duration;
 
+use alloy_rpc_types::{FeeHistory, TransactionReceipt};
 use alloy_rpc_types_txpool::{TxpoolInspect, TxpoolStatus};
 use color_eyre::eyre;
+use malachitebft_eth_types::{Address, Bytes, B256, U256, U64};
 use reqwest::header::CONTENT_TYPE;
 use reqwest::{Client, Url};
 use serde::de::DeserializeOwned;
@@ -10,6 +12,13 @@ use tracing::debug;
 
 use crate::json_structures::*;
 
+/// Maximum number of calls sent in a single JSON-RPC batch, to stay under
+/// the request size limits of the execution client.
+pub const STORAGE_BATCH_SIZE: usize = 256;
+
+/// Delay between receipt polls in [`EthereumRPC::send_and_await`].
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// RPC client for Ethereum server.
 pub struct EthereumRPC {
     client: Client,
@@ -60,12 +69,135 @@ impl EthereumRPC {
         }
     }
 
+    /// Send one batched JSON-RPC request calling `method` once per entry in
+    /// `params`, and return the results in the same order.
+    pub async fn rpc_batch_request<D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+        timeout: Duration,
+    ) -> eyre::Result<Vec<D>> {
+        let count = params.len();
+        let body: Vec<JsonRequestBody> = params
+            .into_iter()
+            .enumerate()
+            .map(|(id, params)| JsonRequestBody {
+                jsonrpc: "2.0",
+                method,
+                params,
+                id: json!(id),
+            })
+            .collect();
+        let request = self
+            .client
+            .post(self.url.clone())
+            .timeout(timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body);
+        let bodies: Vec<JsonResponseBody> =
+            request.send().await?.error_for_status()?.json().await?;
+
+        debug!("batch response: {} bodies", bodies.len());
+
+        // Responses to a batch may come back in any order
+        let mut results: Vec<Option<D>> = (0..count).map(|_| None).collect();
+        for body in bodies {
+            let id = body
+                .id
+                .as_u64()
+                .and_then(|id| usize::try_from(id).ok())
+                .filter(|id| *id < count)
+                .ok_or_else(|| eyre::eyre!("Unexpected id in batch response: {}", body.id))?;
+
+            match (body.result, body.error) {
+                (result, None) => results[id] = Some(serde_json::from_value(result)?),
+                (_, Some(error)) => {
+                    return Err(eyre::eyre!(
+                        "Server Message: code: {}, message: {}",
+                        error.code,
+                        error.message,
+                    ))
+                }
+            }
+        }
+
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(id, result)| {
+                result.ok_or_else(|| eyre::eyre!("Missing response for batch request {id}"))
+            })
+            .collect()
+    }
+
+    /// Read the given storage slots of an account at the latest block, using
+    /// batched requests of at most [`STORAGE_BATCH_SIZE`] slots.
+    pub async fn account_storage(
+        &self,
+        address: Address,
+        slots: &[B256],
+    ) -> eyre::Result<Vec<B256>> {
+        let mut values = Vec::with_capacity(slots.len());
+        for chunk in slots.chunks(STORAGE_BATCH_SIZE) {
+            let params = chunk
+                .iter()
+                .map(|slot| json!([address, slot, "latest"]))
+                .collect();
+            let batch: Vec<B256> = self
+                .rpc_batch_request("eth_getStorageAt", params, Duration::from_secs(5))
+                .await?;
+            values.extend(batch);
+        }
+        Ok(values)
+    }
+
+    /// Get the balance of an account at the given block number or tag.
+    pub async fn get_balance(&self, address: Address, block_number: &str) -> eyre::Result<U256> {
+        self.rpc_request(
+            "eth_getBalance",
+            json!([address, block_number]),
+            Duration::from_secs(1),
+        )
+        .await
+    }
+
+    /// Get the number of transactions sent from an account at the given block
+    /// number or tag, which is the nonce of its next transaction with `pending`.
+    pub async fn get_transaction_count(
+        &self,
+        address: Address,
+        block_number: &str,
+    ) -> eyre::Result<u64> {
+        let count: U64 = self
+            .rpc_request(
+                "eth_getTransactionCount",
+                json!([address, block_number]),
+                Duration::from_secs(1),
+            )
+            .await?;
+        Ok(count.to())
+    }
+
     /// Get the eth1 chain id of the given endpoint.
     pub async fn get_chain_id(&self) -> eyre::Result<String> {
         self.rpc_request("eth_chainId", json!([]), Duration::from_secs(1))
             .await
     }
 
+    /// Get the client name and version of the given endpoint.
+    pub async fn client_version(&self) -> eyre::Result<String> {
+        self.rpc_request("web3_clientVersion", json!([]), Duration::from_secs(1))
+            .await
+    }
+
+    /// Get the number of peers the given endpoint is connected to.
+    pub async fn peer_count(&self) -> eyre::Result<u64> {
+        let count: U64 = self
+            .rpc_request("net_peerCount", json!([]), Duration::from_secs(1))
+            .await?;
+        Ok(count.to())
+    }
+
     pub async fn get_block_by_number(
         &self,
         block_number: &str,
@@ -76,6 +208,76 @@ impl EthereumRPC {
             .await
     }
 
+    /// Get the base fees and gas used ratios of up to `block_count` blocks
+    /// ending at `newest`, without reward percentiles.
+    pub async fn fee_history(&self, block_count: u64, newest: u64) -> eyre::Result<FeeHistory> {
+        let params = json!([format!("{block_count:#x}"), format!("{newest:#x}"), []]);
+        self.rpc_request("eth_feeHistory", params, Duration::from_secs(1))
+            .await
+    }
+
+    /// Get the height of the latest block of the given endpoint.
+    pub async fn block_number(&self) -> eyre::Result<u64> {
+        let number: U64 = self
+            .rpc_request("eth_blockNumber", json!([]), Duration::from_secs(1))
+            .await?;
+        Ok(number.to())
+    }
+
+    /// Submit a signed transaction, returning its hash.
+    pub async fn send_raw_transaction(&self, raw: &Bytes) -> eyre::Result<B256> {
+        self.rpc_request(
+            "eth_sendRawTransaction",
+            json!([raw]),
+            Duration::from_secs(1),
+        )
+        .await
+    }
+
+    /// Get the receipt of a transaction, or `None` while it is pending.
+    pub async fn get_transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> eyre::Result<Option<TransactionReceipt>> {
+        self.rpc_request(
+            "eth_getTransactionReceipt",
+            json!([hash]),
+            Duration::from_secs(1),
+        )
+        .await
+    }
+
+    /// Submit a signed transaction and poll for its receipt until the including
+    /// block has `confirmations` blocks on top of it, itself included.
+    pub async fn send_and_await(
+        &self,
+        raw: &Bytes,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> eyre::Result<TransactionReceipt> {
+        let hash = self.send_raw_transaction(raw).await?;
+
+        let poll = async {
+            loop {
+                if let Some(receipt) = self.get_transaction_receipt(hash).await? {
+                    // A receipt without a block number is still pending
+                    if let Some(included) = receipt.block_number {
+                        if self.block_number().await?.saturating_add(1)
+                            >= included.saturating_add(confirmations)
+                        {
+                            return Ok(receipt);
+                        }
+                    }
+                }
+                tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| eyre::eyre!("Transaction {hash} not confirmed after {timeout:?}"))?
+    }
+
     pub async fn txpool_status(&self) -> eyre::Result<TxpoolStatus> {
         self.rpc_request("txpool_status", json!([]), Duration::from_secs(1))
             .await
@@ -0,0 +1,239 @@
+//! Tests of [`EthereumRPC`] against a local Anvil node.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
+
+use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network::TxSignerSync;
+use alloy_node_bindings::anvil::{Anvil, AnvilInstance};
+use alloy_primitives::{keccak256, Address, TxKind, B256, U256};
+use alloy_signer_local::PrivateKeySigner;
+use axum::extract::State;
+use axum::routing::post;
+use axum::Router;
+use color_eyre::eyre;
+use malachitebft_eth_engine::ethereum_rpc::{EthereumRPC, STORAGE_BATCH_SIZE};
+use malachitebft_eth_types::Bytes;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Url;
+use serde_json::json;
+use tokio::net::TcpListener;
+
+/// Signer of the first pre-funded Anvil account
+fn funded_signer(anvil: &AnvilInstance) -> PrivateKeySigner {
+    PrivateKeySigner::from(anvil.keys()[0].clone())
+}
+
+/// A signed transfer of 1 wei, encoded for `eth_sendRawTransaction`
+fn signed_transfer(signer: &PrivateKeySigner, nonce: u64, chain_id: u64) -> eyre::Result<Bytes> {
+    let mut tx = TxEip1559 {
+        chain_id,
+        nonce,
+        gas_limit: 21_000,
+        max_fee_per_gas: 20_000_000_000,
+        max_priority_fee_per_gas: 1_000_000_000,
+        to: TxKind::Call(Address::repeat_byte(0x01)),
+        value: U256::from(1),
+        ..Default::default()
+    };
+    let signature = signer.sign_transaction_sync(&mut tx)?;
+    Ok(TxEnvelope::from(tx.into_signed(signature))
+        .encoded_2718()
+        .into())
+}
+
+/// Overwrite a storage slot of `address` with Anvil's cheat code
+async fn set_storage(
+    anvil: &AnvilInstance,
+    address: Address,
+    slot: B256,
+    value: B256,
+) -> eyre::Result<()> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "anvil_setStorageAt",
+        "params": [address, slot, value],
+    });
+    reqwest::Client::new()
+        .post(anvil.endpoint())
+        .json(&request)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Forwards JSON-RPC requests to `upstream`, counting the HTTP round-trips
+struct CountingProxy {
+    upstream: String,
+    requests: AtomicUsize,
+}
+
+async fn forward(
+    State(proxy): State<Arc<CountingProxy>>,
+    body: axum::body::Bytes,
+) -> axum::body::Bytes {
+    proxy.requests.fetch_add(1, Ordering::SeqCst);
+    let response = reqwest::Client::new()
+        .post(&proxy.upstream)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("upstream node is reachable");
+    response
+        .bytes()
+        .await
+        .expect("upstream response has a body")
+}
+
+/// Serve a [`CountingProxy`] to Anvil on a local port and return its URL
+async fn counting_proxy(anvil: &AnvilInstance) -> eyre::Result<(Url, Arc<CountingProxy>)> {
+    let proxy = Arc::new(CountingProxy {
+        upstream: anvil.endpoint(),
+        requests: AtomicUsize::new(0),
+    });
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?).parse()?;
+    let router = Router::new()
+        .route("/", post(forward))
+        .with_state(Arc::clone(&proxy));
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    Ok((url, proxy))
+}
+
+/// Read more slots than fit in one batch, in an arbitrary order and including
+/// slots that were never written, and check they match per-slot reads while
+/// taking one round-trip per batch.
+#[tokio::test]
+#[test_log::test]
+async fn test_account_storage_across_batches() -> eyre::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let direct = EthereumRPC::new(anvil.endpoint().parse()?)?;
+    let (proxy_url, proxy) = counting_proxy(&anvil).await?;
+    let rpc = EthereumRPC::new(proxy_url)?;
+    let account = Address::repeat_byte(0x42);
+
+    // Nothing to read, so no request is sent
+    assert!(rpc.account_storage(account.into(), &[]).await?.is_empty());
+    assert_eq!(proxy.requests.load(Ordering::SeqCst), 0);
+
+    let written = STORAGE_BATCH_SIZE as u64 + 1;
+    for slot in 0..written {
+        let value = B256::from(U256::from(slot) + U256::from(1000));
+        set_storage(&anvil, account, B256::from(U256::from(slot)), value).await?;
+    }
+
+    // Unwritten slots, then the written ones in reverse, so that the first batch
+    // ends right before the last slot written
+    let keys: Vec<B256> = (written..written + 2)
+        .chain((0..written).rev())
+        .map(|slot| B256::from(U256::from(slot)))
+        .collect();
+    let batches = keys.len().div_ceil(STORAGE_BATCH_SIZE);
+    assert_eq!(batches, 2);
+
+    let values = rpc.account_storage(account.into(), &keys).await?;
+    assert_eq!(proxy.requests.load(Ordering::SeqCst), batches);
+
+    let mut expected = Vec::with_capacity(keys.len());
+    for key in &keys {
+        let value: B256 = direct
+            .rpc_request(
+                "eth_getStorageAt",
+                json!([account, key, "latest"]),
+                Duration::from_secs(1),
+            )
+            .await?;
+        expected.push(value);
+    }
+    assert_eq!(values, expected);
+    assert!(expected[..2].iter().all(|value| value.is_zero()));
+
+    Ok(())
+}
+
+/// Read the fee history of blocks that each hold one transfer, as Anvil mines
+/// a block per transaction.
+#[tokio::test]
+#[test_log::test]
+async fn test_fee_history() -> eyre::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let rpc = EthereumRPC::new(anvil.endpoint().parse()?)?;
+
+    let signer = funded_signer(&anvil);
+    for nonce in 0..3 {
+        let raw = signed_transfer(&signer, nonce, anvil.chain_id())?;
+        rpc.send_raw_transaction(&raw).await?;
+    }
+
+    let newest = rpc.block_number().await?;
+    assert_eq!(newest, 3);
+    let history = rpc.fee_history(3, newest).await?;
+
+    assert_eq!(history.oldest_block, newest - 2);
+    assert_eq!(history.gas_used_ratio.len(), 3);
+    // Includes the base fee of the block after `newest`
+    assert_eq!(history.base_fee_per_gas.len(), 4);
+    assert!(history
+        .gas_used_ratio
+        .iter()
+        .all(|ratio| *ratio > 0.0 && *ratio <= 1.0));
+
+    Ok(())
+}
+
+/// Send a transfer through the raw transaction helpers and wait for its inclusion.
+#[tokio::test]
+#[test_log::test]
+async fn test_send_and_await() -> eyre::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let rpc = EthereumRPC::new(anvil.endpoint().parse()?)?;
+
+    let raw = signed_transfer(&funded_signer(&anvil), 0, anvil.chain_id())?;
+    let receipt = rpc.send_and_await(&raw, 1, Duration::from_secs(10)).await?;
+    assert!(receipt.status());
+    assert_eq!(receipt.transaction_hash, keccak256(&raw));
+    assert_eq!(receipt.block_number, Some(rpc.block_number().await?));
+
+    Ok(())
+}
+
+/// Read the pending nonce of an account before and after it sends a transaction.
+#[tokio::test]
+#[test_log::test]
+async fn test_account_nonce() -> eyre::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let rpc = EthereumRPC::new(anvil.endpoint().parse()?)?;
+
+    let signer = funded_signer(&anvil);
+    let address = signer.address().into();
+    assert_eq!(rpc.get_transaction_count(address, "pending").await?, 0);
+
+    let raw = signed_transfer(&signer, 0, anvil.chain_id())?;
+    rpc.send_and_await(&raw, 1, Duration::from_secs(10)).await?;
+    assert_eq!(rpc.get_transaction_count(address, "pending").await?, 1);
+
+    Ok(())
+}
+
+/// The client version is the `web3_clientVersion` string of the node, which
+/// names the client before its version.
+#[tokio::test]
+#[test_log::test]
+async fn test_client_version() -> eyre::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let rpc = EthereumRPC::new(anvil.endpoint().parse()?)?;
+
+    let version = rpc.client_version().await?;
+    let (client, release) = version
+        .split_once('/')
+        .ok_or_else(|| eyre::eyre!("unexpected client version {version:?}"))?;
+    assert_eq!(client, "anvil");
+    assert!(release.starts_with('v'), "{version}");
+
+    Ok(())
+}
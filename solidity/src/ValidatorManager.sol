@@ -27,6 +27,11 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
         Secp256k1Key validatorKey;
         /// @dev Voting power assigned to the validator
         uint64 power;
+        /// @dev Recipient of the rewards of blocks proposed by the validator, packed with `power`.
+        ///      Added in storage layout `vm-layout-2`: this extends the tuple returned by
+        ///      `getValidator` and `getValidators`, so callers decoding the previous
+        ///      `(validatorKey, power)` ABI must be regenerated.
+        address feeRecipient;
     }
 
     struct ValidatorRegistration {
@@ -47,6 +52,7 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
     event ValidatorPowerUpdated(
         address indexed validatorAddress, Secp256k1Key validatorKey, uint64 oldPower, uint64 newPower
     );
+    event FeeRecipientUpdated(address indexed validatorAddress, address oldFeeRecipient, address newFeeRecipient);
 
     // Errors
     error ValidatorAlreadyExists();
@@ -157,7 +163,9 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
         uint256 length = registrations.length;
         for (uint256 i = 0; i < length;) {
             Secp256k1Key memory validatorKey = _secp256k1KeyFromBytesInternal(registrations[i].publicKey);
-            _register(ValidatorInfo({validatorKey: validatorKey, power: registrations[i].power}));
+            _register(
+                ValidatorInfo({validatorKey: validatorKey, power: registrations[i].power, feeRecipient: address(0)})
+            );
             unchecked {
                 ++i;
             }
@@ -172,12 +180,13 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
      */
     function register(bytes calldata validatorPublicKey, uint64 power) external nonReentrant onlyOwner {
         Secp256k1Key memory validatorKey = _secp256k1KeyFromBytesInternal(validatorPublicKey);
-        _register(ValidatorInfo({validatorKey: validatorKey, power: power}));
+        _register(ValidatorInfo({validatorKey: validatorKey, power: power, feeRecipient: address(0)}));
     }
 
     /**
      * @dev Internal implementation to register a new validator with specified key and power
-     * @param validator Validator data containing key and power
+     * @param validator Validator data containing key and power, with the fee recipient
+     *      defaulting to the validator address when zero
      */
     function _register(ValidatorInfo memory validator)
         internal
@@ -185,6 +194,9 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
         validPower(validator.power)
     {
         address validatorAddress = _validatedNewAddress(validator.validatorKey);
+        if (validator.feeRecipient == address(0)) {
+            validator.feeRecipient = validatorAddress;
+        }
         _increaseTotalPower(validator.power);
         _validators[validatorAddress] = validator;
         _validatorAddresses.add(validatorAddress);
@@ -261,10 +273,30 @@ contract ValidatorManager is Ownable, ReentrancyGuard {
         emit ValidatorPowerUpdated(validatorAddress, validatorKey, oldPower, newPower);
     }
 
+    /**
+     * @dev Update the recipient of a validator's block rewards (callable by the owner or the validator itself).
+     * @param validatorAddress The registered validator address whose fee recipient is being updated.
+     * @param newFeeRecipient The new fee recipient, or zero to reset it to the validator address.
+     */
+    function setFeeRecipient(address validatorAddress, address newFeeRecipient) external nonReentrant {
+        _requireValidatorAddressExists(validatorAddress);
+        if (_msgSender() != owner() && _msgSender() != validatorAddress) {
+            revert OwnableUnauthorizedAccount(_msgSender());
+        }
+        if (newFeeRecipient == address(0)) {
+            newFeeRecipient = validatorAddress;
+        }
+
+        address oldFeeRecipient = _validators[validatorAddress].feeRecipient;
+        _validators[validatorAddress].feeRecipient = newFeeRecipient;
+
+        emit FeeRecipientUpdated(validatorAddress, oldFeeRecipient, newFeeRecipient);
+    }
+
     /**
      * @dev Get validator information for a registered address.
      * @param validatorAddress The address derived from the validator public key.
-     * @return info The validator information including key and power.
+     * @return info The validator information including key, power and fee recipient.
      * @notice Reverts with {ValidatorDoesNotExist} if the address is not registered.
      */
     function getValidator(address validatorAddress) external view returns (ValidatorInfo memory info) {
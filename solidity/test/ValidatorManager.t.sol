@@ -54,6 +54,7 @@ contract ValidatorManagerTest is Test {
     event ValidatorPowerUpdated(
         address indexed validatorAddress, ValidatorManager.Secp256k1Key validatorKey, uint64 oldPower, uint64 newPower
     );
+    event FeeRecipientUpdated(address indexed validatorAddress, address oldFeeRecipient, address newFeeRecipient);
 
     function setUp() public {
         validatorManager = new ValidatorManager();
@@ -89,6 +90,38 @@ contract ValidatorManagerTest is Test {
         assertKeyEq(validators[0].validatorKey, aliceKey);
     }
 
+    function testFeeRecipientDefaultsToValidatorAddress() public {
+        validatorManager.register(ALICE_UNCOMPRESSED, INITIAL_POWER);
+
+        ValidatorManager.ValidatorInfo memory info = validatorManager.getValidator(aliceValidatorAddress);
+        assertEq(info.feeRecipient, aliceValidatorAddress);
+    }
+
+    function testOwnerAndValidatorCanSetFeeRecipient() public {
+        validatorManager.register(ALICE_UNCOMPRESSED, INITIAL_POWER);
+
+        vm.expectEmit(true, false, false, true);
+        emit FeeRecipientUpdated(aliceValidatorAddress, aliceValidatorAddress, NEW_OWNER);
+        validatorManager.setFeeRecipient(aliceValidatorAddress, NEW_OWNER);
+        assertEq(validatorManager.getValidator(aliceValidatorAddress).feeRecipient, NEW_OWNER);
+        assertEq(validatorManager.getValidator(aliceValidatorAddress).power, INITIAL_POWER);
+
+        vm.prank(aliceValidatorAddress);
+        validatorManager.setFeeRecipient(aliceValidatorAddress, address(0));
+        assertEq(validatorManager.getValidator(aliceValidatorAddress).feeRecipient, aliceValidatorAddress);
+    }
+
+    function testOthersCannotSetFeeRecipient() public {
+        validatorManager.register(ALICE_UNCOMPRESSED, INITIAL_POWER);
+
+        vm.expectRevert(abi.encodeWithSelector(Ownable.OwnableUnauthorizedAccount.selector, NON_OWNER));
+        vm.prank(NON_OWNER);
+        validatorManager.setFeeRecipient(aliceValidatorAddress, NON_OWNER);
+
+        vm.expectRevert(ValidatorManager.ValidatorDoesNotExist.selector);
+        validatorManager.setFeeRecipient(bobValidatorAddress, NON_OWNER);
+    }
+
     function testRegisterWithUncompressedKey() public {
         bytes memory uncompressed = ALICE_UNCOMPRESSED;
 
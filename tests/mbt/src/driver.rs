@@ -1,5 +1,7 @@
 mod environment;
+mod inject;
 mod utils;
+mod validator_set;
 
 use std::collections::BTreeMap;
 
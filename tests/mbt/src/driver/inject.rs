@@ -0,0 +1,46 @@
+use alloy_rpc_types_engine::ExecutionPayloadV3;
+use anyhow::Result;
+use emerald_core::BlockValidity;
+
+use crate::driver::EmeraldDriver;
+use crate::state::Proposal;
+use crate::NODES;
+
+impl EmeraldDriver {
+    /// Builds a height 1 proposal on `proposer`, applies `tamper` to its
+    /// execution payload, and returns the validity `validator` assigns to the
+    /// resulting block.
+    ///
+    /// Malformed blocks can't be produced by the Quint model, so this lets
+    /// tests pin the reason Emerald rejects them for.
+    pub fn inject_block<F>(
+        &mut self,
+        proposer: &str,
+        validator: &str,
+        tamper: F,
+    ) -> Result<BlockValidity>
+    where
+        F: FnOnce(&mut ExecutionPayloadV3),
+    {
+        self.set_initial_state()?;
+
+        for node in NODES {
+            self.perform(node.to_string(), |app, _| app.consensus_ready())?;
+        }
+
+        let proposal = Proposal {
+            height: 1,
+            round: 0,
+            proposer: proposer.to_string(),
+            payload: 0,
+        };
+
+        self.perform(proposer.to_string(), |app, hist| {
+            app.get_value(hist, 1, 0, proposal.clone())
+        })?;
+
+        self.perform(validator.to_string(), |app, hist| {
+            app.validate_block(hist, &proposal, tamper)
+        })
+    }
+}
@@ -10,10 +10,10 @@ use crate::sut::Sut;
 impl EmeraldDriver {
     /// Locates the system under test and use the driver's runtime to wait for
     /// the execution of the given future.
-    pub fn perform<'a, F, Fut>(&'a mut self, node: Node, action: F) -> Result<()>
+    pub fn perform<'a, F, Fut, T>(&'a mut self, node: Node, action: F) -> Result<T>
     where
         F: FnOnce(&'a mut Sut, &'a mut History) -> Fut,
-        Fut: Future<Output = Result<()>>,
+        Fut: Future<Output = Result<T>>,
     {
         let Some(sut) = self.sut.get_mut(&node) else {
             bail!("Unknown node: {node}")
@@ -0,0 +1,111 @@
+use anyhow::{anyhow, Result};
+use malachitebft_app_channel::app::types::core::SignedVote;
+use malachitebft_eth_types::{EmeraldContext, Height as EmeraldHeight, Validator, ValidatorSet};
+
+use crate::driver::EmeraldDriver;
+use crate::state::{Height, Node, Proposal, ValidatorSetChange};
+use crate::{sut, NODES};
+
+impl EmeraldDriver {
+    /// Resets the environment and brings every node to consensus readiness at
+    /// height 1, like the `genesisStart` run of `emerald_tests.qnt`.
+    pub fn start_from_genesis(&mut self) -> Result<()> {
+        self.set_initial_state()?;
+
+        for node in NODES {
+            self.perform(node.to_string(), |app, _| app.consensus_ready())?;
+        }
+        Ok(())
+    }
+
+    /// Drives all nodes through deciding the given proposal: the proposer
+    /// builds it, the other nodes receive it, and every node decides with the
+    /// precommits of the validators active at the proposal height.
+    ///
+    /// Returns the precommits of the commit certificate.
+    pub fn decide(&mut self, proposal: Proposal) -> Result<Vec<SignedVote<EmeraldContext>>> {
+        let (height, round) = (proposal.height, proposal.round);
+        self.perform(proposal.proposer.clone(), |app, hist| {
+            app.get_value(hist, height, round, proposal.clone())
+        })?;
+
+        for node in NODES.iter().filter(|node| **node != proposal.proposer) {
+            self.perform(node.to_string(), |app, hist| {
+                app.receive_proposal(hist, proposal.clone())
+            })?;
+        }
+
+        let votes = sut::mock_votes(&self.sut, &self.history, &proposal)?;
+        for node in NODES {
+            let votes = votes.clone();
+            self.perform(node.to_string(), |app, hist| {
+                app.decided(hist, proposal.clone(), votes)
+            })?;
+        }
+
+        Ok(votes)
+    }
+
+    /// Applies a validator set change on every node for `height`, which must
+    /// be the height consensus is about to start, i.e. right after deciding
+    /// `height - 1`.
+    ///
+    /// The Quint model runs with a static validator set, so this stands in for
+    /// the ValidatorManager transaction that would change it on-chain.
+    pub fn change_validator_set(
+        &mut self,
+        height: Height,
+        change: ValidatorSetChange,
+    ) -> Result<ValidatorSet> {
+        let current = self.validator_set(&NODES[0].to_string(), height)?;
+
+        let validators: Vec<Validator> = match change {
+            ValidatorSetChange::Add(node, power) => {
+                let sut = self.sut.get(&node).ok_or(anyhow!("Unknown node: {node}"))?;
+                let public_key = sut
+                    .components
+                    .state
+                    .signing_provider
+                    .private_key()
+                    .public_key();
+                current
+                    .validators
+                    .iter()
+                    .cloned()
+                    .chain([Validator::new(public_key, power)])
+                    .collect()
+            }
+            ValidatorSetChange::Remove(node) => {
+                let address = self.history.get_address(&node)?;
+                current
+                    .validators
+                    .iter()
+                    .filter(|validator| validator.address != address)
+                    .cloned()
+                    .collect()
+            }
+        };
+        let validator_set = ValidatorSet::new(validators);
+
+        for node in NODES {
+            let validator_set = validator_set.clone();
+            self.perform(node.to_string(), |app, _| async move {
+                app.set_validator_set(height, validator_set)
+            })?;
+        }
+
+        Ok(validator_set)
+    }
+
+    /// Returns the validator set a node uses at the given height.
+    pub fn validator_set(&self, node: &Node, height: Height) -> Result<ValidatorSet> {
+        self.sut
+            .get(node)
+            .ok_or(anyhow!("Unknown node: {node}"))?
+            .components
+            .state
+            .get_validator_set(EmeraldHeight::new(height))
+            .cloned()
+            .ok_or(anyhow!("No validator set for {node} at height {height}"))
+    }
+}
@@ -6,6 +6,8 @@ mod state;
 mod sut;
 
 pub use driver::EmeraldDriver;
+pub use state::{Proposal, ValidatorSetChange};
+pub use sut::has_quorum;
 
 // Node identifiers. They must match the `emerald_mbt.qnt` and
 // `emerald_tests.qnt` specifications.
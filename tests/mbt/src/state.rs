@@ -44,6 +44,16 @@ pub struct NodeState {
     pub proposals: BTreeSet<Proposal>,
 }
 
+/// Change to the validator set between two heights. Not part of the Quint
+/// model, which keeps the validator set static.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValidatorSetChange {
+    /// Add the node as a validator with the given voting power
+    Add(Node, u64),
+    /// Remove the node from the validator set
+    Remove(Node),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
 #[serde(tag = "tag")]
 pub enum FailureMode {
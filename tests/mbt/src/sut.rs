@@ -5,6 +5,8 @@ mod get_value;
 mod process_synced_value;
 mod receive_proposal;
 mod started_round;
+mod validate_block;
+mod validator_set;
 
 use anyhow::{anyhow, Result};
 pub use decided::mock_votes;
@@ -13,6 +15,7 @@ use emerald::node::AppRuntime;
 use malachitebft_app_channel::AppMsg;
 use malachitebft_eth_types::{Address, EmeraldContext};
 use tokio::sync::oneshot::Receiver;
+pub use validator_set::has_quorum;
 
 use crate::runtime::Runtime;
 
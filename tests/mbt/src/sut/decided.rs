@@ -2,14 +2,14 @@
 
 use std::collections::BTreeMap;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use malachitebft_app_channel::app::types::core::{
     CommitCertificate, NilOrVal, Round as EmeraldRound, SignedVote,
 };
 use malachitebft_app_channel::AppMsg;
 use malachitebft_eth_types::{EmeraldContext, Height as EmeraldHeight, Vote};
 
-use super::Sut;
+use super::{has_quorum, Sut};
 use crate::history::History;
 use crate::state::{Node, Proposal};
 
@@ -30,6 +30,13 @@ impl Sut {
         let height = EmeraldHeight::new(proposal.height);
         let round = EmeraldRound::new(proposal.round);
         let value_id = hist.get_value_id(&proposal)?;
+
+        if let Some(validator_set) = self.components.state.get_validator_set(height) {
+            if !has_quorum(validator_set, &votes) {
+                bail!("Votes for {proposal:?} don't reach a quorum of the validator set");
+            }
+        }
+
         let certificate = CommitCertificate::new(height, round, value_id, votes);
 
         let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
@@ -67,6 +74,15 @@ pub fn mock_votes(
     for (node, app) in sut {
         let state = &app.components.state;
         let addr = hist.get_address(node)?;
+
+        // Only validators active at the proposal height vote
+        if state
+            .get_validator_set(height)
+            .is_some_and(|validator_set| validator_set.get_by_address(&addr).is_none())
+        {
+            continue;
+        }
+
         let vote = Vote::new_precommit(height, round, NilOrVal::Val(value_id), addr);
         let sign = state.signing_provider.sign(&vote.to_sign_bytes());
         votes.push(SignedVote::new(vote, sign));
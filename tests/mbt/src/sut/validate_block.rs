@@ -0,0 +1,49 @@
+//! Feeds a tampered proposal block into Emerald's block validation.
+
+use alloy_rpc_types_engine::ExecutionPayloadV3;
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use emerald_core::BlockValidity;
+use malachitebft_app_channel::app::types::core::Round as EmeraldRound;
+use malachitebft_eth_types::Height as EmeraldHeight;
+use ssz::{Decode, Encode};
+
+use super::Sut;
+use crate::history::History;
+use crate::state::Proposal;
+
+impl Sut {
+    /// Validates the block of a previously recorded Quint proposal after
+    /// applying `tamper` to its execution payload.
+    ///
+    /// Returns the [BlockValidity] computed by Emerald so that callers can
+    /// assert on the reason a malformed block was rejected.
+    pub async fn validate_block<F>(
+        &mut self,
+        hist: &History,
+        proposal: &Proposal,
+        tamper: F,
+    ) -> Result<BlockValidity>
+    where
+        F: FnOnce(&mut ExecutionPayloadV3),
+    {
+        let value = hist.get_value(&proposal.id())?;
+        let mut payload = ExecutionPayloadV3::from_ssz_bytes(&value.extensions)
+            .map_err(|err| anyhow!("Failed to decode execution payload: {err:?}"))?;
+
+        tamper(&mut payload);
+        let data = Bytes::from(payload.as_ssz_bytes());
+
+        self.components
+            .state
+            .validate_block(
+                &data,
+                EmeraldHeight::new(proposal.height),
+                EmeraldRound::new(proposal.round),
+                &self.components.engine,
+                &self.components.emerald_config.retry_config,
+            )
+            .await
+            .map_err(|err| anyhow!("Failed to validate block: {err:?}"))
+    }
+}
@@ -0,0 +1,40 @@
+//! Validator set changes between heights, which the Quint model does not drive.
+
+use anyhow::{bail, Result};
+use malachitebft_app_channel::app::types::core::{SignedVote, ThresholdParams};
+use malachitebft_eth_types::{EmeraldContext, Height as EmeraldHeight, ValidatorSet};
+
+use super::Sut;
+use crate::state::Height;
+
+impl Sut {
+    /// Replaces the validator set used from the given height on. The height
+    /// must be the current consensus height.
+    pub fn set_validator_set(&mut self, height: Height, validator_set: ValidatorSet) -> Result<()> {
+        let state = &mut self.components.state;
+        let height = EmeraldHeight::new(height);
+        if state.consensus_height != height {
+            bail!(
+                "Can't change the validator set at {height}, consensus is at {}",
+                state.consensus_height
+            );
+        }
+
+        state.set_validator_set(height, validator_set);
+        Ok(())
+    }
+}
+
+/// Whether the voting power behind `votes` reaches a quorum (more than 2/3) of
+/// the total power of `validator_set`.
+pub fn has_quorum(validator_set: &ValidatorSet, votes: &[SignedVote<EmeraldContext>]) -> bool {
+    let weight = votes
+        .iter()
+        .filter_map(|vote| validator_set.get_by_address(&vote.message.validator_address))
+        .map(|validator| validator.voting_power)
+        .sum();
+
+    ThresholdParams::default()
+        .quorum
+        .is_met(weight, validator_set.total_voting_power())
+}
@@ -1,4 +1,5 @@
-use emerald_mbt::EmeraldDriver;
+use emerald_core::{BlockValidity, InvalidBlockReason};
+use emerald_mbt::{has_quorum, EmeraldDriver, Proposal, ValidatorSetChange};
 use quint_connect::{quint_run, quint_test};
 
 /// Happy path: all 3 nodes start from genesis, the proposer (node1) creates a
@@ -88,3 +89,56 @@ fn simulation_no_failures() -> impl Driver {
 fn simulation_with_failures() -> impl Driver {
     EmeraldDriver::default()
 }
+
+/// Invalid block: node1 proposes at height 1 with a timestamp that goes back
+/// in time; node2 must report the execution client's rejection of it.
+#[test]
+fn test_rejects_decreasing_timestamp() -> anyhow::Result<()> {
+    let mut driver = EmeraldDriver::default();
+
+    let validity = driver.inject_block("node1", "node2", |payload| {
+        payload.payload_inner.payload_inner.timestamp = 0;
+    })?;
+
+    assert_eq!(
+        validity,
+        BlockValidity::Invalid(InvalidBlockReason::Rejected)
+    );
+    Ok(())
+}
+
+/// Validator set change: node3 is removed after height 1, and height 2 is
+/// decided with the precommits of node1 and node2 only, which reach a quorum
+/// of the reduced total power but not of the original one.
+#[test]
+fn test_decides_after_validator_removed() -> anyhow::Result<()> {
+    let mut driver = EmeraldDriver::default();
+    driver.start_from_genesis()?;
+
+    let proposal = |height, payload| Proposal {
+        height,
+        round: 0,
+        proposer: "node1".to_string(),
+        payload,
+    };
+
+    assert_eq!(driver.decide(proposal(1, 0))?.len(), 3);
+
+    let node = "node1".to_string();
+    let previous = driver.validator_set(&node, 2)?;
+    let reduced = driver.change_validator_set(2, ValidatorSetChange::Remove("node3".into()))?;
+    assert_eq!(reduced.validators.len(), 2);
+    assert!(reduced.total_voting_power() < previous.total_voting_power());
+
+    let votes = driver.decide(proposal(2, 1))?;
+    assert_eq!(votes.len(), 2);
+    for sut in driver.sut.values() {
+        let latest = sut.components.state.latest_block.as_ref();
+        assert_eq!(latest.map(|block| block.block_number), Some(2));
+    }
+
+    // The certificate for height 2 would not have finalized under the old set
+    assert!(has_quorum(&reduced, &votes));
+    assert!(!has_quorum(&previous, &votes));
+    Ok(())
+}
@@ -7,9 +7,11 @@ mod aliases;
 mod context;
 mod genesis;
 mod height;
+mod node_identity;
 mod proposal;
 mod proposal_part;
 mod retry_config;
+mod signer_bitmap;
 mod signing;
 mod validator_set;
 mod value;
@@ -25,9 +27,11 @@ pub use crate::aliases::*;
 pub use crate::context::*;
 pub use crate::genesis::*;
 pub use crate::height::*;
+pub use crate::node_identity::*;
 pub use crate::proposal::*;
 pub use crate::proposal_part::*;
 pub use crate::retry_config::*;
+pub use crate::signer_bitmap::*;
 pub use crate::signing::*;
 pub use crate::validator_set::*;
 pub use crate::value::*;
@@ -0,0 +1,79 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bls12381::{MinPk, PrivateKey as BlsPrivateKey, PublicKey as BlsPublicKey};
+use crate::secp256k1::PublicKey;
+use crate::Address;
+
+/// Identity of a node, gathering its moniker, EVM address and BLS public key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeIdentity {
+    pub moniker: String,
+    pub evm_address: Address,
+    pub bls_pubkey: BlsPublicKey<MinPk>,
+}
+
+impl NodeIdentity {
+    /// Build the identity of a node from its configured moniker and loaded keys.
+    /// The EVM address is derived from the consensus public key.
+    pub fn new(
+        moniker: impl Into<String>,
+        consensus_key: &PublicKey,
+        bls_key: &BlsPrivateKey<MinPk>,
+    ) -> Self {
+        Self {
+            moniker: moniker.into(),
+            evm_address: Address::from_public_key(consensus_key),
+            bls_pubkey: bls_key.public_key(),
+        }
+    }
+}
+
+impl fmt::Display for NodeIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (address: {}, bls: {})",
+            self.moniker,
+            self.evm_address.to_alloy_address(),
+            self.bls_pubkey,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+
+    use super::*;
+    use crate::secp256k1::PrivateKey;
+
+    #[test]
+    fn test_node_identity_serde_roundtrip() {
+        // Anvil test account #0
+        let private_key = PrivateKey::from_slice(
+            b256!("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").as_ref(),
+        )
+        .unwrap();
+        let bls_key = BlsPrivateKey::<MinPk>::from_bytes(&[7; 32]).unwrap();
+
+        let identity = NodeIdentity::new("node-0", &private_key.public_key(), &bls_key);
+        assert_eq!(
+            identity.evm_address.to_alloy_address(),
+            address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+        assert_eq!(identity.bls_pubkey, bls_key.public_key());
+
+        let json = serde_json::to_string(&identity).unwrap();
+        let decoded: NodeIdentity = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, identity);
+
+        assert!(identity
+            .to_string()
+            .starts_with("node-0 (address: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert!(identity
+            .to_string()
+            .ends_with(&format!("bls: {})", identity.bls_pubkey)));
+    }
+}
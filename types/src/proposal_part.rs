@@ -9,24 +9,59 @@ use crate::codec::proto::{decode_signature, encode_signature};
 use crate::secp256k1::Signature;
 use crate::{Address, EmeraldContext, Height};
 
+/// Default size in bytes of the data parts a proposal is split into
+pub const DEFAULT_PROPOSAL_PART_SIZE: usize = 128 * 1024; // 128 KiB
+
+/// Smallest accepted proposal part size, below which the per-part overhead dominates
+pub const MIN_PROPOSAL_PART_SIZE: usize = 1024; // 1 KiB
+
+/// Largest accepted proposal part size, above which parts fragment at the transport
+pub const MAX_PROPOSAL_PART_SIZE: usize = 1024 * 1024; // 1 MiB
+
+/// A chunk of the proposed value, at position `index` among the data parts.
+///
+/// In [`ProposalInit::INDEXED`] proposals the index is covered by the proposer's
+/// Fin signature, see [`ProposalData::hash_into`]. Legacy proposals ignore it, and
+/// peers that predate it send 0.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProposalData {
+    pub index: u32,
     pub bytes: Bytes,
 }
 
 impl ProposalData {
-    pub fn new(bytes: Bytes) -> Self {
-        Self { bytes }
+    pub fn new(index: u32, bytes: Bytes) -> Self {
+        Self { index, bytes }
     }
 
     pub fn size_bytes(&self) -> usize {
         core::mem::size_of::<u64>()
     }
+
+    /// Feed this chunk into the digest signed by the proposer's Fin part, preceded
+    /// by its index when the proposal has the given `version` or later
+    /// [`ProposalInit::INDEXED`].
+    pub fn hash_into(&self, version: u32, mut update: impl FnMut(&[u8])) {
+        if version >= ProposalInit::INDEXED {
+            update(&self.index.to_be_bytes());
+        }
+        update(&self.bytes);
+    }
+
+    /// Split `data` into parts of at most `part_size` bytes, indexed from 0.
+    ///
+    /// Panics if `part_size` is 0.
+    pub fn split(data: &Bytes, part_size: usize) -> impl Iterator<Item = Self> + '_ {
+        (0..)
+            .zip(data.chunks(part_size))
+            .map(|(index, chunk)| Self::new(index, data.slice_ref(chunk)))
+    }
 }
 
 impl fmt::Debug for ProposalData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ProposalData")
+            .field("index", &self.index)
             .field("bytes", &"<...>")
             .field("len", &self.bytes.len())
             .finish()
@@ -77,6 +112,13 @@ impl ProposalPart {
         }
     }
 
+    /// Position of a data part among the data parts of its proposal.
+    ///
+    /// The init and fin parts have no index: they are always first and last.
+    pub fn part_index(&self) -> Option<u32> {
+        self.as_data().map(|data| data.index)
+    }
+
     pub fn to_sign_bytes(&self) -> Bytes {
         proto::Protobuf::to_bytes(self).unwrap()
     }
@@ -95,15 +137,53 @@ pub struct ProposalInit {
     #[serde(with = "RoundDef")]
     pub pol_round: Round,
     pub proposer: Address,
+    /// Layout of the proposal parts, [`Self::LEGACY`] or [`Self::INDEXED`]
+    #[serde(default)]
+    pub version: u32,
+    /// Number of data parts in an [`Self::INDEXED`] proposal, 0 otherwise
+    #[serde(default)]
+    pub total_parts: u32,
 }
 
 impl ProposalInit {
+    /// Data parts carry no meaningful index and the Fin signature covers only
+    /// the height, the round and the chunk bytes
+    pub const LEGACY: u32 = 0;
+
+    /// Data parts are indexed, the init part announces their number, and the
+    /// Fin signature covers both, so reordered or truncated parts are rejected
+    pub const INDEXED: u32 = 1;
+
     pub fn new(height: Height, round: Round, pol_round: Round, proposer: Address) -> Self {
         Self {
             height,
             round,
             pol_round,
             proposer,
+            version: Self::LEGACY,
+            total_parts: 0,
+        }
+    }
+
+    /// Announce an [`Self::INDEXED`] proposal of `total_parts` data parts
+    pub fn with_total_parts(mut self, total_parts: u32) -> Self {
+        self.version = Self::INDEXED;
+        self.total_parts = total_parts;
+        self
+    }
+
+    pub fn is_indexed(&self) -> bool {
+        self.version >= Self::INDEXED
+    }
+
+    /// Feed the fields covered by the proposer's Fin signature into `update`,
+    /// followed by each data part through [`ProposalData::hash_into`]
+    pub fn hash_into(&self, mut update: impl FnMut(&[u8])) {
+        update(&self.height.as_u64().to_be_bytes());
+        update(&self.round.as_i64().to_be_bytes());
+        if self.is_indexed() {
+            update(&self.version.to_be_bytes());
+            update(&self.total_parts.to_be_bytes());
         }
     }
 }
@@ -149,8 +229,10 @@ impl Protobuf for ProposalPart {
                     .proposer
                     .ok_or_else(|| ProtoError::missing_field::<Self::Proto>("proposer"))
                     .and_then(Address::from_proto)?,
+                version: init.version,
+                total_parts: init.total_parts,
             })),
-            Part::Data(data) => Ok(Self::Data(ProposalData::new(data.bytes))),
+            Part::Data(data) => Ok(Self::Data(ProposalData::new(data.index, data.bytes))),
             Part::Fin(fin) => Ok(Self::Fin(ProposalFin {
                 signature: fin
                     .signature
@@ -172,11 +254,14 @@ impl Protobuf for ProposalPart {
                     round: init.round.as_u32().unwrap(),
                     pol_round: init.pol_round.as_u32(),
                     proposer: Some(init.proposer.to_proto()?),
+                    version: init.version,
+                    total_parts: init.total_parts,
                 })),
             }),
             Self::Data(data) => Ok(Self::Proto {
                 part: Some(Part::Data(proto::ProposalData {
                     bytes: data.bytes.clone(),
+                    index: data.index,
                 })),
             }),
             Self::Fin(fin) => Ok(Self::Proto {
@@ -187,3 +272,103 @@ impl Protobuf for ProposalPart {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(init: &ProposalInit, data: &[ProposalData]) -> Vec<u8> {
+        let mut input = Vec::new();
+        init.hash_into(|bytes| input.extend_from_slice(bytes));
+        for part in data {
+            part.hash_into(init.version, |bytes| input.extend_from_slice(bytes));
+        }
+        input
+    }
+
+    #[test]
+    fn test_indexed_digest_binds_order_and_count() {
+        let init = ProposalInit::new(
+            Height::new(1),
+            Round::new(0),
+            Round::Nil,
+            Address::new([0; 20]),
+        );
+        let first = ProposalData::new(0, Bytes::from_static(b"ab"));
+        let second = ProposalData::new(1, Bytes::from_static(b"c"));
+        let moved = [
+            ProposalData::new(0, Bytes::from_static(b"a")),
+            ProposalData::new(1, Bytes::from_static(b"bc")),
+        ];
+
+        // Legacy proposals only hash the concatenated chunks, as before indices
+        assert_eq!(
+            digest(&init, &[first.clone(), second.clone()]),
+            digest(&init, &moved)
+        );
+
+        let indexed = init.clone().with_total_parts(2);
+        assert!(indexed.is_indexed());
+        assert_ne!(
+            digest(&indexed, &[first.clone(), second.clone()]),
+            digest(&indexed, &moved)
+        );
+        assert_ne!(
+            digest(&indexed, &[first.clone(), second.clone()]),
+            digest(&init.with_total_parts(1), &[first])
+        );
+    }
+
+    #[test]
+    fn test_part_index_and_version_round_trip() {
+        let data = ProposalPart::Data(ProposalData::new(1, Bytes::from_static(b"second")));
+        assert_eq!(data.part_index(), Some(1));
+        assert_eq!(
+            ProposalPart::from_bytes(&data.to_sign_bytes()).unwrap(),
+            data
+        );
+
+        let init = ProposalPart::Init(
+            ProposalInit::new(
+                Height::new(1),
+                Round::new(0),
+                Round::Nil,
+                Address::new([0; 20]),
+            )
+            .with_total_parts(3),
+        );
+        assert_eq!(
+            ProposalPart::from_bytes(&init.to_sign_bytes()).unwrap(),
+            init
+        );
+    }
+
+    #[test]
+    fn test_split_into_parts_of_configured_size() {
+        let data: Bytes = (0..10_000u32).map(|i| i as u8).collect::<Vec<_>>().into();
+
+        let parts: Vec<ProposalData> = ProposalData::split(&data, 4096).collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(
+            parts
+                .iter()
+                .map(|part| part.bytes.len())
+                .collect::<Vec<_>>(),
+            vec![4096, 4096, 1808]
+        );
+        assert_eq!(
+            parts.iter().map(|part| part.index).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let reassembled: Vec<u8> = parts
+            .iter()
+            .flat_map(|part| part.bytes.iter().copied())
+            .collect();
+        assert_eq!(reassembled, data);
+
+        assert_eq!(ProposalData::split(&data, 10_000).count(), 1);
+        assert_eq!(ProposalData::split(&data, 1000).count(), 10);
+        assert_eq!(ProposalData::split(&Bytes::new(), 4096).count(), 0);
+    }
+}
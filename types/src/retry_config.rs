@@ -46,4 +46,60 @@ impl RetryConfig {
         let next = current_delay.mul_f64(self.multiplier);
         core::cmp::min(next, self.max_delay)
     }
+
+    /// Delays slept between successive attempts, in order, stopping before
+    /// their total would exceed `max_elapsed_time`
+    pub fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        let first = core::cmp::min(self.initial_delay, self.max_delay);
+        let mut elapsed = Duration::ZERO;
+        core::iter::successors(Some(first), |delay| Some(self.next_delay(*delay))).take_while(
+            move |delay| {
+                elapsed += *delay;
+                !delay.is_zero() && elapsed <= self.max_elapsed_time
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(multiplier: f64) -> RetryConfig {
+        RetryConfig {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(800),
+            max_elapsed_time: Duration::from_secs(3),
+            multiplier,
+        }
+    }
+
+    #[test]
+    fn delays_back_off_up_to_the_timeout() {
+        let ms = |delays: &[u64]| -> Vec<Duration> {
+            delays.iter().copied().map(Duration::from_millis).collect()
+        };
+
+        let constant: Vec<_> = config(1.0).delays().collect();
+        assert_eq!(constant, ms(&[100; 30]));
+
+        let doubling: Vec<_> = config(2.0).delays().collect();
+        assert_eq!(doubling, ms(&[100, 200, 400, 800, 800]));
+
+        for multiplier in [1.0, 1.5, 2.0, 3.0] {
+            let config = config(multiplier);
+            let total: Duration = config.delays().sum();
+            assert!(total <= config.max_elapsed_time);
+            assert!(total + config.max_delay > config.max_elapsed_time);
+        }
+    }
+
+    #[test]
+    fn zero_initial_delay_has_no_delays() {
+        let config = RetryConfig {
+            initial_delay: Duration::ZERO,
+            ..config(2.0)
+        };
+        assert_eq!(config.delays().count(), 0);
+    }
 }
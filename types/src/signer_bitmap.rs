@@ -0,0 +1,93 @@
+use thiserror::Error;
+
+use crate::{ValidatorSet, B256};
+
+/// Signers of an aggregate signature, as a bitmap over validator indices.
+///
+/// Bit `i % 8` of byte `i / 8` is set when the validator at index `i` signed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SignerBitmap {
+    bytes: Vec<u8>,
+}
+
+impl SignerBitmap {
+    /// Empty bitmap over `validators` validators
+    pub fn with_len(validators: usize) -> Self {
+        Self {
+            bytes: vec![0; validators.div_ceil(8)],
+        }
+    }
+
+    /// Mark the validator at `index` as a signer, growing the bitmap if needed
+    pub fn set(&mut self, index: usize) {
+        let byte = index / 8;
+        if byte >= self.bytes.len() {
+            self.bytes.resize(byte + 1, 0);
+        }
+        self.bytes[byte] |= 1 << (index % 8);
+    }
+
+    /// Whether the validator at `index` signed
+    pub fn is_set(&self, index: usize) -> bool {
+        self.bytes
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Indices of the signers, in increasing order
+    pub fn signers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.bytes.len() * 8).filter(|index| self.is_set(*index))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<Vec<u8>> for SignerBitmap {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+}
+
+/// A [`SignerBitmap`] tagged with the [`ValidatorSet::checksum`] of the set its
+/// indices refer to, so that it is not read against a different set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TaggedSignerBitmap {
+    pub validator_set: B256,
+    pub signers: SignerBitmap,
+}
+
+impl TaggedSignerBitmap {
+    /// Tag `signers`, built against the order of `validator_set`
+    pub fn new(signers: SignerBitmap, validator_set: &ValidatorSet) -> Self {
+        Self {
+            validator_set: validator_set.checksum(),
+            signers,
+        }
+    }
+
+    /// The 32-byte checksum of the validator set followed by the bitmap bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.validator_set.as_slice(), self.signers.as_bytes()].concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MissingTag> {
+        if bytes.len() < B256::len_bytes() {
+            return Err(MissingTag { len: bytes.len() });
+        }
+        let (tag, signers) = bytes.split_at(B256::len_bytes());
+        Ok(Self {
+            validator_set: B256::from_slice(tag),
+            signers: SignerBitmap::from(signers.to_vec()),
+        })
+    }
+}
+
+/// Error returned by [`TaggedSignerBitmap::from_bytes`] for bytes too short to
+/// hold the validator set checksum
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("tagged signer bitmap is {len} bytes, shorter than its 32-byte tag")]
+pub struct MissingTag {
+    pub len: usize,
+}
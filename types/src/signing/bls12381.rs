@@ -1,15 +1,24 @@
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
+use std::collections::HashSet;
+use std::path::PathBuf;
 
-use alloy_primitives::keccak256;
+use alloy_primitives::{hex, keccak256};
 use async_trait::async_trait;
 use blst::{min_pk, min_sig, BLST_ERROR};
 use bytes::Bytes;
-use malachitebft_core_types::{Context, SignedExtension, SignedMessage, SigningScheme};
+use malachitebft_core_types::{
+    Context, SignedExtension, SignedMessage, SigningScheme, VotingPower,
+};
 use malachitebft_signing::{Error as SigningError, SigningProvider, VerificationResult};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 use super::Hashable;
-use crate::{Proposal, ProposalPart, Vote};
+use crate::{Proposal, ProposalPart, QuorumThreshold, SignerBitmap, Vote};
 
 // IETF BLS ciphersuite for min-sig mode (signature in G1, public key in G2), with PoP.
 // Ethereum consensus uses the companion min-pk ciphersuite below (signature in G2).
@@ -19,6 +28,64 @@ const DST_BLS_SIG_IN_G1_WITH_POP: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_
 // This is the ciphersuite used by Ethereum consensus BLS signatures.
 const DST_BLS_SIG_IN_G2_WITH_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
+// Proof-of-possession tags for the two ciphersuites above. A PoP is a signature
+// over the signer's own serialized public key under this separate DST.
+const DST_BLS_POP_IN_G1: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+const DST_BLS_POP_IN_G2: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// Tags for signatures over a 32-byte digest computed by the caller, see
+// `PrivateKey::sign_prehashed`. Distinct from the message DSTs above so that a
+// signature over a digest is never valid as a signature over a message.
+const DST_BLS_PREHASHED_IN_G1: &[u8] = b"EMERALD_BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_PREHASHED_";
+const DST_BLS_PREHASHED_IN_G2: &[u8] = b"EMERALD_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_PREHASHED_";
+
+// Bits of randomness per signature when batch verifying.
+const BATCH_RAND_BITS: usize = 64;
+
+// BLS12-381 base field modulus, big-endian.
+const FIELD_MODULUS: [u8; 48] = hex!(
+    "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+);
+
+// Flag bits in the most significant byte of a compressed point (ZCash serialization format).
+const FLAG_COMPRESSED: u8 = 0x80;
+const FLAG_INFINITY: u8 = 0x40;
+const FLAG_SIGN: u8 = 0x20;
+const FLAG_MASK: u8 = FLAG_COMPRESSED | FLAG_INFINITY | FLAG_SIGN;
+
+/// Checks that `bytes` is the canonical compressed encoding of a G1 or G2 point:
+/// the compression flag is set, the point at infinity has no sign and an all-zero
+/// body, and every coordinate limb is reduced modulo the field prime.
+fn check_canonical_encoding(bytes: &[u8]) -> Result<(), BLST_ERROR> {
+    let Some(&first) = bytes.first() else {
+        return Err(BLST_ERROR::BLST_BAD_ENCODING);
+    };
+    let flags = first & FLAG_MASK;
+
+    if flags & FLAG_COMPRESSED == 0 {
+        return Err(BLST_ERROR::BLST_BAD_ENCODING);
+    }
+
+    let mut body = bytes.to_vec();
+    body[0] &= !FLAG_MASK;
+
+    if flags & FLAG_INFINITY != 0 {
+        if flags & FLAG_SIGN != 0 || body.iter().any(|byte| *byte != 0) {
+            return Err(BLST_ERROR::BLST_BAD_ENCODING);
+        }
+        return Ok(());
+    }
+
+    if body
+        .chunks(FIELD_MODULUS.len())
+        .any(|limb| limb >= FIELD_MODULUS.as_slice())
+    {
+        return Err(BLST_ERROR::BLST_BAD_ENCODING);
+    }
+
+    Ok(())
+}
+
 pub trait BlsVariant: Clone + core::fmt::Debug + Eq + Ord + Send + Sync + 'static {
     type SecretKey: Clone + Send + Sync;
     type PublicKey;
@@ -28,27 +95,101 @@ pub trait BlsVariant: Clone + core::fmt::Debug + Eq + Ord + Send + Sync + 'stati
     const PK_LEN: usize;
     const SIG_LEN: usize;
     const DST: &'static [u8];
+    const POP_DST: &'static [u8];
+    const PREHASHED_DST: &'static [u8];
 
     fn key_gen(ikm: &[u8]) -> Result<Self::SecretKey, BLST_ERROR>;
     fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, BLST_ERROR>;
     fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8>;
 
     fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, BLST_ERROR>;
+    /// Like `public_key_from_bytes`, but also rejects the identity and points outside the subgroup.
+    fn public_key_from_bytes_strict(bytes: &[u8]) -> Result<Self::PublicKey, BLST_ERROR>;
     fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
     fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey;
 
     fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, BLST_ERROR>;
+    /// Like `signature_from_bytes`, but also rejects the identity and points outside the subgroup.
+    fn signature_from_bytes_strict(bytes: &[u8]) -> Result<Self::Signature, BLST_ERROR>;
     fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8>;
 
-    fn sign(secret_key: &Self::SecretKey, msg: &[u8]) -> Self::Signature;
-    fn verify(signature: &Self::Signature, msg: &[u8], public_key: &Self::PublicKey) -> BLST_ERROR;
+    fn sign(secret_key: &Self::SecretKey, msg: &[u8]) -> Self::Signature {
+        Self::sign_with_dst(secret_key, msg, Self::DST)
+    }
+
+    fn verify(signature: &Self::Signature, msg: &[u8], public_key: &Self::PublicKey) -> BLST_ERROR {
+        Self::verify_with_dst(signature, msg, Self::DST, public_key)
+    }
+
+    fn sign_with_dst(secret_key: &Self::SecretKey, msg: &[u8], dst: &[u8]) -> Self::Signature;
+    fn verify_with_dst(
+        signature: &Self::Signature,
+        msg: &[u8],
+        dst: &[u8],
+        public_key: &Self::PublicKey,
+    ) -> BLST_ERROR;
+
+    /// Verify several signatures at once, each over its own message, using
+    /// random linear combinations with scalars drawn from `rng`. Succeeds only
+    /// if every signature is valid.
+    fn verify_multiple_with_dst<R: RngCore + ?Sized>(
+        signatures: &[&Self::Signature],
+        msgs: &[&[u8]],
+        dst: &[u8],
+        public_keys: &[&Self::PublicKey],
+        rng: &mut R,
+    ) -> BLST_ERROR;
+
+    /// Combine signatures over the same message into a single one.
+    fn aggregate_signatures(signatures: &[&Self::Signature])
+        -> Result<Self::Signature, BLST_ERROR>;
+
+    /// Combine public keys into the key that verifies their aggregate signature
+    /// over a single message.
+    fn aggregate_public_keys(
+        public_keys: &[&Self::PublicKey],
+    ) -> Result<Self::PublicKey, BLST_ERROR>;
+
+    /// Verify an aggregate of signatures by all of `public_keys` over the same message.
+    fn fast_aggregate_verify_with_dst(
+        signature: &Self::Signature,
+        msg: &[u8],
+        dst: &[u8],
+        public_keys: &[&Self::PublicKey],
+    ) -> BLST_ERROR;
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
 #[error("BLS decoding failed: {0:?}")]
 pub struct BlsDecodingError(pub BLST_ERROR);
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Errors returned by [`Signature::aggregate`] and [`PublicKey::aggregate`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum AggregateError {
+    #[error("nothing to aggregate")]
+    Empty,
+
+    #[error(transparent)]
+    Decoding(#[from] BlsDecodingError),
+}
+
+/// Writes `label(0x01234567..89abcdef)`, showing the first and last bytes of
+/// `bytes`, or all of them when they are short enough.
+fn fmt_truncated(f: &mut core::fmt::Formatter<'_>, label: &str, bytes: &[u8]) -> core::fmt::Result {
+    const SHOWN: usize = 4;
+
+    if bytes.len() <= 2 * SHOWN {
+        return write!(f, "{label}({})", hex::encode_prefixed(bytes));
+    }
+    write!(
+        f,
+        "{label}({}..{})",
+        hex::encode_prefixed(&bytes[..SHOWN]),
+        hex::encode(&bytes[bytes.len() - SHOWN..])
+    )
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Signature<V: BlsVariant> {
     bytes: Vec<u8>,
     _marker: PhantomData<V>,
@@ -70,6 +211,20 @@ impl<V: BlsVariant> Signature<V> {
         })
     }
 
+    /// Decodes a signature received from an untrusted source, rejecting any
+    /// non-canonical encoding as Ethereum consensus does.
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<Self, BLST_ERROR> {
+        if bytes.len() != V::SIG_LEN {
+            return Err(BLST_ERROR::BLST_BAD_ENCODING);
+        }
+        check_canonical_encoding(bytes)?;
+        let sig = V::signature_from_bytes_strict(bytes)?;
+        Ok(Self {
+            bytes: V::signature_to_bytes(&sig).to_vec(),
+            _marker: PhantomData,
+        })
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.bytes.clone()
     }
@@ -77,9 +232,67 @@ impl<V: BlsVariant> Signature<V> {
     pub fn verify(&self, data: &[u8], public_key: &PublicKey<V>) -> bool {
         public_key.verify(data, self)
     }
+
+    /// Combine signatures over the same message into one, which verifies
+    /// against all of their public keys with [`Self::verify_aggregate`], or
+    /// against their [`PublicKey::aggregate`].
+    ///
+    /// Fails with [`AggregateError::Empty`] when there is nothing to
+    /// aggregate, and with the decoding error of the first malformed input.
+    pub fn aggregate(signatures: &[Self]) -> Result<Self, AggregateError> {
+        if signatures.is_empty() {
+            return Err(AggregateError::Empty);
+        }
+        let sigs = signatures
+            .iter()
+            .map(|signature| {
+                if signature.bytes.len() != V::SIG_LEN {
+                    return Err(BLST_ERROR::BLST_BAD_ENCODING);
+                }
+                V::signature_from_bytes(&signature.bytes)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(BlsDecodingError)?;
+        let sig_refs: Vec<&V::Signature> = sigs.iter().collect();
+        let aggregate = V::aggregate_signatures(&sig_refs).map_err(BlsDecodingError)?;
+        Ok(Self {
+            bytes: V::signature_to_bytes(&aggregate),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Verify an aggregate signature over `data` by all of `public_keys`.
+    pub fn verify_aggregate(&self, data: &[u8], public_keys: &[PublicKey<V>]) -> bool {
+        let Ok(sig) = V::signature_from_bytes(&self.bytes) else {
+            return false;
+        };
+        let Ok(pks) = public_keys
+            .iter()
+            .map(|public_key| V::public_key_from_bytes(&public_key.bytes))
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            return false;
+        };
+        let pk_refs: Vec<&V::PublicKey> = pks.iter().collect();
+
+        V::fast_aggregate_verify_with_dst(&sig, data, V::DST, &pk_refs) == BLST_ERROR::BLST_SUCCESS
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Truncated for logs, see [`fmt_truncated`]; [`Display`](core::fmt::Display) shows all bytes.
+impl<V: BlsVariant> core::fmt::Debug for Signature<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_truncated(f, "Sig", &self.bytes)
+    }
+}
+
+impl<V: BlsVariant> core::fmt::Display for Signature<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&hex::encode_prefixed(&self.bytes))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PublicKey<V: BlsVariant> {
     bytes: Vec<u8>,
     _marker: PhantomData<V>,
@@ -101,10 +314,49 @@ impl<V: BlsVariant> PublicKey<V> {
         })
     }
 
+    /// Decodes a public key received from an untrusted source, rejecting any
+    /// non-canonical encoding as Ethereum consensus does.
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<Self, BLST_ERROR> {
+        if bytes.len() != V::PK_LEN {
+            return Err(BLST_ERROR::BLST_BAD_ENCODING);
+        }
+        check_canonical_encoding(bytes)?;
+        let pk = V::public_key_from_bytes_strict(bytes)?;
+        Ok(Self {
+            bytes: V::public_key_to_bytes(&pk).to_vec(),
+            _marker: PhantomData,
+        })
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.bytes.clone()
     }
 
+    /// Combine public keys into one that verifies the [`Signature::aggregate`]
+    /// of their signatures over a single message. Only sound for keys admitted
+    /// with a proof of possession.
+    pub fn aggregate(public_keys: &[Self]) -> Result<Self, AggregateError> {
+        if public_keys.is_empty() {
+            return Err(AggregateError::Empty);
+        }
+        let pks = public_keys
+            .iter()
+            .map(|public_key| {
+                if public_key.bytes.len() != V::PK_LEN {
+                    return Err(BLST_ERROR::BLST_BAD_ENCODING);
+                }
+                V::public_key_from_bytes(&public_key.bytes)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(BlsDecodingError)?;
+        let pk_refs: Vec<&V::PublicKey> = pks.iter().collect();
+        let aggregate = V::aggregate_public_keys(&pk_refs).map_err(BlsDecodingError)?;
+        Ok(Self {
+            bytes: V::public_key_to_bytes(&aggregate),
+            _marker: PhantomData,
+        })
+    }
+
     pub fn verify(&self, data: &[u8], signature: &Signature<V>) -> bool {
         // TODO: avoid reparsing signature/public key bytes on every verify call; keep a parsed form
         // or cache decoded blst values for consensus hot paths.
@@ -117,6 +369,56 @@ impl<V: BlsVariant> PublicKey<V> {
 
         V::verify(&sig, data, &pk) == BLST_ERROR::BLST_SUCCESS
     }
+
+    /// Verify a signature over a 32-byte digest, produced by [`PrivateKey::sign_prehashed`].
+    pub fn verify_prehashed(&self, digest: &[u8; 32], signature: &Signature<V>) -> bool {
+        let Ok(sig) = V::signature_from_bytes(&signature.bytes) else {
+            return false;
+        };
+        let Ok(pk) = V::public_key_from_bytes(&self.bytes) else {
+            return false;
+        };
+
+        V::verify_with_dst(&sig, digest, V::PREHASHED_DST, &pk) == BLST_ERROR::BLST_SUCCESS
+    }
+
+    /// Check a proof of possession produced by [`PrivateKey::prove_possession`].
+    pub fn verify_possession(&self, proof: &Signature<V>) -> bool {
+        verify_possessions(&[(self.clone(), proof.clone())])[0]
+    }
+}
+
+/// Truncated for logs, see [`fmt_truncated`]; [`Display`](core::fmt::Display) shows all bytes.
+impl<V: BlsVariant> core::fmt::Debug for PublicKey<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_truncated(f, "Pk", &self.bytes)
+    }
+}
+
+impl<V: BlsVariant> core::fmt::Display for PublicKey<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&hex::encode_prefixed(&self.bytes))
+    }
+}
+
+impl<V: BlsVariant> Hash for PublicKey<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+impl<V: BlsVariant> Serialize for PublicKey<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode_prefixed(&self.bytes))
+    }
+}
+
+impl<'de, V: BlsVariant> Deserialize<'de> for PublicKey<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&encoded).map_err(serde::de::Error::custom)?;
+        Self::from_bytes_strict(&bytes).map_err(|e| serde::de::Error::custom(BlsDecodingError(e)))
+    }
 }
 
 #[derive(Clone)]
@@ -151,6 +453,333 @@ impl<V: BlsVariant> PrivateKey<V> {
             _marker: PhantomData,
         }
     }
+
+    /// Signs a 32-byte digest of a message computed by the caller, e.g. for a
+    /// hardware signer that only accepts digests. Verify with
+    /// [`PublicKey::verify_prehashed`].
+    ///
+    /// The digest is hashed to the curve under its own DST, so a prehashed
+    /// signature never verifies as a regular signature, nor the reverse.
+    /// The security of the signature rests on the collision resistance of the
+    /// hash the caller used to compute the digest, and the signer cannot check
+    /// what message the digest stands for: callers must hash messages with a
+    /// domain-separated, collision-resistant hash and only sign digests they
+    /// computed themselves.
+    pub fn sign_prehashed(&self, digest: &[u8; 32]) -> Signature<V> {
+        let sig = V::sign_with_dst(&self.inner, digest, V::PREHASHED_DST);
+        Signature {
+            bytes: V::signature_to_bytes(&sig).to_vec(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Proof of possession of this key, to be checked with
+    /// [`PublicKey::verify_possession`] before the key is admitted.
+    pub fn prove_possession(&self) -> Signature<V> {
+        let public_key = self.public_key();
+        let sig = V::sign_with_dst(&self.inner, &public_key.bytes, V::POP_DST);
+        Signature {
+            bytes: V::signature_to_bytes(&sig).to_vec(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Verify a batch of proofs of possession, returning whether each entry is valid.
+///
+/// All proofs are first checked together; if that fails, each entry is checked
+/// on its own to find the invalid ones.
+pub fn verify_possessions<V: BlsVariant>(entries: &[(PublicKey<V>, Signature<V>)]) -> Vec<bool> {
+    verify_possessions_with_rng(entries, &mut OsRng)
+}
+
+/// [`verify_possessions`], drawing the batch scalars from `rng`.
+///
+/// The scalars must be unpredictable to whoever produced the proofs, otherwise
+/// invalid proofs can be crafted to cancel out in the batch check. Only pass a
+/// seeded RNG in tests.
+pub fn verify_possessions_with_rng<V: BlsVariant, R: RngCore + ?Sized>(
+    entries: &[(PublicKey<V>, Signature<V>)],
+    rng: &mut R,
+) -> Vec<bool> {
+    let decoded: Vec<Option<(V::PublicKey, V::Signature)>> = entries
+        .iter()
+        .map(|(public_key, proof)| {
+            let pk = V::public_key_from_bytes_strict(&public_key.bytes).ok()?;
+            let sig = V::signature_from_bytes_strict(&proof.bytes).ok()?;
+            Some((pk, sig))
+        })
+        .collect();
+
+    let valid: Vec<(&[u8], &V::PublicKey, &V::Signature)> = entries
+        .iter()
+        .zip(&decoded)
+        .filter_map(|((public_key, _), entry)| {
+            entry
+                .as_ref()
+                .map(|(pk, sig)| (public_key.bytes.as_slice(), pk, sig))
+        })
+        .collect();
+
+    if !valid.is_empty() {
+        let msgs: Vec<&[u8]> = valid.iter().map(|(msg, _, _)| *msg).collect();
+        let pks: Vec<&V::PublicKey> = valid.iter().map(|(_, pk, _)| *pk).collect();
+        let sigs: Vec<&V::Signature> = valid.iter().map(|(_, _, sig)| *sig).collect();
+
+        if V::verify_multiple_with_dst(&sigs, &msgs, V::POP_DST, &pks, rng)
+            == BLST_ERROR::BLST_SUCCESS
+        {
+            return decoded.iter().map(Option::is_some).collect();
+        }
+    }
+
+    entries
+        .iter()
+        .zip(&decoded)
+        .map(|((public_key, _), entry)| {
+            entry.as_ref().is_some_and(|(pk, sig)| {
+                V::verify_with_dst(sig, &public_key.bytes, V::POP_DST, pk)
+                    == BLST_ERROR::BLST_SUCCESS
+            })
+        })
+        .collect()
+}
+
+/// Errors returned by [`collect_quorum`]
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum QuorumError {
+    #[error("vote from unknown validator index {index}")]
+    UnknownSigner { index: usize },
+
+    #[error("validator {index} voted more than once")]
+    DuplicateSigner { index: usize },
+
+    #[error("invalid signature from validator {index}")]
+    InvalidSignature { index: usize },
+
+    #[error("signatures do not aggregate into a valid signature")]
+    InvalidAggregate,
+
+    #[error("signers hold {signed} of {total} voting power, which is not a quorum")]
+    InsufficientPower {
+        signed: VotingPower,
+        total: VotingPower,
+    },
+
+    #[error("total voting power of the validators overflows")]
+    PowerOverflow,
+}
+
+/// Aggregate the votes of a round over `msg` into a quorum certificate.
+///
+/// `votes` pairs each signature with the index of its signer in `validators`.
+/// The signers must hold more than 2/3 of the voting power of `validators`,
+/// whose keys must have been admitted with a proof of possession. Returns the
+/// aggregate signature and the bitmap of signers.
+///
+/// The aggregate is verified once; individual signatures are only checked when
+/// it fails, to report the offending signer.
+pub fn collect_quorum<V: BlsVariant>(
+    votes: &[(usize, Signature<V>)],
+    validators: &[(PublicKey<V>, VotingPower)],
+    msg: &[u8],
+) -> Result<(Signature<V>, SignerBitmap), QuorumError> {
+    collect_quorum_with_threshold(votes, validators, msg, QuorumThreshold::default())
+}
+
+/// [`collect_quorum`] for a deployment that requires the signers to exceed
+/// `threshold` of the voting power of `validators` rather than 2/3.
+pub fn collect_quorum_with_threshold<V: BlsVariant>(
+    votes: &[(usize, Signature<V>)],
+    validators: &[(PublicKey<V>, VotingPower)],
+    msg: &[u8],
+    threshold: QuorumThreshold,
+) -> Result<(Signature<V>, SignerBitmap), QuorumError> {
+    let mut signers = SignerBitmap::with_len(validators.len());
+    let mut public_keys = Vec::with_capacity(votes.len());
+    let mut signed: VotingPower = 0;
+
+    for (index, _) in votes {
+        let index = *index;
+        let (public_key, power) = validators
+            .get(index)
+            .ok_or(QuorumError::UnknownSigner { index })?;
+        if signers.is_set(index) {
+            return Err(QuorumError::DuplicateSigner { index });
+        }
+        signers.set(index);
+        public_keys.push(public_key.clone());
+        signed = signed
+            .checked_add(*power)
+            .ok_or(QuorumError::PowerOverflow)?;
+    }
+
+    let total = validators
+        .iter()
+        .try_fold(0, |total: VotingPower, (_, power)| {
+            total.checked_add(*power)
+        })
+        .ok_or(QuorumError::PowerOverflow)?;
+    if !threshold.is_met(signed, total) {
+        return Err(QuorumError::InsufficientPower { signed, total });
+    }
+
+    let signatures: Vec<Signature<V>> = votes.iter().map(|(_, sig)| sig.clone()).collect();
+    let aggregate = Signature::aggregate(&signatures)
+        .ok()
+        .filter(|aggregate| aggregate.verify_aggregate(msg, &public_keys));
+
+    match aggregate {
+        Some(aggregate) => Ok((aggregate, signers)),
+        None => Err(votes
+            .iter()
+            .zip(&public_keys)
+            .find(|((_, sig), public_key)| !sig.verify(msg, public_key))
+            .map_or(QuorumError::InvalidAggregate, |((index, _), _)| {
+                QuorumError::InvalidSignature { index: *index }
+            })),
+    }
+}
+
+/// Replacement of a validator's consensus key, as registered on chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRotation<V: BlsVariant> {
+    pub old: PublicKey<V>,
+    pub new: PublicKey<V>,
+    /// Proof of possession of `new`, see [`PrivateKey::prove_possession`].
+    pub new_pop: Signature<V>,
+}
+
+impl<V: BlsVariant> KeyRotation<V> {
+    /// Check that the rotating validator controls the new key, so it cannot
+    /// claim a key belonging to someone else.
+    pub fn verify(&self) -> bool {
+        self.new.verify_possession(&self.new_pop)
+    }
+}
+
+/// Verify `signature` over `data` only if `public_key` is in `allowlist`.
+///
+/// Unknown keys are rejected before any pairing is computed, so a node can
+/// drop messages from outside the validator set at the cost of a lookup.
+pub fn verify_allowlisted<V: BlsVariant>(
+    allowlist: &HashSet<PublicKey<V>>,
+    data: &[u8],
+    signature: &Signature<V>,
+    public_key: &PublicKey<V>,
+) -> bool {
+    allowlist.contains(public_key) && public_key.verify(data, signature)
+}
+
+/// Find the contributions to blame for an aggregate signature over `msg` that
+/// fails to verify, returning the indices of the `individual` signatures that
+/// don't verify against the public key at the same index.
+///
+/// Returns no indices when the aggregate verifies. A public key without a
+/// matching signature is reported as well.
+pub fn diagnose_aggregate<V: BlsVariant>(
+    public_keys: &[PublicKey<V>],
+    msg: &[u8],
+    aggregate: &Signature<V>,
+    individual: &[Signature<V>],
+) -> Vec<usize> {
+    if aggregate.verify_aggregate(msg, public_keys) {
+        return Vec::new();
+    }
+
+    public_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, public_key)| {
+            individual
+                .get(*index)
+                .is_none_or(|signature| !public_key.verify(msg, signature))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Errors returned when loading a BLS private key from the environment.
+///
+/// None of the variants carry key material, so they are safe to log.
+#[derive(Debug, Error)]
+pub enum KeyLoadError {
+    #[error("neither {var} nor {var}_FILE is set")]
+    NotSet { var: String },
+
+    #[error("both {var} and {var}_FILE are set")]
+    Ambiguous { var: String },
+
+    #[error("failed to read BLS key file {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("BLS key from {source_name} is not valid hex")]
+    InvalidHex { source_name: String },
+
+    #[error("BLS key from {source_name} is {len} bytes, expected {expected}")]
+    InvalidLength {
+        source_name: String,
+        len: usize,
+        expected: usize,
+    },
+
+    #[error("BLS key from {source_name} is not a valid secret key: {error:?}")]
+    InvalidKey {
+        source_name: String,
+        error: BLST_ERROR,
+    },
+}
+
+/// Load a hex-encoded BLS private key from the environment variable `var`,
+/// or from the file named by `{var}_FILE`.
+///
+/// Intermediate buffers holding the key are zeroized on drop.
+pub fn load_bls_key_from_env<V: BlsVariant>(var: &str) -> Result<PrivateKey<V>, KeyLoadError> {
+    let file_var = format!("{var}_FILE");
+
+    let (encoded, source_name) = match (std::env::var(var), std::env::var_os(&file_var)) {
+        (Ok(_), Some(_)) => {
+            return Err(KeyLoadError::Ambiguous {
+                var: var.to_owned(),
+            })
+        }
+        (Ok(value), None) => (Zeroizing::new(value), var.to_owned()),
+        (Err(_), Some(path)) => {
+            let path = PathBuf::from(path);
+            let value = std::fs::read_to_string(&path).map_err(|source| KeyLoadError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            (Zeroizing::new(value), path.display().to_string())
+        }
+        (Err(_), None) => {
+            return Err(KeyLoadError::NotSet {
+                var: var.to_owned(),
+            })
+        }
+    };
+
+    let trimmed = encoded.trim();
+    let digits = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+
+    let expected = PrivateKey::<V>::LENGTH;
+    if digits.len() != expected * 2 {
+        return Err(KeyLoadError::InvalidLength {
+            source_name,
+            len: digits.len() / 2,
+            expected,
+        });
+    }
+
+    let mut bytes = Zeroizing::new(vec![0u8; expected]);
+    hex::decode_to_slice(digits, &mut bytes).map_err(|_| KeyLoadError::InvalidHex {
+        source_name: source_name.clone(),
+    })?;
+
+    PrivateKey::from_bytes(&bytes).map_err(|error| KeyLoadError::InvalidKey { source_name, error })
 }
 
 impl<V: BlsVariant> Hashable for PublicKey<V> {
@@ -174,7 +803,7 @@ where
     type PrivateKey = PrivateKey<V>;
 
     fn decode_signature(bytes: &[u8]) -> Result<Self::Signature, Self::DecodingError> {
-        Signature::from_bytes(bytes).map_err(BlsDecodingError)
+        Signature::from_bytes_strict(bytes).map_err(BlsDecodingError)
     }
 
     fn encode_signature(signature: &Self::Signature) -> Vec<u8> {
@@ -182,8 +811,13 @@ where
     }
 }
 
+/// Error returned when a verify-only [`BlsProvider`] is asked to sign
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("BLS provider is verify-only and holds no private key")]
+pub struct VerifyOnlyError;
+
 pub struct BlsProvider<V: BlsVariant> {
-    private_key: PrivateKey<V>,
+    private_key: Option<PrivateKey<V>>,
 }
 
 impl<V: BlsVariant> core::fmt::Debug for BlsProvider<V> {
@@ -194,15 +828,80 @@ impl<V: BlsVariant> core::fmt::Debug for BlsProvider<V> {
 
 impl<V: BlsVariant> BlsProvider<V> {
     pub fn new(private_key: PrivateKey<V>) -> Self {
-        Self { private_key }
+        Self {
+            private_key: Some(private_key),
+        }
     }
 
-    pub fn private_key(&self) -> &PrivateKey<V> {
-        &self.private_key
+    /// A provider for sentry nodes that only verify: every signing attempt
+    /// fails with [`VerifyOnlyError`], since it means the node is misconfigured.
+    pub fn verify_only() -> Self {
+        Self { private_key: None }
     }
 
-    pub fn sign(&self, data: &[u8]) -> Signature<V> {
-        self.private_key.sign(data)
+    pub fn is_verify_only(&self) -> bool {
+        self.private_key.is_none()
+    }
+
+    pub fn private_key(&self) -> Option<&PrivateKey<V>> {
+        self.private_key.as_ref()
+    }
+
+    pub fn sign(&self, data: &[u8]) -> Result<Signature<V>, VerifyOnlyError> {
+        self.private_key
+            .as_ref()
+            .map(|key| key.sign(data))
+            .ok_or(VerifyOnlyError)
+    }
+
+    /// Signs `data` with an empty DST, to reproduce third-party test vectors.
+    ///
+    /// Unsafe for production: the signature is not bound to the PoP ciphersuite.
+    #[cfg(feature = "interop")]
+    pub fn sign_raw(&self, data: &[u8]) -> Result<Signature<V>, VerifyOnlyError> {
+        let private_key = self.private_key.as_ref().ok_or(VerifyOnlyError)?;
+        let sig = V::sign_with_dst(&private_key.inner, data, &[]);
+        Ok(Signature {
+            bytes: V::signature_to_bytes(&sig).to_vec(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// [`Self::sign`] for the [`SigningProvider`] methods
+    fn sign_message(&self, data: &[u8]) -> Result<Signature<V>, SigningError> {
+        self.sign(data).map_err(|_| SigningError::new())
+    }
+
+    /// Verifies a signature produced with an empty DST, see [`Self::sign_raw`].
+    ///
+    /// Unsafe for production: the signature is not bound to the PoP ciphersuite.
+    #[cfg(feature = "interop")]
+    pub fn verify_raw(
+        &self,
+        data: &[u8],
+        signature: &Signature<V>,
+        public_key: &PublicKey<V>,
+    ) -> bool {
+        let Ok(sig) = V::signature_from_bytes(&signature.bytes) else {
+            return false;
+        };
+        let Ok(pk) = V::public_key_from_bytes(&public_key.bytes) else {
+            return false;
+        };
+
+        V::verify_with_dst(&sig, data, &[], &pk) == BLST_ERROR::BLST_SUCCESS
+    }
+
+    /// Verifies a signature, rejecting keys outside `allowlist` without
+    /// running the pairing check, see [`verify_allowlisted`].
+    pub fn verify_allowlisted(
+        &self,
+        allowlist: &HashSet<PublicKey<V>>,
+        data: &[u8],
+        signature: &Signature<V>,
+        public_key: &PublicKey<V>,
+    ) -> bool {
+        verify_allowlisted(allowlist, data, signature, public_key)
     }
 }
 
@@ -220,7 +919,7 @@ where
 {
     #[cfg_attr(coverage_nightly, coverage(off))]
     async fn sign_vote(&self, vote: C::Vote) -> Result<SignedMessage<C, C::Vote>, SigningError> {
-        let signature = self.sign(&vote.to_sign_bytes());
+        let signature = self.sign_message(&vote.to_sign_bytes())?;
         Ok(SignedMessage::new(vote, signature))
     }
 
@@ -241,7 +940,7 @@ where
         &self,
         proposal: C::Proposal,
     ) -> Result<SignedMessage<C, C::Proposal>, SigningError> {
-        let signature = self.sign(&proposal.to_sign_bytes());
+        let signature = self.sign_message(&proposal.to_sign_bytes())?;
         Ok(SignedMessage::new(proposal, signature))
     }
 
@@ -262,7 +961,7 @@ where
         &self,
         proposal_part: C::ProposalPart,
     ) -> Result<SignedMessage<C, C::ProposalPart>, SigningError> {
-        let signature = self.sign(&proposal_part.to_sign_bytes());
+        let signature = self.sign_message(&proposal_part.to_sign_bytes())?;
         Ok(SignedMessage::new(proposal_part, signature))
     }
 
@@ -296,7 +995,15 @@ where
 }
 
 macro_rules! impl_bls_variant {
-    ($variant:ident, $module:ident, $pk_len:expr, $sig_len:expr, $dst:expr) => {
+    (
+        $variant:ident,
+        $module:ident,
+        $pk_len:expr,
+        $sig_len:expr,
+        $dst:expr,
+        $pop_dst:expr,
+        $prehashed_dst:expr
+    ) => {
         #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
         pub struct $variant;
 
@@ -308,6 +1015,8 @@ macro_rules! impl_bls_variant {
             const PK_LEN: usize = $pk_len;
             const SIG_LEN: usize = $sig_len;
             const DST: &'static [u8] = $dst;
+            const POP_DST: &'static [u8] = $pop_dst;
+            const PREHASHED_DST: &'static [u8] = $prehashed_dst;
 
             fn key_gen(ikm: &[u8]) -> Result<Self::SecretKey, BLST_ERROR> {
                 $module::SecretKey::key_gen(ikm, &[])
@@ -325,6 +1034,10 @@ macro_rules! impl_bls_variant {
                 $module::PublicKey::from_bytes(bytes)
             }
 
+            fn public_key_from_bytes_strict(bytes: &[u8]) -> Result<Self::PublicKey, BLST_ERROR> {
+                $module::PublicKey::key_validate(bytes)
+            }
+
             fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
                 public_key.to_bytes().to_vec()
             }
@@ -337,27 +1050,105 @@ macro_rules! impl_bls_variant {
                 $module::Signature::from_bytes(bytes)
             }
 
+            fn signature_from_bytes_strict(bytes: &[u8]) -> Result<Self::Signature, BLST_ERROR> {
+                $module::Signature::sig_validate(bytes, true)
+            }
+
             fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
                 signature.to_bytes().to_vec()
             }
 
-            fn sign(secret_key: &Self::SecretKey, msg: &[u8]) -> Self::Signature {
-                secret_key.sign(msg, Self::DST, &[])
+            fn sign_with_dst(
+                secret_key: &Self::SecretKey,
+                msg: &[u8],
+                dst: &[u8],
+            ) -> Self::Signature {
+                secret_key.sign(msg, dst, &[])
             }
 
-            fn verify(
+            fn verify_with_dst(
                 signature: &Self::Signature,
                 msg: &[u8],
+                dst: &[u8],
                 public_key: &Self::PublicKey,
             ) -> BLST_ERROR {
-                signature.verify(true, msg, Self::DST, &[], public_key, true)
+                signature.verify(true, msg, dst, &[], public_key, true)
+            }
+
+            fn verify_multiple_with_dst<R: RngCore + ?Sized>(
+                signatures: &[&Self::Signature],
+                msgs: &[&[u8]],
+                dst: &[u8],
+                public_keys: &[&Self::PublicKey],
+                rng: &mut R,
+            ) -> BLST_ERROR {
+                let rands: Vec<blst::blst_scalar> = signatures
+                    .iter()
+                    .map(|_| {
+                        let mut b = [0u8; 32];
+                        rng.fill_bytes(&mut b[..BATCH_RAND_BITS / 8]);
+                        // A zero scalar would drop the signature from the check
+                        b[0] |= 1;
+                        blst::blst_scalar { b }
+                    })
+                    .collect();
+
+                $module::Signature::verify_multiple_aggregate_signatures(
+                    msgs,
+                    dst,
+                    public_keys,
+                    true,
+                    signatures,
+                    true,
+                    &rands,
+                    BATCH_RAND_BITS,
+                )
+            }
+
+            fn aggregate_signatures(
+                signatures: &[&Self::Signature],
+            ) -> Result<Self::Signature, BLST_ERROR> {
+                $module::AggregateSignature::aggregate(signatures, true)
+                    .map(|aggregate| aggregate.to_signature())
+            }
+
+            fn aggregate_public_keys(
+                public_keys: &[&Self::PublicKey],
+            ) -> Result<Self::PublicKey, BLST_ERROR> {
+                $module::AggregatePublicKey::aggregate(public_keys, true)
+                    .map(|aggregate| aggregate.to_public_key())
+            }
+
+            fn fast_aggregate_verify_with_dst(
+                signature: &Self::Signature,
+                msg: &[u8],
+                dst: &[u8],
+                public_keys: &[&Self::PublicKey],
+            ) -> BLST_ERROR {
+                signature.fast_aggregate_verify(true, msg, dst, public_keys)
             }
         }
     };
 }
 
-impl_bls_variant!(MinSig, min_sig, 96, 48, DST_BLS_SIG_IN_G1_WITH_POP);
-impl_bls_variant!(MinPk, min_pk, 48, 96, DST_BLS_SIG_IN_G2_WITH_POP);
+impl_bls_variant!(
+    MinSig,
+    min_sig,
+    96,
+    48,
+    DST_BLS_SIG_IN_G1_WITH_POP,
+    DST_BLS_POP_IN_G1,
+    DST_BLS_PREHASHED_IN_G1
+);
+impl_bls_variant!(
+    MinPk,
+    min_pk,
+    48,
+    96,
+    DST_BLS_SIG_IN_G2_WITH_POP,
+    DST_BLS_POP_IN_G2,
+    DST_BLS_PREHASHED_IN_G2
+);
 
 pub type Bls12381MinSig = Bls12381<MinSig>;
 pub type Bls12381MinPk = Bls12381<MinPk>;
@@ -366,9 +1157,10 @@ pub type BlsProviderMinPk = BlsProvider<MinPk>;
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::hex;
-    use rand::rngs::OsRng;
-    use rand::RngCore;
+    use core::cell::Cell;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     use super::*;
 
@@ -409,6 +1201,23 @@ mod tests {
         "97558a05b879c8d445f387a5d5b653df"
     );
 
+    // Secret key behind `PUBKEY` in the ethereum/bls12-381-tests vectors.
+    const PRIVKEY: [u8; 32] =
+        hex!("328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216");
+
+    // Signature of `MESSAGE` by `PRIVKEY` with an empty DST. Generated with an
+    // independent hash-to-curve implementation that reproduces `SIGNATURE_VALID`
+    // with the PoP DST.
+    #[cfg(feature = "interop")]
+    const SIGNATURE_EMPTY_DST: [u8; 96] = hex!(
+        "b234c607bd87eac5e6cefec201c0822d"
+        "abef0e74664776a1e326a64fd3c8640f"
+        "d97a70c312ca598aa5e1115cb68e0e48"
+        "171cb6432a2ef6f1d62a6877b0198a4d"
+        "5e76ac0a0ffd1cd41eee4684fb153480"
+        "5244f2fa432191b3eb2e9e779b53c9e7"
+    );
+
     fn sign_and_verify_roundtrip<V: BlsVariant>() {
         let mut ikm = vec![0u8; V::SK_LEN];
         OsRng.fill_bytes(&mut ikm);
@@ -433,6 +1242,28 @@ mod tests {
         sign_and_verify_roundtrip::<MinPk>();
     }
 
+    #[test]
+    fn debug_is_truncated_and_display_is_full() {
+        let public_key = PublicKey::<MinPk>::from_bytes(&PUBKEY).unwrap();
+        let signature = Signature::<MinPk>::from_bytes(&SIGNATURE_VALID).unwrap();
+
+        assert_eq!(format!("{public_key:?}"), "Pk(0xb53d21a4..54051f6f)");
+        assert_eq!(format!("{public_key}"), hex::encode_prefixed(PUBKEY));
+
+        let debug = format!("{signature:?}");
+        assert!(debug.len() < 30);
+        assert!(debug.starts_with(&format!(
+            "Sig({}..",
+            hex::encode_prefixed(&SIGNATURE_VALID[..4])
+        )));
+        assert!(debug.ends_with(&format!("..{})", hex::encode(&SIGNATURE_VALID[92..]))));
+        assert_eq!(
+            format!("{signature}"),
+            hex::encode_prefixed(SIGNATURE_VALID)
+        );
+        assert_eq!(format!("{signature}").len(), 2 + 2 * 96);
+    }
+
     #[test]
     fn min_pk_ethereum_vector_verify_valid_case() {
         let public_key = PublicKey::<MinPk>::from_bytes(&PUBKEY).unwrap();
@@ -448,4 +1279,602 @@ mod tests {
 
         assert!(!signature.verify(&MESSAGE, &public_key));
     }
+
+    #[test]
+    fn min_pk_strict_decoding_accepts_canonical_encodings() {
+        assert!(PublicKey::<MinPk>::from_bytes_strict(&PUBKEY).is_ok());
+        assert!(Signature::<MinPk>::from_bytes_strict(&SIGNATURE_VALID).is_ok());
+    }
+
+    #[test]
+    fn min_pk_strict_decoding_rejects_non_canonical_encodings() {
+        // Compression flag cleared
+        let mut pubkey = PUBKEY;
+        pubkey[0] &= !FLAG_COMPRESSED;
+        assert!(PublicKey::<MinPk>::from_bytes_strict(&pubkey).is_err());
+
+        // Infinity flag set on a non-zero point
+        let mut pubkey = PUBKEY;
+        pubkey[0] |= FLAG_INFINITY;
+        assert!(PublicKey::<MinPk>::from_bytes_strict(&pubkey).is_err());
+
+        // Infinity with the sign flag set
+        let mut infinity = [0u8; 48];
+        infinity[0] = FLAG_COMPRESSED | FLAG_INFINITY | FLAG_SIGN;
+        assert!(PublicKey::<MinPk>::from_bytes_strict(&infinity).is_err());
+
+        // x-coordinate equal to the field modulus
+        let mut pubkey = FIELD_MODULUS;
+        pubkey[0] |= FLAG_COMPRESSED;
+        assert!(PublicKey::<MinPk>::from_bytes_strict(&pubkey).is_err());
+
+        // Second limb of a G2 x-coordinate equal to the field modulus
+        let mut signature = SIGNATURE_VALID;
+        signature[48..].copy_from_slice(&FIELD_MODULUS);
+        assert!(Signature::<MinPk>::from_bytes_strict(&signature).is_err());
+
+        let mut signature = SIGNATURE_VALID;
+        signature[0] &= !FLAG_COMPRESSED;
+        assert!(Signature::<MinPk>::from_bytes_strict(&signature).is_err());
+    }
+
+    fn random_private_key<V: BlsVariant>() -> PrivateKey<V> {
+        let mut ikm = vec![0u8; V::SK_LEN];
+        OsRng.fill_bytes(&mut ikm);
+        let blst_key = V::key_gen(&ikm).expect("key_gen should succeed with 32 bytes");
+        PrivateKey::from_bytes(&V::secret_key_to_bytes(&blst_key)).unwrap()
+    }
+
+    fn verify_possessions_flags_invalid_entry<V: BlsVariant>() {
+        let keys: Vec<PrivateKey<V>> = (0..5).map(|_| random_private_key()).collect();
+        let mut entries: Vec<(PublicKey<V>, Signature<V>)> = keys
+            .iter()
+            .map(|key| (key.public_key(), key.prove_possession()))
+            .collect();
+
+        assert_eq!(verify_possessions(&entries), vec![true; 5]);
+        assert!(entries[0].0.verify_possession(&entries[0].1));
+
+        // A regular signature over the public key is not a valid PoP
+        entries[3].1 = keys[3].sign(&entries[3].0.to_bytes());
+        assert_eq!(
+            verify_possessions(&entries),
+            vec![true, true, true, false, true]
+        );
+    }
+
+    fn seeded_batch_verification_is_deterministic<V: BlsVariant>() {
+        let keys: Vec<PrivateKey<V>> = (0..4).map(|_| random_private_key()).collect();
+        let mut entries: Vec<(PublicKey<V>, Signature<V>)> = keys
+            .iter()
+            .map(|key| (key.public_key(), key.prove_possession()))
+            .collect();
+        entries[1].1 = keys[1].sign(&entries[1].0.to_bytes());
+
+        let individual: Vec<bool> = entries
+            .iter()
+            .map(|(public_key, proof)| public_key.verify_possession(proof))
+            .collect();
+        assert_eq!(individual, vec![true, false, true, true]);
+
+        let run = |entries: &[(PublicKey<V>, Signature<V>)]| {
+            verify_possessions_with_rng(entries, &mut StdRng::seed_from_u64(7))
+        };
+        assert_eq!(run(&entries), individual);
+        assert_eq!(run(&entries), run(&entries));
+
+        entries.remove(1);
+        assert_eq!(run(&entries), vec![true; 3]);
+    }
+
+    #[test]
+    fn min_sig_seeded_batch_verification_is_deterministic() {
+        seeded_batch_verification_is_deterministic::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_seeded_batch_verification_is_deterministic() {
+        seeded_batch_verification_is_deterministic::<MinPk>();
+    }
+
+    fn diagnose_aggregate_finds_corrupted_contribution<V: BlsVariant>() {
+        let keys: Vec<PrivateKey<V>> = (0..4).map(|_| random_private_key()).collect();
+        let public_keys: Vec<PublicKey<V>> = keys.iter().map(PrivateKey::public_key).collect();
+        let mut signatures: Vec<Signature<V>> = keys.iter().map(|key| key.sign(&MESSAGE)).collect();
+
+        let aggregate = Signature::aggregate(&signatures).unwrap();
+        assert!(aggregate.verify_aggregate(&MESSAGE, &public_keys));
+        assert!(diagnose_aggregate(&public_keys, &MESSAGE, &aggregate, &signatures).is_empty());
+
+        // Signer 2 contributed a signature over the wrong message
+        signatures[2] = keys[2].sign(b"wrong message");
+        let aggregate = Signature::aggregate(&signatures).unwrap();
+        assert!(!aggregate.verify_aggregate(&MESSAGE, &public_keys));
+        assert_eq!(
+            diagnose_aggregate(&public_keys, &MESSAGE, &aggregate, &signatures),
+            vec![2]
+        );
+    }
+
+    fn aggregate_verifies_against_aggregate_public_key<V: BlsVariant>() {
+        let keys: Vec<PrivateKey<V>> = (0..5).map(|_| random_private_key()).collect();
+        let public_keys: Vec<PublicKey<V>> = keys.iter().map(PrivateKey::public_key).collect();
+        let signatures: Vec<Signature<V>> = keys.iter().map(|key| key.sign(&MESSAGE)).collect();
+
+        let aggregate = Signature::aggregate(&signatures).unwrap();
+        assert_eq!(aggregate.to_bytes().len(), V::SIG_LEN);
+        let aggregate_key = PublicKey::aggregate(&public_keys).unwrap();
+        assert_eq!(aggregate_key.to_bytes().len(), V::PK_LEN);
+
+        assert!(aggregate.verify(&MESSAGE, &aggregate_key));
+        assert!(aggregate.verify_aggregate(&MESSAGE, &public_keys));
+        assert!(!aggregate.verify(b"wrong message", &aggregate_key));
+
+        // Missing a signer's key
+        let partial_key = PublicKey::aggregate(&public_keys[1..]).unwrap();
+        assert!(!aggregate.verify(&MESSAGE, &partial_key));
+
+        // A single signature aggregates to itself
+        assert_eq!(
+            Signature::aggregate(&signatures[..1]).unwrap(),
+            signatures[0]
+        );
+
+        assert_eq!(Signature::<V>::aggregate(&[]), Err(AggregateError::Empty));
+        assert_eq!(PublicKey::<V>::aggregate(&[]), Err(AggregateError::Empty));
+    }
+
+    #[test]
+    fn min_sig_aggregate_verifies_against_aggregate_public_key() {
+        aggregate_verifies_against_aggregate_public_key::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_aggregate_verifies_against_aggregate_public_key() {
+        aggregate_verifies_against_aggregate_public_key::<MinPk>();
+    }
+
+    fn aggregate_rejects_malformed_signatures<V: BlsVariant>() {
+        let key = random_private_key::<V>();
+        let valid = key.sign(&MESSAGE);
+        let malformed = |bytes: Vec<u8>| Signature::<V> {
+            bytes,
+            _marker: PhantomData,
+        };
+
+        let truncated = malformed(valid.to_bytes()[1..].to_vec());
+        assert_eq!(
+            Signature::aggregate(&[valid.clone(), truncated]),
+            Err(AggregateError::Decoding(BlsDecodingError(
+                BLST_ERROR::BLST_BAD_ENCODING
+            )))
+        );
+
+        let padded = malformed([valid.to_bytes(), vec![0]].concat());
+        assert!(Signature::aggregate(&[padded, valid.clone()]).is_err());
+
+        // Right length, but not a point on the curve
+        let mut garbage = vec![0xff; V::SIG_LEN];
+        garbage[0] = 0x9f;
+        assert!(Signature::aggregate(&[valid.clone(), malformed(garbage)]).is_err());
+
+        let truncated_key = PublicKey::<V> {
+            bytes: key.public_key().to_bytes()[1..].to_vec(),
+            _marker: PhantomData,
+        };
+        assert_eq!(
+            PublicKey::aggregate(&[key.public_key(), truncated_key]),
+            Err(AggregateError::Decoding(BlsDecodingError(
+                BLST_ERROR::BLST_BAD_ENCODING
+            )))
+        );
+    }
+
+    #[test]
+    fn min_sig_aggregate_rejects_malformed_signatures() {
+        aggregate_rejects_malformed_signatures::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_aggregate_rejects_malformed_signatures() {
+        aggregate_rejects_malformed_signatures::<MinPk>();
+    }
+
+    #[test]
+    fn min_sig_diagnose_aggregate_finds_corrupted_contribution() {
+        diagnose_aggregate_finds_corrupted_contribution::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_diagnose_aggregate_finds_corrupted_contribution() {
+        diagnose_aggregate_finds_corrupted_contribution::<MinPk>();
+    }
+
+    #[test]
+    fn min_sig_verify_possessions_flags_invalid_entry() {
+        verify_possessions_flags_invalid_entry::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_verify_possessions_flags_invalid_entry() {
+        verify_possessions_flags_invalid_entry::<MinPk>();
+    }
+
+    fn key_rotation_requires_possession_of_new_key<V: BlsVariant>() {
+        let old = random_private_key::<V>();
+        let new = random_private_key::<V>();
+        let mut rotation = KeyRotation {
+            old: old.public_key(),
+            new: new.public_key(),
+            new_pop: new.prove_possession(),
+        };
+        assert!(rotation.verify());
+
+        // A PoP made with the old key does not prove control of the new one
+        rotation.new_pop = old.prove_possession();
+        assert!(!rotation.verify());
+    }
+
+    #[test]
+    fn min_sig_key_rotation_requires_possession_of_new_key() {
+        key_rotation_requires_possession_of_new_key::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_key_rotation_requires_possession_of_new_key() {
+        key_rotation_requires_possession_of_new_key::<MinPk>();
+    }
+
+    fn prehashed_signatures_are_separate_from_regular_ones<V: BlsVariant>() {
+        let private_key = random_private_key::<V>();
+        let public_key = private_key.public_key();
+        let digest = *keccak256(b"hello bls");
+
+        let prehashed = private_key.sign_prehashed(&digest);
+        assert!(public_key.verify_prehashed(&digest, &prehashed));
+        assert!(!public_key.verify_prehashed(&[0; 32], &prehashed));
+        assert!(!random_private_key::<V>()
+            .public_key()
+            .verify_prehashed(&digest, &prehashed));
+
+        // Neither kind of signature over the digest verifies as the other
+        let regular = private_key.sign(&digest);
+        assert!(!public_key.verify(&digest, &prehashed));
+        assert!(!public_key.verify_prehashed(&digest, &regular));
+    }
+
+    #[test]
+    fn min_sig_prehashed_signatures_are_separate_from_regular_ones() {
+        prehashed_signatures_are_separate_from_regular_ones::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_prehashed_signatures_are_separate_from_regular_ones() {
+        prehashed_signatures_are_separate_from_regular_ones::<MinPk>();
+    }
+
+    fn collect_quorum_checks_power_and_signatures<V: BlsVariant>() {
+        let keys: Vec<PrivateKey<V>> = (0..4).map(|_| random_private_key::<V>()).collect();
+        let validators: Vec<(PublicKey<V>, VotingPower)> =
+            keys.iter().map(|key| (key.public_key(), 1)).collect();
+        let msg = b"precommit";
+        let vote = |index: usize| (index, keys[index].sign(msg));
+
+        let (aggregate, signers) =
+            collect_quorum(&[vote(0), vote(2), vote(3)], &validators, msg).unwrap();
+        assert_eq!(signers.signers().collect::<Vec<_>>(), vec![0, 2, 3]);
+        let signer_keys = [0, 2, 3].map(|index| validators[index].0.clone());
+        assert!(aggregate.verify_aggregate(msg, &signer_keys));
+
+        assert_eq!(
+            collect_quorum(&[vote(0), vote(1)], &validators, msg).unwrap_err(),
+            QuorumError::InsufficientPower {
+                signed: 2,
+                total: 4
+            }
+        );
+
+        let forged = (3, keys[3].sign(b"prevote"));
+        assert_eq!(
+            collect_quorum(&[vote(0), vote(1), forged], &validators, msg).unwrap_err(),
+            QuorumError::InvalidSignature { index: 3 }
+        );
+
+        assert_eq!(
+            collect_quorum(
+                &[vote(0), vote(1), (4, keys[0].sign(msg))],
+                &validators,
+                msg
+            )
+            .unwrap_err(),
+            QuorumError::UnknownSigner { index: 4 }
+        );
+        assert_eq!(
+            collect_quorum(&[vote(0), vote(1), vote(0)], &validators, msg).unwrap_err(),
+            QuorumError::DuplicateSigner { index: 0 }
+        );
+
+        // Three of four is exactly 3/4, which does not exceed a 3/4 threshold
+        let three_quarters = QuorumThreshold::new(3, 4).unwrap();
+        assert_eq!(
+            collect_quorum_with_threshold(
+                &[vote(0), vote(2), vote(3)],
+                &validators,
+                msg,
+                three_quarters
+            )
+            .unwrap_err(),
+            QuorumError::InsufficientPower {
+                signed: 3,
+                total: 4
+            }
+        );
+        assert!(collect_quorum_with_threshold(
+            &[vote(0), vote(1), vote(2), vote(3)],
+            &validators,
+            msg,
+            three_quarters
+        )
+        .is_ok());
+
+        // Powers whose sum does not fit in a VotingPower are rejected instead of wrapping
+        let mut heavy = validators.clone();
+        heavy[0].1 = VotingPower::MAX;
+        assert_eq!(
+            collect_quorum(&[vote(0), vote(1), vote(2)], &heavy, msg).unwrap_err(),
+            QuorumError::PowerOverflow
+        );
+        assert_eq!(
+            collect_quorum(&[vote(1), vote(2), vote(3)], &heavy, msg).unwrap_err(),
+            QuorumError::PowerOverflow
+        );
+    }
+
+    #[test]
+    fn min_sig_collect_quorum_checks_power_and_signatures() {
+        collect_quorum_checks_power_and_signatures::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_collect_quorum_checks_power_and_signatures() {
+        collect_quorum_checks_power_and_signatures::<MinPk>();
+    }
+
+    #[test]
+    fn load_bls_key_from_env_var() {
+        std::env::set_var(
+            "EMERALD_TEST_BLS_KEY",
+            format!("0x{}", hex::encode(PRIVKEY)),
+        );
+
+        let key = load_bls_key_from_env::<MinPk>("EMERALD_TEST_BLS_KEY").unwrap();
+        assert_eq!(key.public_key().to_bytes(), PUBKEY);
+
+        std::env::set_var("EMERALD_TEST_BLS_KEY", "abcd");
+        assert!(matches!(
+            load_bls_key_from_env::<MinPk>("EMERALD_TEST_BLS_KEY"),
+            Err(KeyLoadError::InvalidLength { len: 2, .. })
+        ));
+
+        std::env::remove_var("EMERALD_TEST_BLS_KEY");
+        assert!(matches!(
+            load_bls_key_from_env::<MinPk>("EMERALD_TEST_BLS_KEY"),
+            Err(KeyLoadError::NotSet { .. })
+        ));
+    }
+
+    #[test]
+    fn load_bls_key_from_env_file() {
+        let path = std::env::temp_dir().join(format!("emerald-bls-key-{}", std::process::id()));
+        std::fs::write(&path, format!("{}\n", hex::encode(PRIVKEY))).unwrap();
+        std::env::set_var("EMERALD_TEST_BLS_KEY_INDIRECT_FILE", &path);
+
+        let key = load_bls_key_from_env::<MinPk>("EMERALD_TEST_BLS_KEY_INDIRECT").unwrap();
+        assert_eq!(key.public_key().to_bytes(), PUBKEY);
+
+        std::env::set_var("EMERALD_TEST_BLS_KEY_INDIRECT", hex::encode(PRIVKEY));
+        assert!(matches!(
+            load_bls_key_from_env::<MinPk>("EMERALD_TEST_BLS_KEY_INDIRECT"),
+            Err(KeyLoadError::Ambiguous { .. })
+        ));
+
+        std::env::remove_var("EMERALD_TEST_BLS_KEY_INDIRECT");
+        std::env::remove_var("EMERALD_TEST_BLS_KEY_INDIRECT_FILE");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn min_pk_provider_signs_with_pop_dst() {
+        let provider = BlsProviderMinPk::new(PrivateKey::from_bytes(&PRIVKEY).unwrap());
+
+        assert_eq!(
+            provider.private_key().unwrap().public_key().to_bytes(),
+            PUBKEY
+        );
+        assert_eq!(provider.sign(&MESSAGE).unwrap().to_bytes(), SIGNATURE_VALID);
+        assert!(!provider.is_verify_only());
+    }
+
+    fn verify_only_provider_verifies_but_never_signs<V: BlsVariant>() {
+        let key = random_private_key::<V>();
+        let provider = BlsProvider::<V>::verify_only();
+        assert!(provider.is_verify_only());
+        assert!(provider.private_key().is_none());
+
+        assert_eq!(provider.sign(&MESSAGE), Err(VerifyOnlyError));
+        #[cfg(feature = "interop")]
+        assert_eq!(provider.sign_raw(&MESSAGE), Err(VerifyOnlyError));
+        assert!(provider.sign_message(&MESSAGE).is_err());
+
+        let signature = key.sign(&MESSAGE);
+        let allowlist = HashSet::from([key.public_key()]);
+        assert!(provider.verify_allowlisted(&allowlist, &MESSAGE, &signature, &key.public_key()));
+        assert!(!provider.verify_allowlisted(
+            &allowlist,
+            b"wrong message",
+            &signature,
+            &key.public_key()
+        ));
+    }
+
+    #[test]
+    fn min_sig_verify_only_provider_verifies_but_never_signs() {
+        verify_only_provider_verifies_but_never_signs::<MinSig>();
+    }
+
+    #[test]
+    fn min_pk_verify_only_provider_verifies_but_never_signs() {
+        verify_only_provider_verifies_but_never_signs::<MinPk>();
+    }
+
+    #[cfg(feature = "interop")]
+    #[test]
+    fn min_pk_provider_sign_raw_reproduces_empty_dst_vector() {
+        let provider = BlsProviderMinPk::new(PrivateKey::from_bytes(&PRIVKEY).unwrap());
+        let public_key = provider.private_key().unwrap().public_key();
+
+        let raw = provider.sign_raw(&MESSAGE).unwrap();
+        assert_eq!(raw.to_bytes(), SIGNATURE_EMPTY_DST);
+        assert!(provider.verify_raw(&MESSAGE, &raw, &public_key));
+
+        // Raw and PoP signatures are not interchangeable
+        assert!(!raw.verify(&MESSAGE, &public_key));
+        let pop = provider.sign(&MESSAGE).unwrap();
+        assert!(!provider.verify_raw(&MESSAGE, &pop, &public_key));
+    }
+
+    thread_local! {
+        static PAIRING_CHECKS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// [`MinPk`] with a per-thread count of single-signature verifications.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct CountingMinPk;
+
+    impl BlsVariant for CountingMinPk {
+        type SecretKey = <MinPk as BlsVariant>::SecretKey;
+        type PublicKey = <MinPk as BlsVariant>::PublicKey;
+        type Signature = <MinPk as BlsVariant>::Signature;
+
+        const PK_LEN: usize = MinPk::PK_LEN;
+        const SIG_LEN: usize = MinPk::SIG_LEN;
+        const DST: &'static [u8] = MinPk::DST;
+        const POP_DST: &'static [u8] = MinPk::POP_DST;
+        const PREHASHED_DST: &'static [u8] = MinPk::PREHASHED_DST;
+
+        fn key_gen(ikm: &[u8]) -> Result<Self::SecretKey, BLST_ERROR> {
+            MinPk::key_gen(ikm)
+        }
+
+        fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, BLST_ERROR> {
+            MinPk::secret_key_from_bytes(bytes)
+        }
+
+        fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8> {
+            MinPk::secret_key_to_bytes(secret_key)
+        }
+
+        fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, BLST_ERROR> {
+            MinPk::public_key_from_bytes(bytes)
+        }
+
+        fn public_key_from_bytes_strict(bytes: &[u8]) -> Result<Self::PublicKey, BLST_ERROR> {
+            MinPk::public_key_from_bytes_strict(bytes)
+        }
+
+        fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+            MinPk::public_key_to_bytes(public_key)
+        }
+
+        fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey {
+            MinPk::public_key_from_secret_key(secret_key)
+        }
+
+        fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, BLST_ERROR> {
+            MinPk::signature_from_bytes(bytes)
+        }
+
+        fn signature_from_bytes_strict(bytes: &[u8]) -> Result<Self::Signature, BLST_ERROR> {
+            MinPk::signature_from_bytes_strict(bytes)
+        }
+
+        fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+            MinPk::signature_to_bytes(signature)
+        }
+
+        fn sign_with_dst(secret_key: &Self::SecretKey, msg: &[u8], dst: &[u8]) -> Self::Signature {
+            MinPk::sign_with_dst(secret_key, msg, dst)
+        }
+
+        fn verify_with_dst(
+            signature: &Self::Signature,
+            msg: &[u8],
+            dst: &[u8],
+            public_key: &Self::PublicKey,
+        ) -> BLST_ERROR {
+            PAIRING_CHECKS.with(|checks| checks.set(checks.get() + 1));
+            MinPk::verify_with_dst(signature, msg, dst, public_key)
+        }
+
+        fn verify_multiple_with_dst<R: RngCore + ?Sized>(
+            signatures: &[&Self::Signature],
+            msgs: &[&[u8]],
+            dst: &[u8],
+            public_keys: &[&Self::PublicKey],
+            rng: &mut R,
+        ) -> BLST_ERROR {
+            MinPk::verify_multiple_with_dst(signatures, msgs, dst, public_keys, rng)
+        }
+
+        fn aggregate_signatures(
+            signatures: &[&Self::Signature],
+        ) -> Result<Self::Signature, BLST_ERROR> {
+            MinPk::aggregate_signatures(signatures)
+        }
+
+        fn aggregate_public_keys(
+            public_keys: &[&Self::PublicKey],
+        ) -> Result<Self::PublicKey, BLST_ERROR> {
+            MinPk::aggregate_public_keys(public_keys)
+        }
+
+        fn fast_aggregate_verify_with_dst(
+            signature: &Self::Signature,
+            msg: &[u8],
+            dst: &[u8],
+            public_keys: &[&Self::PublicKey],
+        ) -> BLST_ERROR {
+            MinPk::fast_aggregate_verify_with_dst(signature, msg, dst, public_keys)
+        }
+    }
+
+    #[test]
+    fn verify_allowlisted_skips_pairing_for_unknown_keys() {
+        let known = random_private_key::<CountingMinPk>();
+        let unknown = random_private_key::<CountingMinPk>();
+        let allowlist = HashSet::from([known.public_key()]);
+        let provider = BlsProvider::new(known.clone());
+
+        let signature = unknown.sign(&MESSAGE);
+        assert!(signature.verify(&MESSAGE, &unknown.public_key()));
+        PAIRING_CHECKS.with(|checks| checks.set(0));
+
+        assert!(!provider.verify_allowlisted(
+            &allowlist,
+            &MESSAGE,
+            &signature,
+            &unknown.public_key()
+        ));
+        assert_eq!(PAIRING_CHECKS.with(Cell::get), 0);
+
+        let signature = known.sign(&MESSAGE);
+        assert!(provider.verify_allowlisted(&allowlist, &MESSAGE, &signature, &known.public_key()));
+        assert!(!verify_allowlisted(
+            &allowlist,
+            b"wrong message",
+            &signature,
+            &known.public_key()
+        ));
+        assert_eq!(PAIRING_CHECKS.with(Cell::get), 2);
+    }
 }
@@ -2,15 +2,18 @@ use alloy_primitives::keccak256;
 use async_trait::async_trait;
 use bytes::Bytes;
 use k256::ecdsa::VerifyingKey;
-use malachitebft_core_types::{Context, SignedExtension, SignedMessage};
+use malachitebft_core_types::{
+    CommitCertificate, Context, NilOrVal, SignedExtension, SignedMessage, VotingPower,
+};
 use malachitebft_signing::{Error as SigningError, SigningProvider, VerificationResult};
 use malachitebft_signing_ecdsa::K256Config;
 pub use malachitebft_signing_ecdsa::{
     PrivateKey as EcdsaPrivateKey, PublicKey as EcdsaPublicKey, Signature as EcdsaSignature, K256,
 };
+use thiserror::Error;
 
 use super::Hashable;
-use crate::{Proposal, ProposalPart, Vote};
+use crate::{Address, EmeraldContext, Proposal, ProposalPart, QuorumThreshold, ValidatorSet, Vote};
 
 pub type PrivateKey = EcdsaPrivateKey<K256Config>;
 pub type PublicKey = EcdsaPublicKey<K256Config>;
@@ -58,6 +61,130 @@ impl K256Provider {
     }
 }
 
+/// Errors returned by [`verify_vote_by_address`] before any signature check.
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum VerifyError {
+    #[error("{0} is not in the validator set")]
+    UnknownValidator(Address),
+
+    #[error("vote is from {vote} but was presented as signed by {signer}")]
+    SignerMismatch { signer: Address, vote: Address },
+}
+
+/// Verify `signature` over the sign-bytes of `vote` with the public key that `set`
+/// holds for `signer`.
+///
+/// Returns `Ok(false)` for a bad signature, and an error when `signer` is not a
+/// validator of `set` or is not the author named in the vote.
+pub fn verify_vote_by_address(
+    set: &ValidatorSet,
+    vote: &Vote,
+    signature: &Signature,
+    signer: Address,
+) -> Result<bool, VerifyError> {
+    if vote.validator_address != signer {
+        return Err(VerifyError::SignerMismatch {
+            signer,
+            vote: vote.validator_address,
+        });
+    }
+
+    let validator = set
+        .get_by_address(&signer)
+        .ok_or(VerifyError::UnknownValidator(signer))?;
+
+    Ok(validator
+        .public_key
+        .verify(&vote.to_sign_bytes(), signature)
+        .is_ok())
+}
+
+/// Errors returned by [`verify_commit_certificate`]
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum CertificateError {
+    #[error("{0} signed the certificate but is not in the validator set")]
+    ValidatorSetMismatch(Address),
+
+    #[error("{0} signed the certificate more than once")]
+    DuplicateSigner(Address),
+
+    #[error("invalid precommit signature from {0}")]
+    InvalidSignature(Address),
+
+    #[error("signers hold {signed} of {total} voting power, which is not a quorum")]
+    NoQuorum {
+        signed: VotingPower,
+        total: VotingPower,
+    },
+
+    #[error("total voting power of the validator set overflows")]
+    PowerOverflow,
+}
+
+/// Verify a commit certificate against `set`.
+///
+/// `set` need not be the set the certificate was produced under, e.g. a checkpoint
+/// set during fast sync. Every signer must be a member of `set` with a valid
+/// precommit signature, and together the signers must hold more than 2/3 of the
+/// voting power of `set`.
+pub fn verify_commit_certificate(
+    set: &ValidatorSet,
+    certificate: &CommitCertificate<EmeraldContext>,
+) -> Result<(), CertificateError> {
+    verify_commit_certificate_with_threshold(set, certificate, QuorumThreshold::default())
+}
+
+/// [`verify_commit_certificate`] for a deployment that requires the signers to
+/// exceed `threshold` of the voting power of `set` rather than 2/3.
+pub fn verify_commit_certificate_with_threshold(
+    set: &ValidatorSet,
+    certificate: &CommitCertificate<EmeraldContext>,
+    threshold: QuorumThreshold,
+) -> Result<(), CertificateError> {
+    let mut signers = Vec::with_capacity(certificate.commit_signatures.len());
+    let mut signed: VotingPower = 0;
+
+    for commit in &certificate.commit_signatures {
+        let validator = set
+            .get_by_address(&commit.address)
+            .ok_or(CertificateError::ValidatorSetMismatch(commit.address))?;
+
+        if signers.contains(&commit.address) {
+            return Err(CertificateError::DuplicateSigner(commit.address));
+        }
+        signers.push(commit.address);
+
+        let vote = Vote::new_precommit(
+            certificate.height,
+            certificate.round,
+            NilOrVal::Val(certificate.value_id),
+            commit.address,
+        );
+        if validator
+            .public_key
+            .verify(&vote.to_sign_bytes(), &commit.signature)
+            .is_err()
+        {
+            return Err(CertificateError::InvalidSignature(commit.address));
+        }
+
+        signed = signed
+            .checked_add(validator.voting_power)
+            .ok_or(CertificateError::PowerOverflow)?;
+    }
+
+    let total = set
+        .validators
+        .iter()
+        .try_fold(0, |total: VotingPower, v| total.checked_add(v.voting_power))
+        .ok_or(CertificateError::PowerOverflow)?;
+    if !threshold.is_met(signed, total) {
+        return Err(CertificateError::NoQuorum { signed, total });
+    }
+
+    Ok(())
+}
+
 #[async_trait]
 impl<C> SigningProvider<C> for K256Provider
 where
@@ -149,3 +276,130 @@ where
         unimplemented!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use malachitebft_core_types::{CommitSignature, Round};
+
+    use super::*;
+    use crate::utils::validators::make_validators;
+    use crate::{Height, Validator, ValueId};
+
+    fn precommit(address: Address, value: u64) -> Vote {
+        Vote::new_precommit(
+            Height::new(1),
+            Round::new(0),
+            NilOrVal::Val(ValueId::new(value)),
+            address,
+        )
+    }
+
+    #[test]
+    fn test_verify_vote_by_address() {
+        let [(alice, alice_key), (bob, _), (carol, carol_key)] = make_validators([1, 1, 1]);
+        let set = ValidatorSet::new([alice.clone(), bob.clone()]);
+
+        let vote = precommit(alice.address, 7);
+        let signature = alice_key.sign(&vote.to_sign_bytes());
+        assert_eq!(
+            verify_vote_by_address(&set, &vote, &signature, alice.address),
+            Ok(true)
+        );
+
+        // The signature does not cover a vote for another value
+        let tampered = precommit(alice.address, 8);
+        assert_eq!(
+            verify_vote_by_address(&set, &tampered, &signature, alice.address),
+            Ok(false)
+        );
+
+        // Nor a vote signed by someone else on behalf of a validator
+        let forged = carol_key.sign(&precommit(bob.address, 7).to_sign_bytes());
+        assert_eq!(
+            verify_vote_by_address(&set, &precommit(bob.address, 7), &forged, bob.address),
+            Ok(false)
+        );
+
+        let outsider = precommit(carol.address, 7);
+        let signature = carol_key.sign(&outsider.to_sign_bytes());
+        assert_eq!(
+            verify_vote_by_address(&set, &outsider, &signature, carol.address),
+            Err(VerifyError::UnknownValidator(carol.address))
+        );
+
+        assert_eq!(
+            verify_vote_by_address(&set, &vote, &signature, carol.address),
+            Err(VerifyError::SignerMismatch {
+                signer: carol.address,
+                vote: alice.address
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_commit_certificate_against_checkpoint_set() {
+        let [(alice, alice_key), (bob, bob_key), (carol, carol_key), (dave, _)] =
+            make_validators([1, 1, 1, 1]);
+
+        let certificate = CommitCertificate {
+            height: Height::new(1),
+            round: Round::new(0),
+            value_id: ValueId::new(7),
+            commit_signatures: [(&alice, &alice_key), (&bob, &bob_key), (&carol, &carol_key)]
+                .into_iter()
+                .map(|(validator, key)| {
+                    let vote = precommit(validator.address, 7);
+                    CommitSignature::new(validator.address, key.sign(&vote.to_sign_bytes()))
+                })
+                .collect(),
+        };
+
+        let set = ValidatorSet::new([alice.clone(), bob.clone(), carol.clone(), dave.clone()]);
+        assert_eq!(verify_commit_certificate(&set, &certificate), Ok(()));
+
+        // A smaller checkpoint set that lacks one of the signers
+        let smaller = ValidatorSet::new([alice.clone(), bob.clone(), dave.clone()]);
+        assert_eq!(
+            verify_commit_certificate(&smaller, &certificate),
+            Err(CertificateError::ValidatorSetMismatch(carol.address))
+        );
+
+        // Signers that are all in the set but hold too little of its power
+        let mut partial = certificate.clone();
+        partial.commit_signatures.truncate(2);
+        assert_eq!(
+            verify_commit_certificate(&set, &partial),
+            Err(CertificateError::NoQuorum {
+                signed: 2,
+                total: 4
+            })
+        );
+
+        let mut duplicated = certificate.clone();
+        duplicated
+            .commit_signatures
+            .push(certificate.commit_signatures[0].clone());
+        assert_eq!(
+            verify_commit_certificate(&set, &duplicated),
+            Err(CertificateError::DuplicateSigner(alice.address))
+        );
+
+        let mut tampered = certificate.clone();
+        tampered.value_id = ValueId::new(8);
+        assert_eq!(
+            verify_commit_certificate(&set, &tampered),
+            Err(CertificateError::InvalidSignature(alice.address))
+        );
+
+        // Powers whose sum does not fit in a VotingPower are rejected instead of wrapping
+        let heavy = |validator: &Validator| Validator {
+            voting_power: VotingPower::MAX,
+            ..validator.clone()
+        };
+        let overflowing = ValidatorSet::new([heavy(&alice), bob.clone(), carol, heavy(&dave)]);
+        assert_eq!(
+            verify_commit_certificate(&overflowing, &certificate),
+            Err(CertificateError::PowerOverflow)
+        );
+    }
+}
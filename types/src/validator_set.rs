@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
+use alloy_primitives::keccak256;
 use malachitebft_core_types::VotingPower;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::signing::secp256k1::PublicKey;
-use crate::{Address, EmeraldContext};
+use crate::{Address, EmeraldContext, TaggedSignerBitmap, B256};
 
 /// A validator is a public key and voting power
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -52,7 +54,11 @@ impl malachitebft_core_types::Validator<EmeraldContext> for Validator {
 }
 
 /// A validator set contains a list of validators sorted by address.
+///
+/// Serialized as a [`SerializedValidatorSet`], so that sets written by older
+/// tooling still load as [`Validator`] gains fields.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SerializedValidatorSet", into = "SerializedValidatorSet")]
 pub struct ValidatorSet {
     pub validators: Arc<Vec<Validator>>,
 }
@@ -74,6 +80,77 @@ impl ValidatorSet {
         self.validators.iter().map(|v| v.voting_power).sum()
     }
 
+    /// The least voting power that meets `threshold` in this set
+    pub fn quorum_power(&self, threshold: QuorumThreshold) -> VotingPower {
+        threshold.quorum_power(self.total_voting_power())
+    }
+
+    /// Voting power of the validators whose bits are set in `bitfield`, in the
+    /// layout of [`SignerBitmap`](crate::SignerBitmap), indexed by position in the set
+    pub fn signer_power(&self, bitfield: &[u8]) -> Result<VotingPower, BitfieldError> {
+        let expected = self.validators.len().div_ceil(8);
+        if bitfield.len() != expected {
+            return Err(BitfieldError::Length {
+                expected,
+                got: bitfield.len(),
+            });
+        }
+
+        let mut power = 0;
+        for (byte_index, byte) in bitfield.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (1 << bit) == 0 {
+                    continue;
+                }
+                let index = byte_index * 8 + bit;
+                let validator = self
+                    .validators
+                    .get(index)
+                    .ok_or(BitfieldError::OutOfRange { index })?;
+                power = validator
+                    .voting_power
+                    .checked_add(power)
+                    .ok_or(BitfieldError::PowerOverflow)?;
+            }
+        }
+        Ok(power)
+    }
+
+    /// Commitment to the validators in set order, as `keccak256` of each address
+    /// followed by its big-endian voting power. Two sets share a checksum only when
+    /// every index of a [`SignerBitmap`](crate::SignerBitmap) selects the same validator.
+    pub fn checksum(&self) -> B256 {
+        let mut preimage = Vec::new();
+        for validator in self.validators.iter() {
+            preimage.extend_from_slice(&validator.address.into_inner());
+            preimage.extend_from_slice(&validator.voting_power.to_be_bytes());
+        }
+        keccak256(preimage)
+    }
+
+    /// Check that `signers` was built against this set and that the validators
+    /// it selects exceed `threshold` of the voting power. Returns their power.
+    pub fn verify_certificate(
+        &self,
+        signers: &TaggedSignerBitmap,
+        threshold: QuorumThreshold,
+    ) -> Result<VotingPower, CertificateError> {
+        let expected = self.checksum();
+        if signers.validator_set != expected {
+            return Err(CertificateError::ValidatorSetMismatch {
+                expected,
+                got: signers.validator_set,
+            });
+        }
+
+        let signed = self.signer_power(signers.signers.as_bytes())?;
+        let total = self.total_voting_power();
+        if !threshold.is_met(signed, total) {
+            return Err(CertificateError::InsufficientPower { signed, total });
+        }
+        Ok(signed)
+    }
+
     /// Get a validator by its address
     pub fn get_by_address(&self, address: &Address) -> Option<&Validator> {
         self.validators.iter().find(|v| &v.address == address)
@@ -106,6 +183,175 @@ impl ValidatorSet {
     }
 }
 
+/// Fraction of the total voting power that signers must strictly exceed to
+/// form a quorum.
+///
+/// Defaults to the standard BFT threshold of 2/3. Any threshold below 1/2 would
+/// let two disjoint quorums commit conflicting values, so [`QuorumThreshold::new`]
+/// rejects it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawQuorumThreshold")]
+pub struct QuorumThreshold {
+    numerator: u64,
+    denominator: u64,
+}
+
+#[derive(Deserialize)]
+struct RawQuorumThreshold {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl TryFrom<RawQuorumThreshold> for QuorumThreshold {
+    type Error = InvalidQuorumThreshold;
+
+    fn try_from(raw: RawQuorumThreshold) -> Result<Self, Self::Error> {
+        Self::new(raw.numerator, raw.denominator)
+    }
+}
+
+impl QuorumThreshold {
+    /// The standard BFT threshold, more than 2/3 of the voting power
+    pub const TWO_THIRDS: Self = Self {
+        numerator: 2,
+        denominator: 3,
+    };
+
+    /// Threshold of more than `numerator / denominator` of the voting power.
+    ///
+    /// Requires `numerator < denominator`, so that a quorum is reachable, and
+    /// `2 * numerator >= denominator`, so that any two quorums intersect.
+    pub fn new(numerator: u64, denominator: u64) -> Result<Self, InvalidQuorumThreshold> {
+        if numerator >= denominator || u128::from(numerator) * 2 < u128::from(denominator) {
+            return Err(InvalidQuorumThreshold {
+                numerator,
+                denominator,
+            });
+        }
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    /// Whether `signed` out of `total` voting power exceeds the threshold
+    pub fn is_met(&self, signed: VotingPower, total: VotingPower) -> bool {
+        u128::from(signed) * u128::from(self.denominator)
+            > u128::from(total) * u128::from(self.numerator)
+    }
+
+    /// The least voting power out of `total` that exceeds the threshold
+    pub fn quorum_power(&self, total: VotingPower) -> VotingPower {
+        let floor = u128::from(total) * u128::from(self.numerator) / u128::from(self.denominator);
+        // floor < total since numerator < denominator, so this fits in a VotingPower
+        floor as VotingPower + 1
+    }
+}
+
+impl Default for QuorumThreshold {
+    fn default() -> Self {
+        Self::TWO_THIRDS
+    }
+}
+
+/// Error returned by [`QuorumThreshold::new`] for a threshold that is either
+/// unreachable or does not guarantee that quorums intersect
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("invalid quorum threshold {numerator}/{denominator}, expected 1/2 <= threshold < 1")]
+pub struct InvalidQuorumThreshold {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+/// Error returned by [`ValidatorSet::signer_power`] for a bitfield that does
+/// not describe a subset of the validator set
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum BitfieldError {
+    #[error("signer bitfield is {got} bytes, expected {expected}")]
+    Length { expected: usize, got: usize },
+
+    #[error("signer bitfield sets bit {index}, outside of the validator set")]
+    OutOfRange { index: usize },
+
+    #[error("voting power of the signers overflows")]
+    PowerOverflow,
+}
+
+/// Error returned by [`ValidatorSet::verify_certificate`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum CertificateError {
+    #[error("signer bitmap was built against validator set {got}, expected {expected}")]
+    ValidatorSetMismatch { expected: B256, got: B256 },
+
+    #[error(transparent)]
+    Bitfield(#[from] BitfieldError),
+
+    #[error("signers hold {signed} of {total} voting power, which is not a quorum")]
+    InsufficientPower {
+        signed: VotingPower,
+        total: VotingPower,
+    },
+}
+
+/// Version of the serialized validator set layout written by this code
+pub const VALIDATOR_SET_VERSION: u32 = 1;
+
+/// Error returned when loading a validator set written by newer code
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("unsupported validator set version {0}, expected at most {VALIDATOR_SET_VERSION}")]
+pub struct UnsupportedVersion(pub u32);
+
+/// Serialized form of a [`ValidatorSet`], tagged with the version of its layout
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializedValidatorSet {
+    /// Absent from sets written before versioning, which are version 1
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+    pub validators: Vec<Validator>,
+}
+
+fn legacy_version() -> u32 {
+    1
+}
+
+impl SerializedValidatorSet {
+    /// Upgrade to the current in-memory form, filling defaults for fields added
+    /// since `version`
+    pub fn migrate(self) -> Result<ValidatorSet, UnsupportedVersion> {
+        match self.version {
+            1 => Ok(ValidatorSet {
+                validators: Arc::new(self.validators),
+            }),
+            version => Err(UnsupportedVersion(version)),
+        }
+    }
+}
+
+impl TryFrom<SerializedValidatorSet> for ValidatorSet {
+    type Error = UnsupportedVersion;
+
+    fn try_from(serialized: SerializedValidatorSet) -> Result<Self, Self::Error> {
+        serialized.migrate()
+    }
+}
+
+impl From<ValidatorSet> for SerializedValidatorSet {
+    fn from(set: ValidatorSet) -> Self {
+        Self {
+            version: VALIDATOR_SET_VERSION,
+            validators: Arc::unwrap_or_clone(set.validators),
+        }
+    }
+}
+
 impl malachitebft_core_types::ValidatorSet<EmeraldContext> for ValidatorSet {
     fn count(&self) -> usize {
         self.validators.len()
@@ -123,3 +369,178 @@ impl malachitebft_core_types::ValidatorSet<EmeraldContext> for ValidatorSet {
         self.validators.get(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::validators::make_validators;
+    use crate::{MissingTag, SignerBitmap};
+
+    #[test]
+    fn default_quorum_is_more_than_two_thirds() {
+        let threshold = QuorumThreshold::default();
+        assert_eq!(threshold, QuorumThreshold::TWO_THIRDS);
+
+        assert_eq!(threshold.quorum_power(100), 67);
+        assert_eq!(threshold.quorum_power(99), 67);
+        assert_eq!(threshold.quorum_power(3), 3);
+        assert_eq!(threshold.quorum_power(1), 1);
+
+        // Exactly 2/3 is not a quorum
+        assert!(!threshold.is_met(66, 99));
+        assert!(threshold.is_met(67, 99));
+        assert!(!threshold.is_met(2, 3));
+
+        let validators = make_validators([10, 20, 30, 40]);
+        let set = ValidatorSet::new(validators.map(|(validator, _)| validator));
+        assert_eq!(set.quorum_power(threshold), 67);
+    }
+
+    #[test]
+    fn custom_quorum_threshold() {
+        let threshold = QuorumThreshold::new(3, 4).unwrap();
+        assert_eq!(threshold.quorum_power(100), 76);
+        assert_eq!(threshold.quorum_power(99), 75);
+        assert!(!threshold.is_met(75, 100));
+        assert!(threshold.is_met(76, 100));
+
+        let validators = make_validators([10, 20, 30, 40]);
+        let set = ValidatorSet::new(validators.map(|(validator, _)| validator));
+        assert_eq!(set.quorum_power(threshold), 76);
+
+        // Simple majority is the weakest threshold that keeps quorums intersecting
+        assert!(QuorumThreshold::new(1, 2).is_ok());
+        for (numerator, denominator) in [(1, 3), (3, 3), (4, 3), (0, 1), (0, 0)] {
+            assert_eq!(
+                QuorumThreshold::new(numerator, denominator),
+                Err(InvalidQuorumThreshold {
+                    numerator,
+                    denominator
+                })
+            );
+        }
+
+        let parsed: QuorumThreshold =
+            serde_json::from_str(r#"{"numerator":3,"denominator":4}"#).unwrap();
+        assert_eq!(parsed, threshold);
+        assert!(
+            serde_json::from_str::<QuorumThreshold>(r#"{"numerator":1,"denominator":3}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn signer_power_sums_the_selected_validators() {
+        let validators = make_validators([10, 20, 30, 40, 50, 60, 70, 80, 90]);
+        let set = ValidatorSet::new(validators.map(|(validator, _)| validator));
+        let power = |index: usize| set.validators[index].voting_power;
+
+        // Validators 0, 2 and 8
+        let bitfield = [0b0000_0101, 0b0000_0001];
+        assert_eq!(
+            set.signer_power(&bitfield),
+            Ok(power(0) + power(2) + power(8))
+        );
+        assert_eq!(set.signer_power(&[0, 0]), Ok(0));
+        assert_eq!(
+            set.signer_power(&[0xff, 0x01]),
+            Ok(set.total_voting_power())
+        );
+
+        assert_eq!(
+            set.signer_power(&[0b0000_0101, 0, 0]),
+            Err(BitfieldError::Length {
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            set.signer_power(&[0, 0b0000_0010]),
+            Err(BitfieldError::OutOfRange { index: 9 })
+        );
+
+        let [(alice, _), (bob, _)] = make_validators([10, 20]);
+        let heavy = Validator {
+            voting_power: VotingPower::MAX,
+            ..alice
+        };
+        let set = ValidatorSet::new([heavy, bob]);
+        assert_eq!(set.signer_power(&[0b0000_0001]), Ok(VotingPower::MAX));
+        assert_eq!(
+            set.signer_power(&[0b0000_0011]),
+            Err(BitfieldError::PowerOverflow)
+        );
+    }
+
+    #[test]
+    fn certificate_is_tied_to_its_validator_set() {
+        let [(alice, _), (bob, _), (carol, _), (dave, _)] = make_validators([10, 20, 30, 40]);
+        let set_a = ValidatorSet::new([alice.clone(), bob.clone(), carol.clone()]);
+        let set_b = ValidatorSet::new([alice, bob, carol, dave]);
+        assert_ne!(set_a.checksum(), set_b.checksum());
+
+        let mut signers = SignerBitmap::with_len(set_a.validators.len());
+        for index in 0..set_a.validators.len() {
+            signers.set(index);
+        }
+        let tagged = TaggedSignerBitmap::new(signers, &set_a);
+
+        let bytes = tagged.to_bytes();
+        assert_eq!(&bytes[..32], set_a.checksum().as_slice());
+        let decoded = TaggedSignerBitmap::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, tagged);
+
+        let threshold = QuorumThreshold::default();
+        assert_eq!(
+            set_a.verify_certificate(&decoded, threshold),
+            Ok(set_a.total_voting_power())
+        );
+        assert_eq!(
+            set_b.verify_certificate(&decoded, threshold),
+            Err(CertificateError::ValidatorSetMismatch {
+                expected: set_b.checksum(),
+                got: set_a.checksum(),
+            })
+        );
+
+        assert_eq!(
+            TaggedSignerBitmap::from_bytes(&bytes[..31]),
+            Err(MissingTag { len: 31 })
+        );
+    }
+
+    #[test]
+    fn legacy_unversioned_set_loads_as_version_1() {
+        let [(alice, _), (bob, _)] = make_validators([10, 20]);
+        let set = ValidatorSet::new([alice, bob]);
+
+        let legacy = serde_json::json!({ "validators": set.validators.as_ref() });
+        let loaded: ValidatorSet = serde_json::from_value(legacy).unwrap();
+        assert_eq!(loaded, set);
+    }
+
+    #[test]
+    fn current_version_roundtrips() {
+        let [(alice, _), (bob, _), (carol, _)] = make_validators([10, 20, 30]);
+        let set = ValidatorSet::new([alice, bob, carol]);
+
+        let json = serde_json::to_value(&set).unwrap();
+        assert_eq!(json["version"], VALIDATOR_SET_VERSION);
+        assert_eq!(serde_json::from_value::<ValidatorSet>(json).unwrap(), set);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let [(alice, _)] = make_validators([10]);
+        let serialized = SerializedValidatorSet {
+            version: VALIDATOR_SET_VERSION + 1,
+            validators: vec![alice],
+        };
+
+        let json = serde_json::to_value(&serialized).unwrap();
+        assert!(serde_json::from_value::<ValidatorSet>(json).is_err());
+        assert_eq!(
+            serialized.migrate(),
+            Err(UnsupportedVersion(VALIDATOR_SET_VERSION + 1))
+        );
+    }
+}
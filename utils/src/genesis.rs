@@ -1,23 +1,36 @@
+use core::fmt;
 use core::str::FromStr;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
 use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
-use alloy_primitives::{address, hex, Address, B256, U256};
+use alloy_primitives::{address, hex, keccak256, Address, Bytes, B256, U256};
 use alloy_signer_local::coins_bip39::English;
 use alloy_signer_local::{MnemonicBuilder, PrivateKeySigner};
 use chrono::NaiveDate;
+use clap::ValueEnum;
 use color_eyre::eyre::{eyre, Result};
+use emerald_contracts::SystemContracts;
 use hex::decode;
 use k256::ecdsa::VerifyingKey;
 // Malachite types for Emerald genesis
-use malachitebft_eth_types::secp256k1::PublicKey as EmeraldPublicKey;
+use malachitebft_core_types::CommitCertificate;
+use malachitebft_eth_types::bls12381::{
+    MinPk, PrivateKey as BlsPrivateKey, PublicKey as BlsPublicKey,
+};
+use malachitebft_eth_types::secp256k1::{verify_commit_certificate, PublicKey as EmeraldPublicKey};
 use malachitebft_eth_types::{
-    Genesis as EmeraldGenesis, Validator as EmeraldValidator, ValidatorSet as EmeraldValidatorSet,
+    EmeraldContext, Genesis as EmeraldGenesis, Height, Validator as EmeraldValidator,
+    ValidatorSet as EmeraldValidatorSet, ValueId,
 };
-use tracing::debug;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::validator_manager::{
-    generate_storage_data, Validator, ValidatorManager, GENESIS_VALIDATOR_MANAGER_ACCOUNT,
+    describe_storage, generate_storage_data, parse_validator_set_from_storage,
+    Sec1UncompressedPubKey, Validator, ValidatorManager, ValidatorManagerLayout, ValidatorSet,
+    ValidatroManagerError, GENESIS_VALIDATOR_MANAGER_ACCOUNT,
 };
 
 /// EIP-4788 Beacon Roots Contract address
@@ -27,6 +40,28 @@ const BEACON_ROOTS_ADDRESS: Address = address!("0x000F3df6D732807Ef1319fB7B8bB85
 /// See: https://eips.ethereum.org/EIPS/eip-4788
 const BEACON_ROOTS_CODE: [u8; 97] = hex!("0x3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500");
 
+/// Chain ID used by the development and test presets
+const DEFAULT_CHAIN_ID: u64 = 12345;
+
+/// Balance, in ether, of each pre-funded test account
+const DEFAULT_DEVNET_BALANCE: u64 = 15_000;
+
+/// Version of the tool that generated a genesis, recorded in its extra data
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Identifier of the ValidatorManager storage layout written at genesis,
+/// to be bumped whenever the slots produced by the generator change
+pub const STORAGE_LAYOUT: &str = "vm-layout-2";
+
+/// Voting power given to every validator of the public keys file
+pub const GENESIS_VALIDATOR_POWER: u64 = 100;
+
+/// Prefix of the generator stamp in the genesis extra data
+const GENESIS_STAMP_PREFIX: &str = "emerald";
+
+/// Maximum size of the header extra data accepted by execution clients
+const MAX_EXTRA_DATA_SIZE: usize = 32;
+
 /// Test mnemonic for wallet generation
 const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
 
@@ -44,22 +79,346 @@ pub(crate) fn make_signers() -> Vec<PrivateKeySigner> {
     (0..10).map(make_signer).collect()
 }
 
+/// Per-network bundle of genesis defaults
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum GenesisPreset {
+    /// Local development network with pre-funded test accounts
+    Devnet,
+    /// Public test network without test accounts
+    #[default]
+    Testnet,
+    /// Production network, with strict checks and no default chain ID. As every
+    /// genesis validator gets [`GENESIS_VALIDATOR_POWER`], fewer than 3 validators
+    /// hold more than a third of the power each and are rejected.
+    Mainnet,
+}
+
+/// Latest EVM hardfork active at genesis. Later hardforks stay disabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum EvmHardfork {
+    Shanghai,
+    Cancun,
+    Prague,
+    #[default]
+    Osaka,
+}
+
+impl EvmHardfork {
+    /// Activation time of `fork` at genesis: `Some(0)` if it is not later
+    /// than `self`, `None` if it stays disabled
+    fn activation(self, fork: Self) -> Option<u64> {
+        (fork <= self).then_some(0)
+    }
+}
+
+/// Opcodes introduced by a hardfork, with the first hardfork supporting them
+const FORK_OPCODES: &[(u8, &str, EvmHardfork)] = &[
+    (0x5f, "PUSH0", EvmHardfork::Shanghai),
+    (0x5c, "TLOAD", EvmHardfork::Cancun),
+    (0x5d, "TSTORE", EvmHardfork::Cancun),
+    (0x5e, "MCOPY", EvmHardfork::Cancun),
+    (0x49, "BLOBHASH", EvmHardfork::Cancun),
+    (0x4a, "BLOBBASEFEE", EvmHardfork::Cancun),
+    (0x1e, "CLZ", EvmHardfork::Osaka),
+];
+
+/// Names of the opcodes in `code` that are not available at `hardfork`,
+/// skipping the immediate data of PUSH instructions
+fn unsupported_opcodes(code: &[u8], hardfork: EvmHardfork) -> Vec<&'static str> {
+    let mut unsupported = Vec::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let opcode = code[pc];
+        if let Some((_, name, fork)) = FORK_OPCODES.iter().find(|(op, _, _)| *op == opcode) {
+            if *fork > hardfork && !unsupported.contains(name) {
+                unsupported.push(*name);
+            }
+        }
+
+        // PUSH1..PUSH32 are followed by 1..32 bytes of immediate data
+        pc += match opcode {
+            0x60..=0x7f => usize::from(opcode - 0x5f) + 1,
+            _ => 1,
+        };
+    }
+    unsupported
+}
+
+/// Layout of the generated EVM genesis file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum GenesisFormat {
+    /// Standard genesis, as accepted by any execution client
+    #[default]
+    Standard,
+    /// Standard genesis with reth-specific hints added to the chain config,
+    /// such as the address of the ValidatorManager system contract
+    RethChainspec,
+}
+
+impl GenesisFormat {
+    /// Add the fields specific to this format to a standard genesis
+    fn apply(self, genesis: &mut Genesis) -> Result<()> {
+        match self {
+            Self::Standard => {}
+            Self::RethChainspec => {
+                genesis.config.extra_fields.insert_value(
+                    "validatorManagerAddress".to_string(),
+                    GENESIS_VALIDATOR_MANAGER_ACCOUNT,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How to report a validator holding more than a third of the total power
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcentrationCheck {
+    /// Log a warning and continue
+    Warn,
+    /// Refuse to generate the genesis
+    Deny,
+}
+
+/// Resolved parameters for EVM genesis generation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenesisParams {
+    /// Chain ID, which must be set explicitly for presets without a default
+    pub chain_id: Option<u64>,
+    /// Pre-fund the accounts derived from the test mnemonic
+    pub devnet: bool,
+    /// Balance, in ether, of each pre-funded test account
+    pub devnet_balance: u64,
+    /// Handling of validators with more than a third of the total power
+    pub concentration_check: ConcentrationCheck,
+    /// Latest EVM hardfork active at genesis
+    pub hardfork: EvmHardfork,
+    /// Layout of the generated genesis file
+    pub format: GenesisFormat,
+    /// Number of validators the public keys file must hold, if known
+    pub expected_validators: Option<usize>,
+    /// Also write a [`GenesisMetadata`] sidecar next to the genesis file
+    pub metadata: bool,
+}
+
+/// Explicit values that take precedence over the preset defaults
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenesisOverrides {
+    pub chain_id: Option<u64>,
+    pub devnet: Option<bool>,
+    pub devnet_balance: Option<u64>,
+    pub concentration_check: Option<ConcentrationCheck>,
+    pub hardfork: Option<EvmHardfork>,
+    pub format: Option<GenesisFormat>,
+    pub expected_validators: Option<usize>,
+    pub metadata: Option<bool>,
+}
+
+impl GenesisPreset {
+    /// Default parameters for this preset
+    pub fn params(self) -> GenesisParams {
+        match self {
+            Self::Devnet => GenesisParams {
+                chain_id: Some(DEFAULT_CHAIN_ID),
+                devnet: true,
+                devnet_balance: DEFAULT_DEVNET_BALANCE,
+                concentration_check: ConcentrationCheck::Warn,
+                hardfork: EvmHardfork::default(),
+                format: GenesisFormat::default(),
+                expected_validators: None,
+                metadata: false,
+            },
+            Self::Testnet => GenesisParams {
+                chain_id: Some(DEFAULT_CHAIN_ID),
+                devnet: false,
+                devnet_balance: DEFAULT_DEVNET_BALANCE,
+                concentration_check: ConcentrationCheck::Warn,
+                hardfork: EvmHardfork::default(),
+                format: GenesisFormat::default(),
+                expected_validators: None,
+                metadata: false,
+            },
+            Self::Mainnet => GenesisParams {
+                chain_id: None,
+                devnet: false,
+                devnet_balance: 0,
+                concentration_check: ConcentrationCheck::Deny,
+                hardfork: EvmHardfork::default(),
+                format: GenesisFormat::default(),
+                expected_validators: None,
+                metadata: false,
+            },
+        }
+    }
+}
+
+impl GenesisParams {
+    /// Replace the defaults with any explicitly provided values
+    pub fn with_overrides(self, overrides: &GenesisOverrides) -> Self {
+        Self {
+            chain_id: overrides.chain_id.or(self.chain_id),
+            devnet: overrides.devnet.unwrap_or(self.devnet),
+            devnet_balance: overrides.devnet_balance.unwrap_or(self.devnet_balance),
+            concentration_check: overrides
+                .concentration_check
+                .unwrap_or(self.concentration_check),
+            hardfork: overrides.hardfork.unwrap_or(self.hardfork),
+            format: overrides.format.unwrap_or(self.format),
+            expected_validators: overrides.expected_validators.or(self.expected_validators),
+            metadata: overrides.metadata.unwrap_or(self.metadata),
+        }
+    }
+
+    /// Like [`Self::with_overrides`], but rejects a devnet balance given for a
+    /// genesis without pre-funded test accounts, where it would be ignored
+    pub fn try_with_overrides(self, overrides: &GenesisOverrides) -> Result<Self> {
+        let params = self.with_overrides(overrides);
+        if overrides.devnet_balance.is_some_and(|balance| balance != 0) && !params.devnet {
+            return Err(eyre!(
+                "a devnet balance was given but devnet accounts are disabled; pass --devnet to pre-fund them"
+            ));
+        }
+        Ok(params)
+    }
+
+    /// Balance of each pre-funded test account, in wei
+    pub fn devnet_balance_wei(&self) -> U256 {
+        U256::from(self.devnet_balance) * U256::from(10).pow(U256::from(18))
+    }
+}
+
+/// Human-readable summary of a generated genesis, written next to the genesis
+/// file for operators. It is informational only and never read by the node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisMetadata {
+    pub chain_id: u64,
+    pub generator_version: String,
+    pub storage_layout: String,
+    pub validator_manager: Address,
+    pub owner: Address,
+    pub total_power: u64,
+    pub validators: Vec<ValidatorMetadata>,
+}
+
+/// A genesis validator, as listed in [`GenesisMetadata`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorMetadata {
+    pub address: Address,
+    /// Uncompressed SEC1 public key
+    pub public_key: Bytes,
+    pub power: u64,
+}
+
+impl GenesisMetadata {
+    fn new(chain_id: u64, validators: &[Validator], owner: Address) -> Self {
+        let stamp = GenesisStamp::current();
+        Self {
+            chain_id,
+            generator_version: stamp.generator_version,
+            storage_layout: stamp.storage_layout,
+            validator_manager: GENESIS_VALIDATOR_MANAGER_ACCOUNT,
+            owner,
+            total_power: validators.iter().map(|v| v.power).sum(),
+            validators: validators
+                .iter()
+                .map(|v| ValidatorMetadata {
+                    address: v.address(),
+                    public_key: Sec1UncompressedPubKey::from_validator_key(&v.validator_key).into(),
+                    power: v.power,
+                })
+                .collect(),
+        }
+    }
+
+    /// Path of the sidecar for a genesis file, e.g. `genesis.meta.json` for `genesis.json`
+    pub fn path_for(genesis_file: &Path) -> PathBuf {
+        genesis_file.with_extension("meta.json")
+    }
+}
+
+/// A genesis validator, as listed in the validators sidecar of [`generate_evm_genesis`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorEntry {
+    pub address: Address,
+    pub x: U256,
+    pub y: U256,
+    pub power: u64,
+}
+
+impl ValidatorEntry {
+    /// Entries for `validators` and their addresses, sorted by address
+    pub fn sorted<'a>(validators: impl IntoIterator<Item = (Address, &'a Validator)>) -> Vec<Self> {
+        let mut entries: Vec<Self> = validators
+            .into_iter()
+            .map(|(address, v)| {
+                let (x, y) = v.validator_key;
+                Self {
+                    address,
+                    x,
+                    y,
+                    power: v.power,
+                }
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.address);
+        entries
+    }
+}
+
+/// Generator version and storage layout identifier read back from a genesis
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenesisStamp {
+    pub generator_version: String,
+    pub storage_layout: String,
+}
+
+impl GenesisStamp {
+    /// Stamp of the current generator
+    pub fn current() -> Self {
+        Self {
+            generator_version: GENERATOR_VERSION.to_string(),
+            storage_layout: STORAGE_LAYOUT.to_string(),
+        }
+    }
+
+    /// Encode as `emerald/<version>/<layout>` for the genesis extra data
+    pub fn encode(&self) -> Bytes {
+        Bytes::from(format!(
+            "{GENESIS_STAMP_PREFIX}/{}/{}",
+            self.generator_version, self.storage_layout
+        ))
+    }
+
+    /// Read the stamp from the extra data of a genesis, if it has one
+    pub fn read(genesis: &Genesis) -> Option<Self> {
+        let text = core::str::from_utf8(&genesis.extra_data).ok()?;
+        let mut parts = text.split('/');
+        if parts.next()? != GENESIS_STAMP_PREFIX {
+            return None;
+        }
+
+        let stamp = Self {
+            generator_version: parts.next()?.to_string(),
+            storage_layout: parts.next()?.to_string(),
+        };
+        parts.next().is_none().then_some(stamp)
+    }
+}
+
 pub(crate) fn generate_genesis(
     public_keys_file: &str,
     poa_address_owner: &Option<String>,
-    testnet: &bool,
-    testnet_balance: &u64,
-    chain_id: &u64,
+    params: &GenesisParams,
     evm_genesis_output_file: &str,
     emerald_genesis_output_file: &str,
+    validators_sidecar_path: Option<&str>,
 ) -> Result<()> {
     generate_evm_genesis(
         public_keys_file,
         poa_address_owner,
-        testnet,
-        testnet_balance,
-        chain_id,
+        params,
         evm_genesis_output_file,
+        validators_sidecar_path,
     )?;
 
     generate_emerald_genesis(public_keys_file, emerald_genesis_output_file)?;
@@ -67,18 +426,24 @@ pub(crate) fn generate_genesis(
     Ok(())
 }
 
+/// Write the EVM genesis to `genesis_output_file` and, when
+/// `validators_sidecar_path` is given, the genesis validators sorted by
+/// address to that path
 pub(crate) fn generate_evm_genesis(
     public_keys_file: &str,
     poa_address_owner: &Option<String>,
-    testnet: &bool,
-    testnet_balance: &u64,
-    chain_id: &u64,
+    params: &GenesisParams,
     genesis_output_file: &str,
+    validators_sidecar_path: Option<&str>,
 ) -> Result<()> {
+    let chain_id = params
+        .chain_id
+        .ok_or_else(|| eyre!("a chain ID is required for this genesis preset"))?;
+
     let mut alloc = BTreeMap::new();
     let signers = make_signers();
     // If test addresses are requested, create them and pre-fund them
-    if *testnet {
+    if params.devnet {
         // Create signers and get their addresses
         let signer_addresses: Vec<Address> =
             signers.iter().map(|signer| signer.address()).collect();
@@ -91,7 +456,7 @@ pub(crate) fn generate_evm_genesis(
             );
         }
 
-        let amount = U256::from(*testnet_balance) * U256::from(10).pow(U256::from(18));
+        let amount = params.devnet_balance_wei();
         for addr in &signer_addresses {
             alloc.insert(
                 *addr,
@@ -149,29 +514,61 @@ pub(crate) fn generate_evm_genesis(
         let mut y_bytes = [0u8; 32];
         y_bytes.copy_from_slice(&bytes[32..]);
         let key = (U256::from_be_bytes(x_bytes), U256::from_be_bytes(y_bytes));
-        initial_validators.push(Validator::from_public_key(key, 100));
+        initial_validators.push(Validator::from_public_key(key, GENESIS_VALIDATOR_POWER));
     }
 
+    if let Some(expected) = params.expected_validators {
+        if initial_validators.len() != expected {
+            return Err(ValidatroManagerError::ValidatorCountMismatch {
+                expected,
+                got: initial_validators.len(),
+            }
+            .into());
+        }
+    }
+
+    check_power_concentration(&initial_validators, params.concentration_check)?;
+
     // Parse PoA owner address or override with first test address
     let poa_address_owner = if let Some(addr_str) = poa_address_owner {
         Address::from_str(addr_str)
             .map_err(|e| eyre!("invalid PoA owner address '{}': {}", addr_str, e))?
-    } else if *testnet {
+    } else if params.devnet {
         signers[0].address()
     } else {
-        unreachable!("unable to determine PoA owner address");
+        return Err(eyre!(
+            "a PoA owner address is required when test accounts are not generated"
+        ));
     };
 
-    let storage = generate_storage_data(initial_validators, poa_address_owner)?;
+    if let Some(address) = SystemContracts::default().find_collision(alloc.keys()) {
+        return Err(eyre!(
+            "prefunded account {address} uses an address reserved for system contracts"
+        ));
+    }
+
+    let metadata = params
+        .metadata
+        .then(|| GenesisMetadata::new(chain_id, &initial_validators, poa_address_owner));
+    let validator_entries = validators_sidecar_path.map(|path| {
+        let validators = initial_validators.iter().map(|v| (v.address(), v));
+        (path, ValidatorEntry::sorted(validators))
+    });
+
     alloc.insert(
         GENESIS_VALIDATOR_MANAGER_ACCOUNT,
-        GenesisAccount {
-            code: Some(ValidatorManager::DEPLOYED_BYTECODE.clone()),
-            storage: Some(storage),
-            ..Default::default()
-        },
+        expected_validator_manager_account(initial_validators, poa_address_owner)?,
     );
 
+    let unsupported = unsupported_opcodes(&ValidatorManager::DEPLOYED_BYTECODE, params.hardfork);
+    if !unsupported.is_empty() {
+        warn!(
+            "ValidatorManager bytecode uses opcodes not available at {:?}: {}",
+            params.hardfork,
+            unsupported.join(", ")
+        );
+    }
+
     // Deploy EIP-4788 Beacon Roots Contract
     // Required for Engine API V3 compliance when parent_beacon_block_root is set
     // reth deploys this contract at genesis but only for chain-id 1 so we add it here manually in
@@ -193,10 +590,18 @@ pub(crate) fn generate_evm_genesis(
         .expect("Failed to create datetime with 21:49:01");
     let valid_fusaka_timestamp = datetime.and_utc().timestamp() as u64;
 
+    let stamp = GenesisStamp::current().encode();
+    if stamp.len() > MAX_EXTRA_DATA_SIZE {
+        return Err(eyre!(
+            "genesis stamp is {} bytes, exceeding the {MAX_EXTRA_DATA_SIZE} bytes of extra data",
+            stamp.len()
+        ));
+    }
+
     // Create genesis configuration
-    let genesis = Genesis {
+    let mut genesis = Genesis {
         config: ChainConfig {
-            chain_id: *chain_id,
+            chain_id,
             homestead_block: Some(0),
             eip150_block: Some(0),
             eip155_block: Some(0),
@@ -207,10 +612,10 @@ pub(crate) fn generate_evm_genesis(
             istanbul_block: Some(0),
             berlin_block: Some(0),
             london_block: Some(0),
-            shanghai_time: Some(0),
-            cancun_time: Some(0),
-            prague_time: Some(0),
-            osaka_time: Some(0),
+            shanghai_time: params.hardfork.activation(EvmHardfork::Shanghai),
+            cancun_time: params.hardfork.activation(EvmHardfork::Cancun),
+            prague_time: params.hardfork.activation(EvmHardfork::Prague),
+            osaka_time: params.hardfork.activation(EvmHardfork::Osaka),
             terminal_total_difficulty: Some(U256::ZERO),
             terminal_total_difficulty_passed: true,
             ..Default::default()
@@ -219,7 +624,9 @@ pub(crate) fn generate_evm_genesis(
         ..Default::default()
     }
     .with_gas_limit(60_000_000) // Fusaka default gas limit
-    .with_timestamp(valid_fusaka_timestamp);
+    .with_timestamp(valid_fusaka_timestamp)
+    .with_extra_data(stamp);
+    params.format.apply(&mut genesis)?;
 
     // Create data directory if it doesn't exist
     std::fs::create_dir_all("./assets")?;
@@ -229,6 +636,403 @@ pub(crate) fn generate_evm_genesis(
     std::fs::write(genesis_output_file, genesis_json)?;
     debug!("Genesis configuration written to {genesis_output_file}");
 
+    if let Some(metadata) = metadata {
+        let path = GenesisMetadata::path_for(Path::new(genesis_output_file));
+        std::fs::write(&path, serde_json::to_string_pretty(&metadata)?)?;
+        debug!("Genesis metadata written to {}", path.display());
+    }
+
+    if let Some((path, entries)) = validator_entries {
+        std::fs::write(path, serde_json::to_string_pretty(&entries)?)?;
+        debug!("Genesis validators written to {path}");
+    }
+
+    Ok(())
+}
+
+/// Name of a node's BLS validator key file, relative to its config directory.
+///
+/// Kept apart from `priv_validator_key.json`, which holds the secp256k1 key the
+/// node signs consensus messages with.
+pub const PRIV_VALIDATOR_BLS_KEY_FILE: &str = "priv_validator_bls_key.json";
+
+/// Contents of a node's BLS validator key file, as written by [`write_validator_key_files`]
+#[derive(Serialize, Deserialize)]
+struct ValidatorKeyFile {
+    /// Hex-encoded secret key
+    private_key: String,
+    public_key: BlsPublicKey<MinPk>,
+}
+
+impl Drop for ValidatorKeyFile {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+/// Path of the BLS validator key file of node `index` under `out_dir`, following
+/// the testnet home directory layout: `<out_dir>/<index>/config/priv_validator_bls_key.json`
+pub fn validator_key_file_path(out_dir: &Path, index: usize) -> PathBuf {
+    out_dir
+        .join(index.to_string())
+        .join("config")
+        .join(PRIV_VALIDATOR_BLS_KEY_FILE)
+}
+
+/// Write one BLS validator key file per node, holding the private key with its
+/// public key, to [`validator_key_file_path`].
+///
+/// Buffers holding key material are zeroized, and on unix the files are only
+/// readable by their owner.
+pub fn write_validator_key_files(
+    private_keys: &[BlsPrivateKey<MinPk>],
+    out_dir: &Path,
+) -> Result<()> {
+    for (index, private_key) in private_keys.iter().enumerate() {
+        let path = validator_key_file_path(out_dir, index);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let public_key = private_key.public_key();
+        let bytes = Zeroizing::new(private_key.to_bytes());
+        let key_file = ValidatorKeyFile {
+            private_key: hex::encode_prefixed(&*bytes),
+            public_key,
+        };
+        let json = Zeroizing::new(serde_json::to_string_pretty(&key_file)?);
+
+        write_private_file(&path, json.as_bytes())?;
+        debug!("Validator key written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Read back a validator key file written by [`write_validator_key_files`],
+/// checking that its public key matches the private key
+pub fn read_validator_key_file(path: &Path) -> Result<BlsPrivateKey<MinPk>> {
+    let json = Zeroizing::new(std::fs::read_to_string(path)?);
+    let key_file: ValidatorKeyFile = serde_json::from_str(&json)?;
+
+    let bytes = Zeroizing::new(
+        hex::decode(&key_file.private_key)
+            .map_err(|_| eyre!("invalid private key hex in {}", path.display()))?,
+    );
+    let private_key = BlsPrivateKey::<MinPk>::from_bytes(&bytes)
+        .map_err(|e| eyre!("invalid private key in {}: {e:?}", path.display()))?;
+
+    let public_key = private_key.public_key();
+    if public_key != key_file.public_key {
+        return Err(eyre!(
+            "public key in {} does not match its private key",
+            path.display()
+        ));
+    }
+    Ok(private_key)
+}
+
+fn write_private_file(path: &Path, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    // The mode above only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(data)?;
+
+    Ok(())
+}
+
+/// Build the genesis account of the validator manager contract, with its code,
+/// storage, nonce and balance, as it appears in the genesis alloc
+pub fn expected_validator_manager_account(
+    validators: Vec<Validator>,
+    owner: Address,
+) -> Result<GenesisAccount> {
+    let storage = generate_storage_data(validators, owner)?;
+
+    // Balance and nonce are set explicitly so both are always serialized,
+    // keeping the genesis hash independent of serde defaults
+    Ok(GenesisAccount {
+        code: Some(ValidatorManager::DEPLOYED_BYTECODE.clone()),
+        storage: Some(storage),
+        nonce: Some(0),
+        balance: U256::ZERO,
+        ..Default::default()
+    })
+}
+
+/// Recover the genesis validator set from the validator manager storage in an
+/// EVM genesis, without querying a node
+pub fn validator_set_from_genesis(genesis: &Genesis) -> Result<ValidatorSet> {
+    validator_set_from_genesis_at(genesis, GENESIS_VALIDATOR_MANAGER_ACCOUNT)
+}
+
+/// Like [`validator_set_from_genesis`], for a validator manager deployed at `account`
+pub fn validator_set_from_genesis_at(genesis: &Genesis, account: Address) -> Result<ValidatorSet> {
+    let storage = genesis
+        .alloc
+        .get(&account)
+        .ok_or_else(|| eyre!("no account at {account} in genesis alloc"))?
+        .storage
+        .as_ref()
+        .ok_or_else(|| eyre!("account {account} has no storage in genesis alloc"))?;
+
+    Ok(parse_validator_set_from_storage(storage)?)
+}
+
+/// Consensus validator set holding the validators of `validator_set`, which
+/// must all have a known public key
+pub fn consensus_validator_set(validator_set: &ValidatorSet) -> Result<EmeraldValidatorSet> {
+    let validators = validator_set
+        .iter_with_address()
+        .map(|(address, validator)| {
+            if validator_set
+                .address_only(&validator.validator_key)
+                .is_some()
+            {
+                return Err(eyre!("validator {address} has no known public key"));
+            }
+            let key = Sec1UncompressedPubKey::from_validator_key(&validator.validator_key);
+            let public_key = EmeraldPublicKey::from_sec1_bytes(key.as_bytes())
+                .map_err(|_| eyre!("validator {address} has an invalid secp256k1 public key"))?;
+            Ok(EmeraldValidator::new(public_key, validator.power))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if validators.is_empty() {
+        return Err(eyre!("validator set is empty"));
+    }
+    Ok(EmeraldValidatorSet::new(validators))
+}
+
+/// Verify that `certificate` commits `value_id` at `height` with a quorum of the
+/// validator set in `genesis`, as a light client bootstrapped from the genesis file.
+///
+/// Only valid for heights at which the genesis validator set is still active.
+pub fn verify_commit_over_genesis(
+    genesis: &Genesis,
+    height: Height,
+    value_id: ValueId,
+    certificate: &CommitCertificate<EmeraldContext>,
+) -> Result<()> {
+    if certificate.height != height || certificate.value_id != value_id {
+        return Err(eyre!(
+            "certificate commits value {} at height {}, expected value {value_id} at height {height}",
+            certificate.value_id,
+            certificate.height
+        ));
+    }
+
+    let validator_set = consensus_validator_set(&validator_set_from_genesis(genesis)?)?;
+    verify_commit_certificate(&validator_set, certificate)?;
+    Ok(())
+}
+
+/// Keccak-256 hash of the validator manager code placed in the genesis alloc,
+/// for comparison against the hash of the code returned by `eth_getCode`
+pub fn expected_validator_manager_code_hash() -> B256 {
+    keccak256(&ValidatorManager::DEPLOYED_BYTECODE)
+}
+
+/// Change of a single storage slot of an account present in both genesis files
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageChange {
+    pub account: Address,
+    pub slot: B256,
+    /// Name of the slot in the ValidatorManager layout, when known
+    pub label: Option<String>,
+    pub old: Option<B256>,
+    pub new: Option<B256>,
+}
+
+/// Change of a top-level chain config field, as JSON
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub field: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+/// Differences between two EVM genesis files, as reported by [`diff_genesis`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GenesisReport {
+    pub added_accounts: Vec<Address>,
+    pub removed_accounts: Vec<Address>,
+    /// Accounts present in both files whose code differs
+    pub code_changes: Vec<Address>,
+    pub storage_changes: Vec<StorageChange>,
+    pub config_changes: Vec<ConfigChange>,
+}
+
+impl GenesisReport {
+    pub fn is_empty(&self) -> bool {
+        self.added_accounts.is_empty()
+            && self.removed_accounts.is_empty()
+            && self.code_changes.is_empty()
+            && self.storage_changes.is_empty()
+            && self.config_changes.is_empty()
+    }
+}
+
+impl fmt::Display for GenesisReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show =
+            |value: Option<B256>| value.map_or_else(|| "<unset>".to_string(), |v| v.to_string());
+
+        for account in &self.added_accounts {
+            writeln!(f, "+ account {account}")?;
+        }
+        for account in &self.removed_accounts {
+            writeln!(f, "- account {account}")?;
+        }
+        for account in &self.code_changes {
+            writeln!(f, "~ code of {account}")?;
+        }
+        for change in &self.storage_changes {
+            let label = change.label.as_deref().unwrap_or("unknown slot");
+            writeln!(
+                f,
+                "~ storage of {} at {} ({label}): {} -> {}",
+                change.account,
+                change.slot,
+                show(change.old),
+                show(change.new)
+            )?;
+        }
+        for change in &self.config_changes {
+            let show = |value: &Option<serde_json::Value>| {
+                value
+                    .as_ref()
+                    .map_or_else(|| "<unset>".to_string(), ToString::to_string)
+            };
+            writeln!(
+                f,
+                "~ config.{}: {} -> {}",
+                change.field,
+                show(&change.old),
+                show(&change.new)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two EVM genesis files, reporting alloc accounts added or removed,
+/// code and per-slot storage changes of the remaining accounts, and chain
+/// config changes. Storage slots of the validator manager are labelled with
+/// their name in the contract layout.
+pub fn diff_genesis(old: &Genesis, new: &Genesis) -> GenesisReport {
+    let mut report = GenesisReport::default();
+    let empty = BTreeMap::new();
+
+    for (address, new_account) in &new.alloc {
+        let Some(old_account) = old.alloc.get(address) else {
+            report.added_accounts.push(*address);
+            continue;
+        };
+
+        if old_account.code != new_account.code {
+            report.code_changes.push(*address);
+        }
+
+        let old_storage = old_account.storage.as_ref().unwrap_or(&empty);
+        let new_storage = new_account.storage.as_ref().unwrap_or(&empty);
+        let mut labels = BTreeMap::new();
+        if *address == GENESIS_VALIDATOR_MANAGER_ACCOUNT {
+            let layout = ValidatorManagerLayout::default();
+            labels.extend(describe_storage(old_storage, &layout));
+            labels.extend(describe_storage(new_storage, &layout));
+        }
+
+        let slots: BTreeSet<&B256> = old_storage.keys().chain(new_storage.keys()).collect();
+        for slot in slots {
+            let (old_value, new_value) = (old_storage.get(slot), new_storage.get(slot));
+            if old_value != new_value {
+                report.storage_changes.push(StorageChange {
+                    account: *address,
+                    slot: *slot,
+                    label: labels.get(slot).cloned(),
+                    old: old_value.copied(),
+                    new: new_value.copied(),
+                });
+            }
+        }
+    }
+
+    report.removed_accounts = old
+        .alloc
+        .keys()
+        .filter(|address| !new.alloc.contains_key(address))
+        .copied()
+        .collect();
+
+    let fields = |config: &ChainConfig| match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let (old_config, new_config) = (fields(&old.config), fields(&new.config));
+    let names: BTreeSet<&String> = old_config.keys().chain(new_config.keys()).collect();
+    for name in names {
+        let (old_value, new_value) = (old_config.get(name), new_config.get(name));
+        if old_value != new_value {
+            report.config_changes.push(ConfigChange {
+                field: name.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            });
+        }
+    }
+
+    report
+}
+
+/// Check that no validator holds more than a third of the total voting power,
+/// which would let it halt the chain on its own
+fn check_power_concentration(validators: &[Validator], check: ConcentrationCheck) -> Result<()> {
+    let total_power: u128 = validators.iter().map(|v| u128::from(v.power)).sum();
+
+    for validator in validators {
+        if u128::from(validator.power) * 3 <= total_power {
+            continue;
+        }
+
+        let (x, y) = validator.validator_key;
+        match check {
+            ConcentrationCheck::Warn => warn!(
+                "Validator ({x:#x}, {y:#x}) holds more than a third of the total power ({} of {total_power})",
+                validator.power
+            ),
+            ConcentrationCheck::Deny => {
+                // With equal powers, this only happens for sets that are too small
+                let hint = if validators.len() < 3 {
+                    format!(
+                        "; at least 3 validators are needed, got {}",
+                        validators.len()
+                    )
+                } else {
+                    String::new()
+                };
+                return Err(eyre!(
+                    "validator ({x:#x}, {y:#x}) holds more than a third of the total power ({} of {total_power}){hint}",
+                    validator.power
+                ));
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -303,3 +1107,549 @@ pub(crate) fn generate_emerald_genesis(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use malachitebft_core_types::{CommitSignature, NilOrVal, Round};
+    use malachitebft_eth_types::secp256k1::PrivateKey as EmeraldPrivateKey;
+    use malachitebft_eth_types::{Address as EmeraldAddress, Vote};
+
+    use super::*;
+    use crate::validator_manager::storage::validator_address_from_key;
+    use crate::validator_manager::validator_slots;
+
+    fn validator(seed: u64, power: u64) -> Validator {
+        Validator::from_public_key((U256::from(seed), U256::from(seed)), power)
+    }
+
+    #[test]
+    fn test_verify_commit_over_genesis() {
+        let (genesis, _, _) = generated_genesis(4);
+        let height = Height::new(1);
+        let value_id = ValueId::new(42);
+
+        // Three of the four genesis validators, with equal power, precommit
+        let commit_signatures = (0..3)
+            .map(|i| {
+                let key =
+                    EmeraldPrivateKey::from_slice(&make_signer(i).credential().to_bytes()).unwrap();
+                let address = EmeraldAddress::from_public_key(&key.public_key());
+                let vote =
+                    Vote::new_precommit(height, Round::new(0), NilOrVal::Val(value_id), address);
+                CommitSignature::new(address, key.sign(&vote.to_sign_bytes()))
+            })
+            .collect::<Vec<_>>();
+        let certificate = CommitCertificate {
+            height,
+            round: Round::new(0),
+            value_id,
+            commit_signatures,
+        };
+
+        verify_commit_over_genesis(&genesis, height, value_id, &certificate).unwrap();
+        assert!(
+            verify_commit_over_genesis(&genesis, height.increment(), value_id, &certificate)
+                .is_err()
+        );
+        assert!(
+            verify_commit_over_genesis(&genesis, height, ValueId::new(43), &certificate).is_err()
+        );
+
+        // Two of four validators are not a quorum
+        let mut partial = certificate.clone();
+        partial.commit_signatures.truncate(2);
+        assert!(verify_commit_over_genesis(&genesis, height, value_id, &partial).is_err());
+
+        // Nor are signers outside of the genesis set
+        let mut outsider = certificate.clone();
+        let key = EmeraldPrivateKey::from_slice(&make_signer(9).credential().to_bytes()).unwrap();
+        let address = EmeraldAddress::from_public_key(&key.public_key());
+        let vote = Vote::new_precommit(height, Round::new(0), NilOrVal::Val(value_id), address);
+        outsider.commit_signatures[2] =
+            CommitSignature::new(address, key.sign(&vote.to_sign_bytes()));
+        assert!(verify_commit_over_genesis(&genesis, height, value_id, &outsider).is_err());
+    }
+
+    #[test]
+    fn test_reth_chainspec_format() {
+        let (standard, _, _) = generated_genesis(3);
+        assert!(standard.config.extra_fields.is_empty());
+
+        let params = GenesisParams {
+            format: GenesisFormat::RethChainspec,
+            ..GenesisPreset::Testnet.params()
+        };
+        let (genesis, _, _) = generated_genesis_with(3, &params);
+        assert_eq!(
+            genesis.config.extra_fields.get("validatorManagerAddress"),
+            Some(&serde_json::json!(GENESIS_VALIDATOR_MANAGER_ACCOUNT))
+        );
+        assert_eq!(genesis.alloc, standard.alloc);
+
+        let chainspec = reth_chainspec::ChainSpec::from(genesis);
+        assert_eq!(chainspec.chain.id(), DEFAULT_CHAIN_ID);
+        assert!(chainspec
+            .genesis
+            .alloc
+            .contains_key(&GENESIS_VALIDATOR_MANAGER_ACCOUNT));
+    }
+
+    /// Generate an EVM genesis for `count` validators derived from the test
+    /// mnemonic, returning it with the validators and the PoA owner
+    fn generated_genesis(count: u64) -> (Genesis, Vec<Validator>, Address) {
+        generated_genesis_with(count, &GenesisPreset::Testnet.params())
+    }
+
+    fn generated_genesis_with(
+        count: u64,
+        params: &GenesisParams,
+    ) -> (Genesis, Vec<Validator>, Address) {
+        try_generated_genesis_with(count, params).unwrap()
+    }
+
+    fn try_generated_genesis_with(
+        count: u64,
+        params: &GenesisParams,
+    ) -> Result<(Genesis, Vec<Validator>, Address)> {
+        let dir = tempfile::tempdir().unwrap();
+        try_generated_genesis_in(dir.path(), count, params)
+    }
+
+    /// Like [`try_generated_genesis_with`], writing the files to `dir`
+    fn try_generated_genesis_in(
+        dir: &Path,
+        count: u64,
+        params: &GenesisParams,
+    ) -> Result<(Genesis, Vec<Validator>, Address)> {
+        try_generated_genesis_with_sidecar(dir, count, params, None)
+    }
+
+    /// Like [`try_generated_genesis_in`], also writing the validators sidecar
+    /// to `sidecar` if given
+    fn try_generated_genesis_with_sidecar(
+        dir: &Path,
+        count: u64,
+        params: &GenesisParams,
+        sidecar: Option<&Path>,
+    ) -> Result<(Genesis, Vec<Validator>, Address)> {
+        let keys_file = dir.join("public_keys.txt");
+        let genesis_file = dir.join("genesis.json");
+
+        let validators: Vec<Validator> = (0..count)
+            .map(|i| {
+                let encoded = make_signer(i)
+                    .credential()
+                    .verifying_key()
+                    .to_encoded_point(false);
+                let bytes = &encoded.as_bytes()[1..];
+                let key = (
+                    U256::from_be_slice(&bytes[..32]),
+                    U256::from_be_slice(&bytes[32..]),
+                );
+                Validator::from_public_key(key, 100)
+            })
+            .collect();
+
+        let keys: Vec<String> = validators
+            .iter()
+            .map(|v| {
+                let (x, y) = v.validator_key;
+                format!(
+                    "{}{}",
+                    hex::encode(x.to_be_bytes::<32>()),
+                    hex::encode(y.to_be_bytes::<32>())
+                )
+            })
+            .collect();
+        std::fs::write(&keys_file, keys.join("\n")).unwrap();
+
+        let owner = make_signer(0).address();
+        generate_evm_genesis(
+            keys_file.to_str().unwrap(),
+            &Some(owner.to_string()),
+            params,
+            genesis_file.to_str().unwrap(),
+            sidecar.map(|path| path.to_str().unwrap()),
+        )?;
+
+        let genesis: Genesis = serde_json::from_str(&std::fs::read_to_string(&genesis_file)?)?;
+        Ok((genesis, validators, owner))
+    }
+
+    #[test]
+    fn test_validators_sidecar() {
+        let write = |dir: &Path| -> Vec<u8> {
+            let sidecar_file = dir.join("validators.json");
+            let (genesis, _, _) = try_generated_genesis_with_sidecar(
+                dir,
+                4,
+                &GenesisPreset::Testnet.params(),
+                Some(&sidecar_file),
+            )
+            .unwrap();
+            let encoded = ValidatorEntry::sorted(
+                validator_set_from_genesis(&genesis)
+                    .unwrap()
+                    .iter_with_address(),
+            );
+
+            let sidecar = std::fs::read(&sidecar_file).unwrap();
+            let entries: Vec<ValidatorEntry> = serde_json::from_slice(&sidecar).unwrap();
+            assert_eq!(entries, encoded);
+            assert_eq!(entries.len(), 4);
+            assert!(entries.is_sorted_by_key(|entry| entry.address));
+
+            sidecar
+        };
+
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        assert_eq!(write(first.path()), write(second.path()));
+    }
+
+    #[test]
+    fn test_write_validator_key_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let private_keys: Vec<BlsPrivateKey<MinPk>> = (1..=3u8)
+            .map(|i| BlsPrivateKey::from_bytes(&[i; 32]).unwrap())
+            .collect();
+
+        // The secp256k1 key file the node loads is left alone
+        let secp_key_file = dir.path().join("0/config/priv_validator_key.json");
+        std::fs::create_dir_all(secp_key_file.parent().unwrap()).unwrap();
+        std::fs::write(&secp_key_file, b"secp256k1 key").unwrap();
+
+        write_validator_key_files(&private_keys, dir.path()).unwrap();
+        assert_eq!(std::fs::read(&secp_key_file).unwrap(), b"secp256k1 key");
+
+        for (index, private_key) in private_keys.iter().enumerate() {
+            let path = validator_key_file_path(dir.path(), index);
+            assert_eq!(
+                path,
+                dir.path()
+                    .join(index.to_string())
+                    .join("config/priv_validator_bls_key.json")
+            );
+
+            let read = read_validator_key_file(&path).unwrap();
+            assert_eq!(read.to_bytes(), private_key.to_bytes());
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+            }
+        }
+        assert!(!validator_key_file_path(dir.path(), private_keys.len()).exists());
+
+        // Rewriting over an existing, world-readable file restricts it again
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = validator_key_file_path(dir.path(), 0);
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            write_validator_key_files(&private_keys[..1], dir.path()).unwrap();
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_read_validator_key_file_rejects_mismatched_public_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let private_keys: Vec<BlsPrivateKey<MinPk>> = (1..=2u8)
+            .map(|i| BlsPrivateKey::from_bytes(&[i; 32]).unwrap())
+            .collect();
+        write_validator_key_files(&private_keys, dir.path()).unwrap();
+
+        let path = validator_key_file_path(dir.path(), 0);
+        let mut json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        json["public_key"] = serde_json::json!(private_keys[1].public_key());
+        std::fs::write(&path, json.to_string()).unwrap();
+
+        assert!(read_validator_key_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_metadata_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let genesis_file = dir.path().join("genesis.json");
+        let metadata_file = GenesisMetadata::path_for(&genesis_file);
+        assert_eq!(metadata_file, dir.path().join("genesis.meta.json"));
+
+        try_generated_genesis_in(dir.path(), 3, &GenesisPreset::Testnet.params()).unwrap();
+        assert!(!metadata_file.exists());
+        let (standard, _, _) = generated_genesis(3);
+
+        let params = GenesisParams {
+            metadata: true,
+            ..GenesisPreset::Testnet.params()
+        };
+        let (genesis, validators, owner) =
+            try_generated_genesis_in(dir.path(), 3, &params).unwrap();
+        assert_eq!(genesis.alloc, standard.alloc);
+        assert_eq!(genesis.config, standard.config);
+
+        let metadata: GenesisMetadata =
+            serde_json::from_str(&std::fs::read_to_string(&metadata_file).unwrap()).unwrap();
+        assert_eq!(metadata.chain_id, DEFAULT_CHAIN_ID);
+        assert_eq!(metadata.generator_version, GENERATOR_VERSION);
+        assert_eq!(metadata.owner, owner);
+        assert_eq!(metadata.total_power, 300);
+        assert_eq!(metadata.validators.len(), 3);
+        assert_eq!(metadata.validators[0].address, validators[0].address());
+    }
+
+    #[test]
+    fn test_expected_validator_count() {
+        let params = GenesisParams {
+            expected_validators: Some(3),
+            ..GenesisPreset::Testnet.params()
+        };
+        assert!(try_generated_genesis_with(3, &params).is_ok());
+
+        // A truncated keys file is rejected instead of shrinking the validator set
+        let params = GenesisParams {
+            expected_validators: Some(5),
+            ..params
+        };
+        let err = try_generated_genesis_with(3, &params).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ValidatroManagerError>(),
+            Some(ValidatroManagerError::ValidatorCountMismatch {
+                expected: 5,
+                got: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_expected_validator_manager_account_matches_genesis_file() {
+        let (genesis, validators, owner) = generated_genesis(3);
+        let account = &genesis.alloc[&GENESIS_VALIDATOR_MANAGER_ACCOUNT];
+
+        let expected = expected_validator_manager_account(validators, owner).unwrap();
+        assert_eq!(account, &expected);
+    }
+
+    #[test]
+    fn test_validator_manager_account_serializes_zero_balance_and_nonce() {
+        let (_, validators, owner) = generated_genesis(1);
+        let account = expected_validator_manager_account(validators, owner).unwrap();
+
+        let json = serde_json::to_value(&account).unwrap();
+        assert_eq!(json["balance"], serde_json::json!("0x0"));
+        assert_eq!(json["nonce"], serde_json::json!("0x0"));
+    }
+
+    #[test]
+    fn test_diff_genesis_with_extra_validator() {
+        let (old, _, _) = generated_genesis(3);
+        let (new, validators, _) = generated_genesis(4);
+        let added = validators[3].validator_key;
+
+        let report = diff_genesis(&old, &new);
+        assert!(report.removed_accounts.is_empty());
+        assert!(!report
+            .code_changes
+            .contains(&GENESIS_VALIDATOR_MANAGER_ACCOUNT));
+        assert!(report.config_changes.is_empty());
+
+        let slots = validator_slots(&added, &ValidatorManagerLayout::default());
+        let changed: BTreeMap<B256, Option<String>> = report
+            .storage_changes
+            .iter()
+            .filter(|change| change.account == GENESIS_VALIDATOR_MANAGER_ACCOUNT)
+            .map(|change| (change.slot, change.label.clone()))
+            .collect();
+
+        let address = validator_address_from_key(&added, &ValidatorManagerLayout::default());
+        assert_eq!(
+            changed[&slots.position].as_deref(),
+            Some(format!("_validatorAddresses._positions[{address}]").as_str())
+        );
+        assert_eq!(
+            changed[&slots.power].as_deref(),
+            Some(format!("_validators[{address}].power/feeRecipient").as_str())
+        );
+        assert!(changed.contains_key(&slots.x) && changed.contains_key(&slots.y));
+        assert!(changed
+            .values()
+            .any(|label| label.as_deref() == Some("_validatorAddresses._values[3]")));
+        assert!(changed
+            .values()
+            .any(|label| label.as_deref() == Some("_validatorAddresses._values.length")));
+        assert!(changed
+            .values()
+            .any(|label| label.as_deref() == Some("_totalPower")));
+
+        assert!(diff_genesis(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_devnet_balance_in_wei() {
+        let params = GenesisPreset::Testnet
+            .params()
+            .try_with_overrides(&GenesisOverrides {
+                devnet: Some(true),
+                devnet_balance: Some(1000),
+                ..Default::default()
+            })
+            .unwrap();
+        let (genesis, _, _) = generated_genesis_with(1, &params);
+
+        let expected = U256::from(1000u64) * U256::from(10u64).pow(U256::from(18u64));
+        let funded = make_signer(0).address();
+        assert_eq!(genesis.alloc[&funded].balance, expected);
+    }
+
+    #[test]
+    fn test_devnet_balance_requires_devnet() {
+        let result = GenesisPreset::Testnet
+            .params()
+            .try_with_overrides(&GenesisOverrides {
+                devnet_balance: Some(1000),
+                ..Default::default()
+            });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cancun_hardfork_config() {
+        let params = GenesisPreset::Testnet
+            .params()
+            .with_overrides(&GenesisOverrides {
+                hardfork: Some(EvmHardfork::Cancun),
+                ..Default::default()
+            });
+        let (genesis, _, _) = generated_genesis_with(1, &params);
+
+        assert_eq!(genesis.config.shanghai_time, Some(0));
+        assert_eq!(genesis.config.cancun_time, Some(0));
+        assert_eq!(genesis.config.prague_time, None);
+        assert_eq!(genesis.config.osaka_time, None);
+    }
+
+    #[test]
+    fn test_unsupported_opcodes() {
+        // PUSH0, then PUSH2 whose immediate data contains a TSTORE byte
+        let code = [0x5f, 0x61, 0x5d, 0x00, 0x5c];
+        assert_eq!(
+            unsupported_opcodes(&code, EvmHardfork::Shanghai),
+            vec!["TLOAD"]
+        );
+        assert!(unsupported_opcodes(&code, EvmHardfork::Cancun).is_empty());
+    }
+
+    #[test]
+    fn test_genesis_stamp_is_written() {
+        let (genesis, _, _) = generated_genesis(1);
+
+        let stamp = GenesisStamp::read(&genesis).expect("genesis should be stamped");
+        assert_eq!(stamp, GenesisStamp::current());
+        assert_eq!(stamp.generator_version, GENERATOR_VERSION);
+        assert_eq!(stamp.storage_layout, STORAGE_LAYOUT);
+
+        assert_eq!(GenesisStamp::read(&Genesis::default()), None);
+    }
+
+    #[test]
+    fn test_validator_set_from_genesis_roundtrip() {
+        let (genesis, validators, _) = generated_genesis(4);
+
+        let recovered = validator_set_from_genesis(&genesis).unwrap();
+        assert_eq!(
+            recovered.get_validators(),
+            validators.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(recovered.total_power().unwrap(), 400);
+
+        assert!(validator_set_from_genesis_at(&genesis, Address::ZERO).is_err());
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn test_smoke_boot_generated_genesis() {
+        let (genesis, _, _) = generated_genesis(3);
+        assert_eq!(crate::smoke::smoke_boot(&genesis).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_expected_validator_manager_code_hash() {
+        let account =
+            expected_validator_manager_account(vec![validator(1, 100)], Address::ZERO).unwrap();
+        let code = account.code.expect("validator manager account has code");
+
+        assert_eq!(expected_validator_manager_code_hash(), keccak256(&code));
+    }
+
+    #[test]
+    fn test_preset_defaults() {
+        let devnet = GenesisPreset::Devnet.params();
+        assert_eq!(devnet.chain_id, Some(DEFAULT_CHAIN_ID));
+        assert!(devnet.devnet);
+        assert_eq!(devnet.devnet_balance, DEFAULT_DEVNET_BALANCE);
+        assert_eq!(devnet.concentration_check, ConcentrationCheck::Warn);
+
+        let testnet = GenesisPreset::Testnet.params();
+        assert_eq!(testnet.chain_id, Some(DEFAULT_CHAIN_ID));
+        assert!(!testnet.devnet);
+        assert_eq!(testnet.concentration_check, ConcentrationCheck::Warn);
+
+        let mainnet = GenesisPreset::Mainnet.params();
+        assert_eq!(mainnet.chain_id, None);
+        assert!(!mainnet.devnet);
+        assert_eq!(mainnet.devnet_balance, 0);
+        assert_eq!(mainnet.concentration_check, ConcentrationCheck::Deny);
+    }
+
+    #[test]
+    fn test_override_wins_over_preset() {
+        let overrides = GenesisOverrides {
+            chain_id: Some(777),
+            devnet_balance: Some(1),
+            ..Default::default()
+        };
+
+        let params = GenesisPreset::Devnet.params().with_overrides(&overrides);
+        assert_eq!(params.chain_id, Some(777));
+        assert_eq!(params.devnet_balance, 1);
+        // Values without an override keep the preset default
+        assert!(params.devnet);
+
+        let params = GenesisPreset::Mainnet.params().with_overrides(&overrides);
+        assert_eq!(params.chain_id, Some(777));
+        assert_eq!(params.concentration_check, ConcentrationCheck::Deny);
+
+        // An explicit false turns off a preset default
+        let disabled = GenesisOverrides {
+            devnet: Some(false),
+            ..Default::default()
+        };
+        assert!(
+            !GenesisPreset::Devnet
+                .params()
+                .with_overrides(&disabled)
+                .devnet
+        );
+    }
+
+    #[test]
+    fn test_power_concentration_check() {
+        let balanced = [validator(1, 100), validator(2, 100), validator(3, 100)];
+        assert!(check_power_concentration(&balanced, ConcentrationCheck::Deny).is_ok());
+
+        let concentrated = [validator(1, 300), validator(2, 100), validator(3, 100)];
+        assert!(check_power_concentration(&concentrated, ConcentrationCheck::Warn).is_ok());
+        assert!(check_power_concentration(&concentrated, ConcentrationCheck::Deny).is_err());
+
+        let pair = [
+            validator(1, GENESIS_VALIDATOR_POWER),
+            validator(2, GENESIS_VALIDATOR_POWER),
+        ];
+        let err = check_power_concentration(&pair, ConcentrationCheck::Deny).unwrap_err();
+        assert!(err.to_string().contains("at least 3 validators"), "{err}");
+    }
+}
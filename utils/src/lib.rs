@@ -1,14 +1,20 @@
 use alloy_primitives::Address;
 use clap::{Parser, Subcommand, ValueHint};
 use color_eyre::eyre::Result;
-use genesis::{generate_genesis, make_signers};
+use genesis::{
+    generate_genesis, make_signers, EvmHardfork, GenesisFormat, GenesisOverrides, GenesisPreset,
+};
 use reqwest::Url;
 use spammer::Spammer;
 
 pub mod genesis;
 pub mod modify_config;
 pub mod poa;
+#[cfg(feature = "test-util")]
+pub mod smoke;
 pub mod spammer;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod tx;
 pub mod validator_manager;
 
@@ -25,20 +31,37 @@ impl Cli {
             Commands::Genesis {
                 public_keys_file,
                 poa_owner_address,
+                preset,
                 devnet,
                 devnet_balance,
                 chain_id,
+                hardfork,
+                format,
+                expected_validators,
+                metadata,
                 evm_genesis_output,
                 emerald_genesis_output,
-            } => generate_genesis(
-                public_keys_file,
-                poa_owner_address,
-                devnet,
-                devnet_balance,
-                chain_id,
-                evm_genesis_output,
-                emerald_genesis_output,
-            ),
+                validators_output,
+            } => {
+                let overrides = GenesisOverrides {
+                    chain_id: *chain_id,
+                    devnet: *devnet,
+                    devnet_balance: *devnet_balance,
+                    hardfork: *hardfork,
+                    format: Some(*format),
+                    expected_validators: *expected_validators,
+                    metadata: *metadata,
+                    ..Default::default()
+                };
+                generate_genesis(
+                    public_keys_file,
+                    poa_owner_address,
+                    &preset.params().try_with_overrides(&overrides)?,
+                    evm_genesis_output,
+                    emerald_genesis_output,
+                    validators_output.as_deref(),
+                )
+            }
             Commands::Spam(spam_cmd) => spam_cmd.run().await,
             Commands::Poa(poa_cmd) => poa_cmd.run().await,
             Commands::SpamContract(spam_contract_cmd) => spam_contract_cmd.run().await,
@@ -62,34 +85,69 @@ pub enum Commands {
         #[clap(
             long,
             short = 'a',
-            required_unless_present = "devnet",
-            help = "Address of the Proof-of-Authority owner"
+            help = "Address of the Proof-of-Authority owner (defaults to the first test address with test accounts)"
         )]
         poa_owner_address: Option<String>,
 
+        #[clap(
+            long,
+            value_enum,
+            default_value_t = GenesisPreset::Testnet,
+            help = "Network preset providing the defaults below; mainnet needs at least 3 validators, as they all get the same power"
+        )]
+        preset: GenesisPreset,
+
         #[clap(
             long,
             short = 'c',
-            help = "Chain ID for the genesis file (default: 12345)",
-            default_value_t = 12345
+            help = "Chain ID for the genesis file (default: 12345, none for mainnet)"
         )]
-        chain_id: u64,
+        chain_id: Option<u64>,
 
         #[clap(
             short,
             long,
-            default_value_t = false,
-            help = "Generate test addresses in genesis using mnemonic: 'test test test test test test test test test test test junk'"
+            num_args = 0..=1,
+            default_missing_value = "true",
+            help = "Generate test addresses in genesis using mnemonic: 'test test test test test test test test test test test junk' (default: from the preset; pass false to disable)"
         )]
-        devnet: bool,
+        devnet: Option<bool>,
 
         #[clap(
             long,
             short = 'b',
-            default_value_t = 15_000_u64,
-            help = "Balance for each testnet wallet (default: 15000)"
+            help = "Balance, in ether, for each devnet wallet; requires --devnet (default: 15000)"
+        )]
+        devnet_balance: Option<u64>,
+
+        #[clap(
+            long,
+            value_enum,
+            help = "Latest EVM hardfork active at genesis (default: osaka)"
         )]
-        devnet_balance: u64,
+        hardfork: Option<EvmHardfork>,
+
+        #[clap(
+            long,
+            value_enum,
+            default_value_t = GenesisFormat::Standard,
+            help = "Layout of the generated genesis file"
+        )]
+        format: GenesisFormat,
+
+        #[clap(
+            long,
+            help = "Number of validators the public keys file must hold; guards against truncated input"
+        )]
+        expected_validators: Option<usize>,
+
+        #[clap(
+            long,
+            num_args = 0..=1,
+            default_missing_value = "true",
+            help = "Also write a human-readable genesis.meta.json sidecar describing the validator set (default: from the preset; pass false to disable)"
+        )]
+        metadata: Option<bool>,
 
         #[clap(
             long,
@@ -107,6 +165,13 @@ pub enum Commands {
             help = "Output path for the generated Emerald genesis file"
         )]
         emerald_genesis_output: String,
+
+        #[clap(
+            long,
+            value_hint = ValueHint::FilePath,
+            help = "Also write the genesis validators, sorted by address, to this JSON file"
+        )]
+        validators_output: Option<String>,
     },
 
     /// Spam transactions
@@ -1,11 +1,25 @@
+use std::process::ExitCode;
+
 use clap::Parser;
-use color_eyre::eyre::Result;
+use emerald_utils::validator_manager::ValidatroManagerError;
 use emerald_utils::Cli;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    color_eyre::install()?;
+async fn main() -> ExitCode {
+    if let Err(error) = color_eyre::install() {
+        eprintln!("Error: {error:?}");
+        return ExitCode::FAILURE;
+    }
     tracing_subscriber::fmt::init();
 
-    Cli::parse().run().await
+    match Cli::parse().run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error:?}");
+            // Typed failures get a stable code, anything else the generic one
+            error
+                .downcast_ref::<ValidatroManagerError>()
+                .map_or(ExitCode::FAILURE, ExitCode::from)
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use alloy_network::EthereumWallet;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, Bytes, U256};
 use alloy_provider::ProviderBuilder;
 use alloy_signer::utils::raw_public_key_to_address;
 use alloy_signer_local::PrivateKeySigner;
@@ -10,6 +10,8 @@ use k256::elliptic_curve::sec1::ToEncodedPoint;
 use k256::PublicKey;
 use reqwest::Url;
 
+use crate::validator_manager::Sec1UncompressedPubKey;
+
 pub fn pubkey_parser(validator_pubkey: &str) -> Result<(U256, U256)> {
     let pubkey_bytes = hex::decode(
         validator_pubkey
@@ -116,13 +118,13 @@ pub async fn list_validators(rpc_url: &Url, contract_address: &Address) -> Resul
         println!("Validator #{}:", i + 1);
         println!("  Power: {}", validator.power);
         // validator pubkey in hex
-        let mut pubkey_bytes = Vec::with_capacity(65);
-        pubkey_bytes.push(0x04); // uncompressed prefix
-        pubkey_bytes.extend_from_slice(&validator.validatorKey.x.to_be_bytes::<32>());
-        pubkey_bytes.extend_from_slice(&validator.validatorKey.y.to_be_bytes::<32>());
-        println!("  Pubkey: {}", hex::encode(&pubkey_bytes));
+        let pubkey_bytes = Sec1UncompressedPubKey::from_validator_key(&(
+            validator.validatorKey.x,
+            validator.validatorKey.y,
+        ));
+        println!("  Pubkey: {}", hex::encode(pubkey_bytes.as_bytes()));
         // print validator address 0x
-        let pubkey = PublicKey::from_sec1_bytes(&pubkey_bytes)
+        let pubkey = PublicKey::from_sec1_bytes(pubkey_bytes.as_bytes())
             .map_err(|e| color_eyre::eyre::eyre!("Invalid public key bytes: {}", e))?;
         let address = raw_public_key_to_address(&pubkey.to_encoded_point(false).as_bytes()[1..]);
         println!("Validator address: 0x{address:x}");
@@ -148,15 +150,13 @@ pub async fn add_validator(
 
     // Ensure the public key is in the correct format for the contract
     // Contract accepts: 33 bytes (compressed) or 65 bytes (uncompressed with 0x04 prefix)
-    let validator_public_key_bytes: Vec<u8> = if pubkey_bytes.len() == 64 {
-        // If 64 bytes, add the 0x04 prefix for uncompressed format
-        let mut prefixed = Vec::with_capacity(65);
-        prefixed.push(0x04);
-        prefixed.extend_from_slice(&pubkey_bytes);
-        prefixed
-    } else if pubkey_bytes.len() == 65 || pubkey_bytes.len() == 33 || pubkey_bytes.len() == 20 {
-        // Already in correct format (65 bytes uncompressed, 33 bytes compressed, or 20 bytes address)
-        pubkey_bytes
+    let validator_public_key_bytes: Bytes = if pubkey_bytes.len() == 64 || pubkey_bytes.len() == 65
+    {
+        // Uncompressed keys are checked and sent with the 0x04 prefix
+        Sec1UncompressedPubKey::from_validator_key(&pubkey_parser(hex_str)?).into()
+    } else if pubkey_bytes.len() == 33 || pubkey_bytes.len() == 20 {
+        // Already in correct format (33 bytes compressed or 20 bytes address)
+        pubkey_bytes.into()
     } else {
         return Err(color_eyre::eyre::eyre!(
             "Invalid input length: expected 20 (address), 33 (compressed key), 64, or 65 bytes (uncompressed key), got {}",
@@ -182,7 +182,7 @@ pub async fn add_validator(
     println!("  Power: {power}");
 
     let tx = contract
-        .register(validator_public_key_bytes.into(), power)
+        .register(validator_public_key_bytes, power)
         .send()
         .await
         .context("Failed to send register transaction")?;
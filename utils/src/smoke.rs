@@ -0,0 +1,62 @@
+//! In-process smoke test of a generated genesis, without spawning Anvil or reth.
+
+use alloy_genesis::Genesis;
+use alloy_sol_types::SolCall;
+use color_eyre::eyre::{eyre, Result};
+use revm::context::{Context, TxEnv};
+use revm::context_interface::result::{ExecutionResult, Output};
+use revm::database::{CacheDB, EmptyDB};
+use revm::primitives::TxKind;
+use revm::state::{AccountInfo, Bytecode};
+use revm::{ExecuteEvm, MainBuilder, MainContext};
+
+use crate::validator_manager::{ValidatorManager, GENESIS_VALIDATOR_MANAGER_ACCOUNT};
+
+/// Load the genesis alloc into an in-memory EVM and call `getValidatorCount`
+/// on the validator manager, returning the number of registered validators.
+pub fn smoke_boot(genesis: &Genesis) -> Result<u64> {
+    let mut db = CacheDB::<EmptyDB>::default();
+
+    for (address, account) in &genesis.alloc {
+        let mut info = AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce.unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Some(code) = &account.code {
+            info = info.with_code(Bytecode::new_raw(code.clone()));
+        }
+        db.insert_account_info(*address, info);
+
+        for (slot, value) in account.storage.iter().flatten() {
+            db.insert_account_storage(*address, (*slot).into(), (*value).into())
+                .map_err(|e| eyre!("failed to load storage of {address}: {e}"))?;
+        }
+    }
+
+    let tx = TxEnv::builder()
+        .kind(TxKind::Call(GENESIS_VALIDATOR_MANAGER_ACCOUNT))
+        .data(
+            ValidatorManager::getValidatorCountCall {}
+                .abi_encode()
+                .into(),
+        )
+        .build()
+        .map_err(|e| eyre!("failed to build call: {e:?}"))?;
+
+    let mut evm = Context::mainnet().with_db(db).build_mainnet();
+    let result = evm
+        .transact_one(tx)
+        .map_err(|e| eyre!("getValidatorCount call failed: {e}"))?;
+
+    let ExecutionResult::Success {
+        output: Output::Call(output),
+        ..
+    } = result
+    else {
+        return Err(eyre!("getValidatorCount did not succeed: {result:?}"));
+    };
+
+    let count = ValidatorManager::getValidatorCountCall::abi_decode_returns(&output)?;
+    u64::try_from(count).map_err(|e| eyre!("validator count out of range: {e}"))
+}
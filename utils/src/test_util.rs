@@ -0,0 +1,56 @@
+//! Lightweight fixtures for tests that need validators but not their signing keys
+
+use alloy_primitives::keccak256;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::SecretKey;
+
+use crate::validator_manager::{Sec1UncompressedPubKey, Validator};
+
+/// Derive `count` validators with distinct, valid secp256k1 keys from `seed`,
+/// without going through a mnemonic.
+///
+/// The secret key of validator `i` is `keccak256(seed || i)` (big-endian), rehashed
+/// in the negligible case that it is not a valid scalar. Powers are `1000 * (i + 1)`.
+pub fn deterministic_validators(count: usize, seed: u64) -> Vec<Validator> {
+    (0..count as u64)
+        .map(|index| {
+            let mut preimage = [0u8; 16];
+            preimage[..8].copy_from_slice(&seed.to_be_bytes());
+            preimage[8..].copy_from_slice(&index.to_be_bytes());
+
+            let mut hash = keccak256(preimage);
+            let secret = loop {
+                match SecretKey::from_slice(hash.as_slice()) {
+                    Ok(secret) => break secret,
+                    Err(_) => hash = keccak256(hash),
+                }
+            };
+
+            let encoded = secret.public_key().to_encoded_point(false);
+            let key = Sec1UncompressedPubKey::try_from(encoded.as_bytes())
+                .expect("uncompressed secp256k1 point")
+                .validator_key();
+            Validator::from_public_key(key, 1000 * (index + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_deterministic_validators() {
+        let validators = deterministic_validators(8, 42);
+        assert_eq!(validators, deterministic_validators(8, 42));
+
+        let keys: HashSet<_> = validators.iter().map(|v| v.validator_key).collect();
+        assert_eq!(keys.len(), 8);
+
+        // A prefix of a larger set is the same validators
+        assert_eq!(validators[..3], deterministic_validators(3, 42)[..]);
+        assert_ne!(validators, deterministic_validators(8, 43));
+    }
+}
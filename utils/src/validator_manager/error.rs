@@ -1,8 +1,26 @@
 //! Error types for storage data generation
 
-use alloy_primitives::U256;
+use std::process::ExitCode;
+
+use alloy_primitives::{B256, U256};
 use thiserror::Error;
 
+use crate::validator_manager::types::ValidatorKey;
+
+/// Process exit codes reported for [`Error`], stable across releases so that
+/// scripts can tell failure categories apart. Distinct from the codes of the
+/// node CLI configuration errors.
+pub mod exit_code {
+    /// Validator list that cannot be turned into a genesis validator set
+    pub const GENESIS: u8 = 20;
+    /// ValidatorManager storage that does not decode to a validator set
+    pub const STORAGE: u8 = 21;
+    /// Malformed public key
+    pub const CRYPTO: u8 = 22;
+    /// Filesystem failure
+    pub const IO: u8 = 23;
+}
+
 /// Result type for storage operations
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -15,9 +33,77 @@ pub enum Error {
     #[error("Invalid power for validator ({x:#x}, {y:#x})")]
     InvalidPower { x: U256, y: U256 },
 
+    #[error("Power {power} for validator ({:#x}, {:#x}) is below minimum {min}", .key.0, .key.1)]
+    PowerBelowMinimum {
+        key: ValidatorKey,
+        power: u64,
+        min: u64,
+    },
+
     #[error("Duplicate validator ({x:#x}, {y:#x})")]
     DuplicateValidator { x: U256, y: U256 },
 
     #[error("Total validator power exceeds uint64 max")]
     TotalPowerOverflow,
+
+    #[error("Unknown validator ({x:#x}, {y:#x})")]
+    UnknownValidator { x: U256, y: U256 },
+
+    #[error("Missing storage slot {slot}")]
+    MissingStorageSlot { slot: B256 },
+
+    #[error("Invalid value {value} at storage slot {slot}")]
+    InvalidStorageValue { slot: B256, value: B256 },
+
+    #[error("Invalid SEC1 public key length {len}, expected 65 bytes")]
+    InvalidSec1Length { len: usize },
+
+    #[error("Invalid SEC1 public key prefix {prefix:#04x}, expected 0x04")]
+    InvalidSec1Prefix { prefix: u8 },
+
+    #[error(
+        "Validator address set length {length} does not match {elements} written elements and {validators} validators"
+    )]
+    LengthMismatch {
+        length: U256,
+        elements: u64,
+        validators: usize,
+    },
+
+    #[error("Validator set order lists {ordered} keys but the set holds {validators} validators or disagrees on them")]
+    InconsistentIteration { ordered: usize, validators: usize },
+
+    #[error("Expected {expected} validators but found {got}")]
+    ValidatorCountMismatch { expected: usize, got: usize },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl Error {
+    /// Process exit code for this error, see [`exit_code`]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::EmptyValidatorSet
+            | Self::InvalidPower { .. }
+            | Self::PowerBelowMinimum { .. }
+            | Self::DuplicateValidator { .. }
+            | Self::TotalPowerOverflow
+            | Self::UnknownValidator { .. }
+            | Self::LengthMismatch { .. }
+            | Self::InconsistentIteration { .. }
+            | Self::ValidatorCountMismatch { .. } => exit_code::GENESIS,
+            Self::MissingStorageSlot { .. } | Self::InvalidStorageValue { .. } => {
+                exit_code::STORAGE
+            }
+            Self::InvalidSec1Length { .. } | Self::InvalidSec1Prefix { .. } => exit_code::CRYPTO,
+            Self::Io(_) => exit_code::IO,
+        }
+    }
+}
+
+impl From<&Error> for ExitCode {
+    fn from(error: &Error) -> Self {
+        Self::from(error.exit_code())
+    }
 }
@@ -0,0 +1,33 @@
+//! Spreadsheet-friendly exports of the validator set
+
+use std::io::Write;
+
+use alloy_primitives::Bytes;
+
+use crate::validator_manager::error::Result;
+use crate::validator_manager::types::{Sec1UncompressedPubKey, ValidatorSet};
+
+/// Header row written by [`export_validators_csv`]
+pub const VALIDATORS_CSV_HEADER: &str = "index,key,address,power";
+
+/// Write the validator set as CSV, one row per validator in canonical order.
+///
+/// The key is the uncompressed SEC1 encoding of the public key, left empty for
+/// validators added by address only.
+pub fn export_validators_csv<W: Write>(validator_set: &ValidatorSet, mut writer: W) -> Result<()> {
+    writeln!(writer, "{VALIDATORS_CSV_HEADER}")?;
+
+    for (index, (address, validator)) in validator_set.iter_with_address().enumerate() {
+        let key = match validator_set.address_only(&validator.validator_key) {
+            Some(_) => String::new(),
+            None => Bytes::from(Sec1UncompressedPubKey::from_validator_key(
+                &validator.validator_key,
+            ))
+            .to_string(),
+        };
+        writeln!(writer, "{index},{key},{address},{}", validator.power)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Binary Merkle tree over the validator set
+//!
+//! Leaves are `keccak256(address || x || y || power)` with `power` encoded as a
+//! big-endian `uint64`, taken in the canonical (registration) order of the set.
+//! Inner nodes hash the sorted pair of their children and an odd node is
+//! promoted to the next level unchanged, so proofs can be checked with
+//! OpenZeppelin's `MerkleProof.verify`.
+
+use alloy_primitives::{keccak256, B256};
+
+use crate::validator_manager::storage::{validator_address_from_key, Be32, ValidatorManagerLayout};
+use crate::validator_manager::types::{Validator, ValidatorKey, ValidatorSet};
+
+/// Inclusion proof for a single validator leaf
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Sibling hashes from the leaf level up to the root
+    pub siblings: Vec<B256>,
+}
+
+/// Compute the leaf hash for a validator
+pub fn validator_leaf(validator: &Validator) -> B256 {
+    let (x, y) = validator.validator_key;
+    let address =
+        validator_address_from_key(&validator.validator_key, &ValidatorManagerLayout::default());
+
+    let mut preimage = Vec::with_capacity(20 + 32 + 32 + 8);
+    preimage.extend_from_slice(address.as_slice());
+    preimage.extend_from_slice(&Be32::from_u256(x).0);
+    preimage.extend_from_slice(&Be32::from_u256(y).0);
+    preimage.extend_from_slice(&validator.power.to_be_bytes());
+    keccak256(preimage)
+}
+
+/// Compute the leaf hashes of the validator set in canonical order
+pub fn validator_merkle_leaves(set: &ValidatorSet) -> Vec<B256> {
+    set.get_validators()
+        .into_iter()
+        .map(validator_leaf)
+        .collect()
+}
+
+/// Compute the Merkle root of the validator set, or `B256::ZERO` for an empty set
+pub fn validator_merkle_root(set: &ValidatorSet) -> B256 {
+    let mut level = validator_merkle_leaves(set);
+    if level.is_empty() {
+        return B256::ZERO;
+    }
+
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Build an inclusion proof for the validator with the given key
+///
+/// Returns `None` if the key is not part of the set.
+pub fn validator_merkle_proof(set: &ValidatorSet, key: &ValidatorKey) -> Option<MerkleProof> {
+    let mut index = set.ordered_validator_keys().iter().position(|k| k == key)?;
+    let mut level = validator_merkle_leaves(set);
+    let mut siblings = Vec::new();
+
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            siblings.push(*hash);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    Some(MerkleProof { siblings })
+}
+
+/// Verify that `leaf` is included in the tree with the given `root`
+pub fn verify_validator_proof(root: B256, leaf: B256, proof: &MerkleProof) -> bool {
+    let computed = proof
+        .siblings
+        .iter()
+        .fold(leaf, |acc, sibling| hash_pair(acc, *sibling));
+    computed == root
+}
+
+fn next_level(level: &[B256]) -> Vec<B256> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_pair(*left, *right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two elements"),
+        })
+        .collect()
+}
+
+fn hash_pair(a: B256, b: B256) -> B256 {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    keccak256([first.as_slice(), second.as_slice()].concat())
+}
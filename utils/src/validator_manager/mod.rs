@@ -4,27 +4,56 @@
 //! for the ValidatorSet smart contract based on a given validator list.
 
 pub mod error;
+pub mod export;
+pub mod merkle;
+pub mod oz;
 pub mod storage;
 #[cfg(test)]
 mod tests;
 pub mod types;
 
 use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
 
 use alloy_primitives::{Address, B256, U256};
 pub use emerald_contracts::{ValidatorManager, GENESIS_VALIDATOR_MANAGER_ACCOUNT};
 pub use error::{Error as ValidatroManagerError, Result};
-pub use storage::StorageSlotCalculator;
-pub use types::{Validator, ValidatorKey, ValidatorSet};
+pub use export::export_validators_csv;
+pub use merkle::{
+    validator_merkle_leaves, validator_merkle_proof, validator_merkle_root, verify_validator_proof,
+    MerkleProof,
+};
+pub use oz::{detect_oz_modules, OzModule, OzVersion};
+pub use storage::{
+    check_deployment_slots, check_reentrancy_status, describe_storage,
+    is_validator_manager_initialized, storage_commitment, validator_slots, AddressDerivation, Be32,
+    HashAlgo, JsonLinesSink, SetBacking, StorageSink, StorageSlotCalculator,
+    ValidatorManagerLayout, ValidatorSlots,
+};
+pub use types::{Sec1UncompressedPubKey, Validator, ValidatorKey, ValidatorSet};
 
 use crate::validator_manager::storage::{
-    set_validator_addresses_set, set_validator_entries_mapping,
+    check_addresses_length, read_validator_set, set_validator_addresses_set,
+    set_validator_entries_mapping, REENTRANCY_STATUS_SLOT,
 };
 
+/// Minimum validator power accepted by default, which only rules out zero power
+pub const DEFAULT_MIN_POWER: u64 = 1;
+
 /// Generate storage slots and values for a given validator list
 pub fn generate_storage_data(
     validators: Vec<Validator>,
     owner: Address,
+) -> Result<BTreeMap<B256, B256>> {
+    generate_storage_data_with_min_power(validators, owner, DEFAULT_MIN_POWER)
+}
+
+/// Generate storage slots and values for a given validator list, rejecting
+/// any validator whose power is below `min_power`
+pub fn generate_storage_data_with_min_power(
+    validators: Vec<Validator>,
+    owner: Address,
+    min_power: u64,
 ) -> Result<BTreeMap<B256, B256>> {
     // Validate validators
     if validators.is_empty() {
@@ -36,6 +65,14 @@ pub fn generate_storage_data(
             let (x, y) = validator.validator_key;
             return Err(ValidatroManagerError::InvalidPower { x, y });
         }
+
+        if validator.power < min_power {
+            return Err(ValidatroManagerError::PowerBelowMinimum {
+                key: validator.validator_key,
+                power: validator.power,
+                min: min_power,
+            });
+        }
     }
 
     // Check for duplicate validators by key
@@ -48,7 +85,7 @@ pub fn generate_storage_data(
     }
 
     // Create validator set
-    let mut validator_set = ValidatorSet::default();
+    let mut validator_set = ValidatorSet::with_capacity(validators.len());
     for validator in validators {
         validator_set.add_validator(validator)?;
     }
@@ -62,35 +99,114 @@ pub fn generate_from_validator_set(
     validator_set: &ValidatorSet,
     owner: Address,
 ) -> Result<BTreeMap<B256, B256>> {
+    generate_from_validator_set_with_derivation(validator_set, owner, &AddressDerivation::default())
+}
+
+/// Generate storage data from validator set, for a contract deriving validator
+/// addresses with the given scheme
+pub fn generate_from_validator_set_with_derivation(
+    validator_set: &ValidatorSet,
+    owner: Address,
+    derivation: &AddressDerivation,
+) -> Result<BTreeMap<B256, B256>> {
+    let layout = ValidatorManagerLayout {
+        address_derivation: derivation.clone(),
+        ..Default::default()
+    };
+    generate_from_validator_set_with_layout(validator_set, owner, &layout)
+}
+
+/// Generate storage data from validator set, for a contract with the given layout
+pub fn generate_from_validator_set_with_layout(
+    validator_set: &ValidatorSet,
+    owner: Address,
+    layout: &ValidatorManagerLayout,
+) -> Result<BTreeMap<B256, B256>> {
+    let mut storage = BTreeMap::new();
+    write_storage(&mut storage, validator_set, owner, layout)?;
+
+    check_addresses_length(&storage, validator_set, layout.addresses_slot)?;
+
+    // A validator slot colliding with the guard would leave the contract bricked
+    check_reentrancy_status(&storage, layout.oz_version)?;
+
+    Ok(storage)
+}
+
+/// Stream the storage data of [`generate_from_validator_set_with_layout`] to
+/// `writer` as JSON lines, without holding the whole storage map in memory.
+///
+/// Slots are written in generation order rather than sorted, and the checks
+/// that need to read back the full storage are skipped.
+pub fn stream_storage_data<W: Write>(
+    validator_set: &ValidatorSet,
+    owner: Address,
+    layout: &ValidatorManagerLayout,
+    writer: W,
+) -> Result<W> {
+    let mut sink = JsonLinesSink::new(writer);
+    write_storage(&mut sink, validator_set, owner, layout)?;
+    sink.into_inner()
+}
+
+fn write_storage(
+    storage: &mut impl StorageSink,
+    validator_set: &ValidatorSet,
+    owner: Address,
+    layout: &ValidatorManagerLayout,
+) -> Result<()> {
     // Storage layout for ValidatorManager contract:
     // Slot 0: Ownable._owner (set separately by deployment or genesis tooling)
-    // Slot 1: ReentrancyGuard._status (set to 1)
+    // Slot 1: ReentrancyGuard._status (set to NOT_ENTERED, 1 for supported OZ versions)
     // Slot 2: _validatorAddresses._values (EnumerableSet internal storage)
     // Slot 3: _validatorAddresses._positions
     // Slot 4: _validators mapping(address => ValidatorInfo)
     // Slot 5: _totalPower
+    let derivation = &layout.address_derivation;
 
-    let mut storage = BTreeMap::new();
+    validator_set.check_iteration_consistency()?;
 
     // Ownable owner
-    storage.insert(B256::ZERO, owner.into_word());
-
-    // ReentrancyGuard initial status (_status = 1) at slot 1
-    let status_slot = B256::from(U256::from(1u64).to_be_bytes::<32>());
-    storage.insert(
-        status_slot,
-        B256::from(U256::from(1u64).to_be_bytes::<32>()),
-    );
+    storage.write_slot(B256::ZERO, B256::from(Be32::from_address(owner)))?;
+
+    // ReentrancyGuard initial status (_status = NOT_ENTERED) at slot 1
+    storage.write_slot(
+        REENTRANCY_STATUS_SLOT,
+        B256::from(layout.oz_version.reentrancy_not_entered()),
+    )?;
+
+    set_validator_addresses_set(
+        storage,
+        validator_set,
+        layout.addresses_slot,
+        derivation,
+        layout.set_backing,
+    )?;
+    set_validator_entries_mapping(storage, validator_set, layout.validators_slot, derivation)?;
 
-    set_validator_addresses_set(&mut storage, validator_set, U256::from(2))?; // _validatorAddresses base at slot 2
-    set_validator_entries_mapping(&mut storage, validator_set, U256::from(4))?; // _validators mapping at slot 4
-
-    let total_power_slot = B256::from(U256::from(5u64).to_be_bytes::<32>()); // _totalPower at slot 5
     let total_power = validator_set.total_power()?;
-    storage.insert(
-        total_power_slot,
-        B256::from(U256::from(total_power).to_be_bytes::<32>()),
-    );
+    storage.write_slot(
+        B256::from(Be32::from_u256(layout.total_power_slot)),
+        B256::from(Be32::from_u256(U256::from(total_power))),
+    )
+}
 
-    Ok(storage)
+/// Self-test that the storage written by [`generate_from_validator_set`] holds
+/// the NOT_ENTERED value that a contract built against `version` expects in
+/// `ReentrancyGuard._status`, so that genesis does not brick its guarded functions
+pub fn verify_reentrancy_sentinel(version: OzVersion) -> Result<()> {
+    let mut validator_set = ValidatorSet::with_capacity(1);
+    validator_set.add_validator(Validator::from_public_key(
+        (U256::from(1), U256::from(2)),
+        DEFAULT_MIN_POWER,
+    ))?;
+
+    let storage = generate_from_validator_set(&validator_set, Address::ZERO)?;
+    check_reentrancy_status(&storage, version)
+}
+
+/// Parse the validator set back from ValidatorManager storage, as produced by
+/// [`generate_from_validator_set`]
+pub fn parse_validator_set_from_storage(storage: &BTreeMap<B256, B256>) -> Result<ValidatorSet> {
+    read_validator_set(storage, &ValidatorManagerLayout::default())
 }
@@ -0,0 +1,93 @@
+//! Detection of OpenZeppelin upgradeable modules from their ERC-7201 namespaced storage
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{keccak256, B256, U256};
+
+/// OpenZeppelin upgradeable module keeping its state in an ERC-7201 namespace
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OzModule {
+    Ownable,
+    ReentrancyGuard,
+    Initializable,
+    AccessControl,
+    Pausable,
+}
+
+impl OzModule {
+    /// All known modules, in detection order
+    pub const ALL: [Self; 5] = [
+        Self::Ownable,
+        Self::ReentrancyGuard,
+        Self::Initializable,
+        Self::AccessControl,
+        Self::Pausable,
+    ];
+
+    /// ERC-7201 namespace id of the module storage
+    pub fn namespace(self) -> &'static str {
+        match self {
+            Self::Ownable => "openzeppelin.storage.Ownable",
+            Self::ReentrancyGuard => "openzeppelin.storage.ReentrancyGuard",
+            Self::Initializable => "openzeppelin.storage.Initializable",
+            Self::AccessControl => "openzeppelin.storage.AccessControl",
+            Self::Pausable => "openzeppelin.storage.Pausable",
+        }
+    }
+
+    /// Root slot of the module storage, computed as
+    /// `keccak256(abi.encode(uint256(keccak256(namespace)) - 1)) & ~bytes32(uint256(0xff))`
+    pub fn slot(self) -> B256 {
+        let id = U256::from_be_bytes(keccak256(self.namespace()).0) - U256::from(1);
+        let mut slot = keccak256(id.to_be_bytes::<32>());
+        slot.0[31] = 0;
+        slot
+    }
+}
+
+/// OpenZeppelin contracts release line the validator manager is compiled against,
+/// for the values its modules expect in storage.
+///
+/// `ReentrancyGuard._status` outside of a guarded call (NOT_ENTERED):
+///
+/// | Version | NOT_ENTERED | ENTERED |
+/// |---------|-------------|---------|
+/// | 4.x     | 1           | 2       |
+/// | 5.x     | 1           | 2       |
+///
+/// The contract is currently built against v5.4.0, see `solidity/foundry.lock`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OzVersion {
+    V4,
+    #[default]
+    V5,
+}
+
+impl OzVersion {
+    /// Versions whose storage values are known
+    pub const SUPPORTED: [Self; 2] = [Self::V4, Self::V5];
+
+    /// Value of `ReentrancyGuard._status` outside of a guarded call. Any other
+    /// value written at genesis makes every `nonReentrant` function revert.
+    pub fn reentrancy_not_entered(self) -> U256 {
+        match self {
+            Self::V4 | Self::V5 => U256::from(1),
+        }
+    }
+}
+
+/// Modules whose namespace root slot holds a nonzero value in `storage`.
+///
+/// Only the first field of each namespace is checked: a paused flag left unset
+/// or a namespace holding only mappings, such as AccessControl's roles, is not
+/// detected.
+pub fn detect_oz_modules(storage: &BTreeMap<B256, B256>) -> Vec<OzModule> {
+    OzModule::ALL
+        .into_iter()
+        .filter(|module| {
+            storage
+                .get(&module.slot())
+                .is_some_and(|value| !value.is_zero())
+        })
+        .collect()
+}
@@ -1,11 +1,50 @@
 //! Storage layout and encoding for ValidatorManager contract
 
 use std::collections::BTreeMap;
+use std::io::Write;
 
 use alloy_primitives::{keccak256, Address, B256, U256};
+use sha2::{Digest, Sha256};
 
-use crate::validator_manager::error::Result;
-use crate::validator_manager::types::{ValidatorKey, ValidatorSet};
+use crate::validator_manager::error::{Error, Result};
+use crate::validator_manager::oz::{OzModule, OzVersion};
+use crate::validator_manager::types::{Validator, ValidatorKey, ValidatorSet};
+
+/// A 32-byte EVM word in big-endian order.
+///
+/// Storage slots and values, key limbs and packed fields are all encoded
+/// through this type, so that the byte order is decided in one place.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Be32(pub [u8; 32]);
+
+impl Be32 {
+    pub fn from_u256(value: U256) -> Self {
+        Self(value.to_be_bytes::<32>())
+    }
+
+    pub fn to_u256(self) -> U256 {
+        U256::from_be_bytes(self.0)
+    }
+
+    /// The address left-padded with zeros, as Solidity stores an `address`
+    pub fn from_address(address: Address) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[12..].copy_from_slice(address.as_slice());
+        Self(bytes)
+    }
+}
+
+impl From<B256> for Be32 {
+    fn from(word: B256) -> Self {
+        Self(word.0)
+    }
+}
+
+impl From<Be32> for B256 {
+    fn from(word: Be32) -> Self {
+        B256::new(word.0)
+    }
+}
 
 /// Storage slot calculator for Solidity mappings and arrays
 pub struct StorageSlotCalculator;
@@ -13,63 +52,408 @@ pub struct StorageSlotCalculator;
 impl StorageSlotCalculator {
     /// Calculate storage slot for mapping(key => value) where the key is encoded as bytes32
     pub fn mapping_slot(key: B256, base_slot: U256) -> B256 {
-        let key_hash = keccak256([key.as_slice(), &base_slot.to_be_bytes::<32>()].concat());
+        let key_hash = keccak256([key.as_slice(), &Be32::from_u256(base_slot).0].concat());
         key_hash
     }
 
     /// Calculate storage slot for dynamic array element in `_validatorAddresses._inner._values`
     pub fn array_element_slot(base_slot: U256, index: U256) -> B256 {
-        let array_base = keccak256(base_slot.to_be_bytes::<32>());
-        let array_base_u256 = U256::from_be_slice(array_base.as_slice());
+        let array_base = keccak256(Be32::from_u256(base_slot).0);
+        let array_base_u256 = Be32::from(array_base).to_u256();
         let element_slot = array_base_u256 + index;
-        B256::from(element_slot.to_be_bytes::<32>())
+        B256::from(Be32::from_u256(element_slot))
     }
 
     /// Calculate storage slot for a struct field at the given index (0-based)
     pub fn struct_field_slot(base_slot: B256, field_index: usize) -> B256 {
-        let base = U256::from_be_slice(base_slot.as_slice());
+        let base = Be32::from(base_slot).to_u256();
         let field_slot = base + U256::from(field_index as u64);
-        B256::from(field_slot.to_be_bytes::<32>())
+        B256::from(Be32::from_u256(field_slot))
+    }
+}
+
+/// Destination of the storage slots written by the generator
+pub trait StorageSink {
+    /// Record `value` at `slot`
+    fn write_slot(&mut self, slot: B256, value: B256) -> Result<()>;
+}
+
+impl StorageSink for BTreeMap<B256, B256> {
+    fn write_slot(&mut self, slot: B256, value: B256) -> Result<()> {
+        self.insert(slot, value);
+        Ok(())
+    }
+}
+
+/// Sink writing each slot to `writer` as soon as it is generated, one
+/// `{"slot":"0x..","value":"0x.."}` JSON object per line
+pub struct JsonLinesSink<W> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Flush and return the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> StorageSink for JsonLinesSink<W> {
+    fn write_slot(&mut self, slot: B256, value: B256) -> Result<()> {
+        writeln!(self.writer, r#"{{"slot":"{slot}","value":"{value}"}}"#)?;
+        Ok(())
+    }
+}
+
+/// Scheme used by the contract to derive a validator address from its public key
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AddressDerivation {
+    /// `keccak256(x || y)[12..]`, as in the ValidatorManager contract
+    #[default]
+    Keccak,
+    /// `keccak256(domain || x || y)[12..]`, for contracts that prefix a domain tag
+    KeccakWithDomain(Vec<u8>),
+}
+
+impl AddressDerivation {
+    /// Derive the address of the validator with the given key
+    pub fn derive(&self, key: &ValidatorKey) -> Address {
+        let domain: &[u8] = match self {
+            Self::Keccak => &[],
+            Self::KeccakWithDomain(domain) => domain,
+        };
+
+        let mut preimage = Vec::with_capacity(domain.len() + 64);
+        preimage.extend_from_slice(domain);
+        preimage.extend_from_slice(&Be32::from_u256(key.0).0);
+        preimage.extend_from_slice(&Be32::from_u256(key.1).0);
+        let hash = keccak256(preimage);
+        Address::from_slice(&hash[12..])
+    }
+}
+
+/// Storage backing the `_validatorAddresses` collection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SetBacking {
+    /// OpenZeppelin `EnumerableSet`: the `_values` array plus the `_positions` mapping
+    #[default]
+    EnumerableSet,
+    /// Plain `address[]`, with no `_positions` mapping
+    PlainArray,
+}
+
+/// Location of the validator data in the ValidatorManager storage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatorManagerLayout {
+    /// Base slot of the `_validatorAddresses` EnumerableSet
+    pub addresses_slot: U256,
+    /// Slot of the `_validators` mapping
+    pub validators_slot: U256,
+    /// Slot of `_totalPower`
+    pub total_power_slot: U256,
+    /// Scheme used to derive the validator addresses keying both
+    pub address_derivation: AddressDerivation,
+    /// OpenZeppelin release the contract is built against
+    pub oz_version: OzVersion,
+    /// Whether `_validatorAddresses` carries a `_positions` mapping
+    pub set_backing: SetBacking,
+}
+
+impl Default for ValidatorManagerLayout {
+    fn default() -> Self {
+        Self {
+            addresses_slot: U256::from(2),
+            validators_slot: U256::from(4),
+            total_power_slot: U256::from(5),
+            address_derivation: AddressDerivation::default(),
+            oz_version: OzVersion::default(),
+            set_backing: SetBacking::default(),
+        }
     }
 }
 
-/// Set up the EnumerableSet for validator addresses
+/// Storage slots holding the data of a single validator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidatorSlots {
+    /// Entry of the `_validatorAddresses._positions` mapping
+    pub position: B256,
+    /// First limb of the public key in the `_validators` mapping
+    pub x: B256,
+    /// Second limb of the public key in the `_validators` mapping
+    pub y: B256,
+    /// Voting power in the `_validators` mapping, packed with the fee recipient
+    pub power: B256,
+}
+
+/// Slots occupied by the validator with the given key, so that it can be
+/// verified without reading the whole set
+pub fn validator_slots(key: &ValidatorKey, layout: &ValidatorManagerLayout) -> ValidatorSlots {
+    let address_word = B256::from(Be32::from_address(validator_address_from_key(key, layout)));
+
+    let addresses_base = B256::from(Be32::from_u256(layout.addresses_slot));
+    let positions_base = StorageSlotCalculator::struct_field_slot(addresses_base, 1);
+    let position =
+        StorageSlotCalculator::mapping_slot(address_word, Be32::from(positions_base).to_u256());
+
+    let entry = StorageSlotCalculator::mapping_slot(address_word, layout.validators_slot);
+
+    ValidatorSlots {
+        position,
+        x: entry,
+        y: StorageSlotCalculator::struct_field_slot(entry, 1),
+        power: StorageSlotCalculator::struct_field_slot(entry, 2),
+    }
+}
+
+/// Human-readable names of the ValidatorManager storage slots present in `storage`,
+/// such as `_validators[0x..].power/feeRecipient`. Slots that are not part of the layout are left out.
+pub fn describe_storage(
+    storage: &BTreeMap<B256, B256>,
+    layout: &ValidatorManagerLayout,
+) -> BTreeMap<B256, String> {
+    let word = |slot: u64| B256::from(Be32::from_u256(U256::from(slot)));
+    let mut labels = BTreeMap::new();
+    labels.insert(word(0), "_owner".to_string());
+    labels.insert(word(1), "_status".to_string());
+    labels.insert(
+        B256::from(Be32::from_u256(layout.total_power_slot)),
+        "_totalPower".to_string(),
+    );
+
+    let length_slot = B256::from(Be32::from_u256(layout.addresses_slot));
+    labels.insert(
+        length_slot,
+        "_validatorAddresses._values.length".to_string(),
+    );
+
+    let length = storage
+        .get(&length_slot)
+        .and_then(|value| u64::try_from(Be32::from(*value).to_u256()).ok())
+        .unwrap_or_default();
+
+    let positions_base =
+        Be32::from(StorageSlotCalculator::struct_field_slot(length_slot, 1)).to_u256();
+    for index in 0..length {
+        let element_slot =
+            StorageSlotCalculator::array_element_slot(layout.addresses_slot, U256::from(index));
+        labels.insert(
+            element_slot,
+            format!("_validatorAddresses._values[{index}]"),
+        );
+
+        let Some(element) = storage.get(&element_slot) else {
+            continue;
+        };
+        let address = Address::from_word(*element);
+        labels.insert(
+            StorageSlotCalculator::mapping_slot(*element, positions_base),
+            format!("_validatorAddresses._positions[{address}]"),
+        );
+
+        let entry = StorageSlotCalculator::mapping_slot(*element, layout.validators_slot);
+        for (field_index, field) in ["validatorKey.x", "validatorKey.y", "power/feeRecipient"]
+            .iter()
+            .enumerate()
+        {
+            labels.insert(
+                StorageSlotCalculator::struct_field_slot(entry, field_index),
+                format!("_validators[{address}].{field}"),
+            );
+        }
+    }
+
+    labels.retain(|slot, _| storage.contains_key(slot));
+    labels
+}
+
+/// Digest used for a [`storage_commitment`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashAlgo {
+    #[default]
+    Keccak256,
+    Sha256,
+}
+
+impl HashAlgo {
+    fn digest(self, data: &[u8]) -> B256 {
+        match self {
+            Self::Keccak256 => keccak256(data),
+            Self::Sha256 => B256::from_slice(&Sha256::digest(data)),
+        }
+    }
+}
+
+/// Commitment to a whole storage map: the digest of every `slot || value`
+/// pair concatenated in ascending slot order
+pub fn storage_commitment(storage: &BTreeMap<B256, B256>, hash_algo: HashAlgo) -> B256 {
+    let mut preimage = Vec::with_capacity(storage.len() * 64);
+    for (slot, value) in storage {
+        preimage.extend_from_slice(slot.as_slice());
+        preimage.extend_from_slice(value.as_slice());
+    }
+    hash_algo.digest(&preimage)
+}
+
+/// Set up the EnumerableSet for validator addresses, or only its array of
+/// values when the contract uses a [`SetBacking::PlainArray`]
 pub(crate) fn set_validator_addresses_set(
-    storage: &mut BTreeMap<B256, B256>,
+    storage: &mut impl StorageSink,
     validator_set: &ValidatorSet,
     base_slot: U256,
+    derivation: &AddressDerivation,
+    backing: SetBacking,
 ) -> Result<()> {
-    let base_slot_b256 = B256::from(base_slot.to_be_bytes::<32>());
+    let base_slot_b256 = B256::from(Be32::from_u256(base_slot));
     let validator_addresses: Vec<Address> = validator_set
         .ordered_validator_keys()
         .iter()
-        .map(validator_address_from_key)
+        .map(|key| {
+            validator_set
+                .address_only(key)
+                .unwrap_or_else(|| derivation.derive(key))
+        })
         .collect();
 
     // Slot stores the length of the dynamic array `_inner._values`
     let length_slot = StorageSlotCalculator::struct_field_slot(base_slot_b256, 0);
-    storage.insert(
+    storage.write_slot(
         length_slot,
-        B256::from(U256::from(validator_addresses.len() as u64).to_be_bytes::<32>()),
-    );
+        B256::from(Be32::from_u256(
+            U256::from(validator_addresses.len() as u64),
+        )),
+    )?;
 
     // `_inner._positions` mapping is located at slot + 1
     let positions_base_slot_b256 = StorageSlotCalculator::struct_field_slot(base_slot_b256, 1);
-    let positions_base_slot = U256::from_be_slice(positions_base_slot_b256.as_slice());
+    let positions_base_slot = Be32::from(positions_base_slot_b256).to_u256();
 
     for (index, address) in validator_addresses.iter().enumerate() {
         // Write array element at base + index
         let element_slot =
             StorageSlotCalculator::array_element_slot(base_slot, U256::from(index as u64));
-        storage.insert(element_slot, address.into_word());
+        storage.write_slot(element_slot, B256::from(Be32::from_address(*address)))?;
+
+        if backing == SetBacking::PlainArray {
+            continue;
+        }
 
         // Write mapping entry with 1-based index
-        let position_slot =
-            StorageSlotCalculator::mapping_slot(address.into_word(), positions_base_slot);
-        storage.insert(
-            position_slot,
-            B256::from(U256::from((index as u64) + 1).to_be_bytes::<32>()),
+        let position_slot = StorageSlotCalculator::mapping_slot(
+            B256::from(Be32::from_address(*address)),
+            positions_base_slot,
         );
+        storage.write_slot(
+            position_slot,
+            B256::from(Be32::from_u256(U256::from((index as u64) + 1))),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Slot of `ReentrancyGuard._status`
+pub(crate) const REENTRANCY_STATUS_SLOT: B256 = B256::with_last_byte(1);
+
+/// Check that the `Ownable._owner` and `ReentrancyGuard._status` slots in
+/// `storage` hold what deploying the contract and transferring ownership to
+/// `owner` would leave, so genesis storage can be audited against a regular
+/// deployment
+pub fn check_deployment_slots(storage: &BTreeMap<B256, B256>, owner: Address) -> Result<()> {
+    let expected = [
+        (B256::ZERO, B256::from(Be32::from_address(owner))),
+        (
+            REENTRANCY_STATUS_SLOT,
+            B256::from(OzVersion::default().reentrancy_not_entered()),
+        ),
+    ];
+
+    for (slot, value) in expected {
+        let actual = storage
+            .get(&slot)
+            .ok_or(Error::MissingStorageSlot { slot })?;
+        if *actual != value {
+            return Err(Error::InvalidStorageValue {
+                slot,
+                value: *actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `storage` belongs to a ValidatorManager that has been initialized,
+/// so that nobody can take it over by calling `initialize` first.
+///
+/// For an upgradeable deployment keeping an `Initializable` namespace, its
+/// `_initialized` version must be nonzero with `_initializing` unset, and an
+/// owner must be set in either layout. A constructor-initialized contract, as
+/// written by the genesis generator, needs an owner and a nonzero
+/// `ReentrancyGuard._status`.
+pub fn is_validator_manager_initialized(storage: &BTreeMap<B256, B256>) -> bool {
+    let word = |slot: B256| storage.get(&slot).copied().unwrap_or_default();
+
+    let initializable = word(OzModule::Initializable.slot());
+    if !initializable.is_zero() {
+        // `uint64 _initialized` in the low 8 bytes, followed by `bool _initializing`
+        let initialized = initializable[24..].iter().any(|byte| *byte != 0);
+        let initializing = initializable[23] != 0;
+        let owner_set = !word(OzModule::Ownable.slot()).is_zero() || !word(B256::ZERO).is_zero();
+        return initialized && !initializing && owner_set;
+    }
+
+    !word(B256::ZERO).is_zero() && !word(REENTRANCY_STATUS_SLOT).is_zero()
+}
+
+/// Check that `ReentrancyGuard._status` in `storage` holds the NOT_ENTERED
+/// value of `version`
+pub fn check_reentrancy_status(storage: &BTreeMap<B256, B256>, version: OzVersion) -> Result<()> {
+    let value = storage
+        .get(&REENTRANCY_STATUS_SLOT)
+        .ok_or(Error::MissingStorageSlot {
+            slot: REENTRANCY_STATUS_SLOT,
+        })?;
+    if *value != B256::from(version.reentrancy_not_entered()) {
+        return Err(Error::InvalidStorageValue {
+            slot: REENTRANCY_STATUS_SLOT,
+            value: *value,
+        });
+    }
+    Ok(())
+}
+
+/// Check that the length of the `_validatorAddresses` array in `storage` matches
+/// both the number of array elements written and the size of the validator set
+pub(crate) fn check_addresses_length(
+    storage: &BTreeMap<B256, B256>,
+    validator_set: &ValidatorSet,
+    base_slot: U256,
+) -> Result<()> {
+    let length_slot = B256::from(Be32::from_u256(base_slot));
+    let length = storage
+        .get(&length_slot)
+        .map(|value| Be32::from(*value).to_u256())
+        .ok_or(Error::MissingStorageSlot { slot: length_slot })?;
+
+    let elements = (0u64..)
+        .take_while(|index| {
+            let slot = StorageSlotCalculator::array_element_slot(base_slot, U256::from(*index));
+            storage.contains_key(&slot)
+        })
+        .count() as u64;
+
+    let validators = validator_set.count();
+    if length != U256::from(elements) || length != U256::from(validators) {
+        return Err(Error::LengthMismatch {
+            length,
+            elements,
+            validators,
+        });
     }
 
     Ok(())
@@ -77,42 +461,124 @@ pub(crate) fn set_validator_addresses_set(
 
 /// Set up the validators mapping
 pub(crate) fn set_validator_entries_mapping(
-    storage: &mut BTreeMap<B256, B256>,
+    storage: &mut impl StorageSink,
     validator_set: &ValidatorSet,
     base_slot: U256,
+    derivation: &AddressDerivation,
 ) -> Result<()> {
     for validator in validator_set.get_validators() {
-        let address = validator_address_from_key(&validator.validator_key);
-        let address_word = address.into_word();
+        let address_only = validator_set.address_only(&validator.validator_key);
+        let address = address_only.unwrap_or_else(|| derivation.derive(&validator.validator_key));
+        let address_word = B256::from(Be32::from_address(address));
         let validator_slot = StorageSlotCalculator::mapping_slot(address_word, base_slot);
 
-        let mut slot_index = U256::from_be_slice(validator_slot.as_slice());
+        let mut slot_index = Be32::from(validator_slot).to_u256();
         let (x_limb, y_limb) = validator.validator_key;
 
-        // Store first limb
-        storage.insert(validator_slot, B256::from(x_limb.to_be_bytes::<32>()));
+        // Key limbs are left zero when only the address is known
+        if address_only.is_none() {
+            // Store first limb
+            storage.write_slot(validator_slot, B256::from(Be32::from_u256(x_limb)))?;
 
-        // Store second limb
-        slot_index += U256::from(1u64);
-        let second_slot = B256::from(slot_index.to_be_bytes::<32>());
-        storage.insert(second_slot, B256::from(y_limb.to_be_bytes::<32>()));
+            // Store second limb
+            let second_slot = B256::from(Be32::from_u256(slot_index + U256::from(1u64)));
+            storage.write_slot(second_slot, B256::from(Be32::from_u256(y_limb)))?;
+        }
 
-        // Store power as uint64 in third slot
-        slot_index += U256::from(1u64);
-        let power_slot = B256::from(slot_index.to_be_bytes::<32>());
-        storage.insert(
+        // Store power as uint64 in third slot, packed with the fee recipient
+        slot_index += U256::from(2u64);
+        let power_slot = B256::from(Be32::from_u256(slot_index));
+        let fee_recipient = validator.fee_recipient.unwrap_or(address);
+        storage.write_slot(
             power_slot,
-            B256::from(U256::from(validator.power).to_be_bytes::<32>()),
-        );
+            pack_power_and_fee_recipient(validator.power, fee_recipient),
+        )?;
     }
 
     Ok(())
 }
 
-fn validator_address_from_key(key: &ValidatorKey) -> Address {
-    let mut raw = [0u8; 64];
-    raw[..32].copy_from_slice(&key.0.to_be_bytes::<32>());
-    raw[32..].copy_from_slice(&key.1.to_be_bytes::<32>());
-    let hash = keccak256(raw);
-    Address::from_slice(&hash[12..])
+/// Third word of a `_validators` entry: `uint64 power` in the low 8 bytes and
+/// `address feeRecipient` packed right above it
+pub(crate) fn pack_power_and_fee_recipient(power: u64, fee_recipient: Address) -> B256 {
+    let word = U256::from(power) | (Be32::from_address(fee_recipient).to_u256() << 64);
+    B256::from(Be32::from_u256(word))
+}
+
+/// Reverse of [`pack_power_and_fee_recipient`], `None` if the unused high bytes are set
+fn unpack_power_and_fee_recipient(word: U256) -> Option<(u64, Address)> {
+    let bytes = Be32::from_u256(word).0;
+    if bytes[..4].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    let power = u64::from_be_bytes(bytes[24..].try_into().ok()?);
+    Some((power, Address::from_slice(&bytes[4..24])))
+}
+
+/// Rebuild the validator set from the storage of a ValidatorManager contract,
+/// reversing [`set_validator_addresses_set`] and [`set_validator_entries_mapping`]
+pub(crate) fn read_validator_set(
+    storage: &BTreeMap<B256, B256>,
+    layout: &ValidatorManagerLayout,
+) -> Result<ValidatorSet> {
+    let addresses_base_slot = layout.addresses_slot;
+    let entries_base_slot = layout.validators_slot;
+
+    let read = |slot: B256| -> Result<U256> {
+        storage
+            .get(&slot)
+            .map(|value| Be32::from(*value).to_u256())
+            .ok_or(Error::MissingStorageSlot { slot })
+    };
+    let invalid = |slot: B256| Error::InvalidStorageValue {
+        slot,
+        value: storage.get(&slot).copied().unwrap_or_default(),
+    };
+
+    let length_slot = B256::from(Be32::from_u256(addresses_base_slot));
+    let length: u64 = read(length_slot)?
+        .try_into()
+        .map_err(|_| invalid(length_slot))?;
+
+    let mut validator_set = ValidatorSet::default();
+    for index in 0..length {
+        let element_slot =
+            StorageSlotCalculator::array_element_slot(addresses_base_slot, U256::from(index));
+        let address = Address::from_word(
+            *storage
+                .get(&element_slot)
+                .ok_or(Error::MissingStorageSlot { slot: element_slot })?,
+        );
+
+        let entry_slot = StorageSlotCalculator::mapping_slot(
+            B256::from(Be32::from_address(address)),
+            entries_base_slot,
+        );
+        let key = (
+            read(entry_slot)?,
+            read(StorageSlotCalculator::struct_field_slot(entry_slot, 1))?,
+        );
+        if validator_address_from_key(&key, layout) != address {
+            return Err(invalid(element_slot));
+        }
+
+        let power_slot = StorageSlotCalculator::struct_field_slot(entry_slot, 2);
+        let (power, fee_recipient) =
+            unpack_power_and_fee_recipient(read(power_slot)?).ok_or_else(|| invalid(power_slot))?;
+
+        let mut validator = Validator::from_public_key(key, power);
+        validator.fee_recipient = (fee_recipient != address).then_some(fee_recipient);
+        validator_set.add_validator(validator)?;
+    }
+
+    Ok(validator_set)
+}
+
+/// Address keying the validator with the given key in a contract with the given
+/// layout, prefixed by the layout's domain tag if any
+pub(crate) fn validator_address_from_key(
+    key: &ValidatorKey,
+    layout: &ValidatorManagerLayout,
+) -> Address {
+    layout.address_derivation.derive(key)
 }
@@ -1,16 +1,33 @@
 use core::str::FromStr;
+use std::collections::BTreeMap;
 
 use alloy_network::EthereumWallet;
 use alloy_node_bindings::anvil::Anvil;
-use alloy_primitives::{address, Address, U256};
+use alloy_primitives::{address, b256, Address, Bytes, B256, U256};
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_signer_local::coins_bip39::English;
 use alloy_signer_local::{MnemonicBuilder, PrivateKeySigner};
 use color_eyre::eyre;
+use proptest::prelude::*;
 use reqwest::Url;
 use tracing::debug;
 
-use super::{generate_storage_data, Validator};
+use super::merkle::validator_leaf;
+use super::storage::{
+    check_addresses_length, pack_power_and_fee_recipient, read_validator_set,
+    validator_address_from_key,
+};
+use super::{
+    check_deployment_slots, check_reentrancy_status, detect_oz_modules, export_validators_csv,
+    generate_from_validator_set, generate_from_validator_set_with_derivation,
+    generate_from_validator_set_with_layout, generate_storage_data,
+    generate_storage_data_with_min_power, is_validator_manager_initialized,
+    parse_validator_set_from_storage, storage_commitment, stream_storage_data,
+    validator_merkle_proof, validator_merkle_root, validator_slots, verify_reentrancy_sentinel,
+    verify_validator_proof, AddressDerivation, Be32, HashAlgo, JsonLinesSink, OzModule, OzVersion,
+    Sec1UncompressedPubKey, SetBacking, StorageSink, StorageSlotCalculator, Validator,
+    ValidatorKey, ValidatorManagerLayout, ValidatorSet, ValidatroManagerError,
+};
 use crate::validator_manager::ValidatorManager;
 
 /// Generate validators from "test test ... junk" mnemonic using sequential derivation paths.
@@ -55,6 +72,493 @@ fn generate_validators_from_mnemonic(count: usize) -> eyre::Result<Vec<Validator
     Ok(derived)
 }
 
+fn validator_set_from(validators: &[Validator]) -> eyre::Result<ValidatorSet> {
+    let mut set = ValidatorSet::default();
+    for validator in validators {
+        set.add_validator(validator.clone())?;
+    }
+    Ok(set)
+}
+
+#[test]
+fn test_validator_merkle_root_is_deterministic() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(5)?;
+
+    let root = validator_merkle_root(&validator_set_from(&validators)?);
+    assert_eq!(
+        root,
+        validator_merkle_root(&validator_set_from(&validators)?)
+    );
+
+    let mut changed = validators.clone();
+    changed[2].power += 1;
+    assert_ne!(root, validator_merkle_root(&validator_set_from(&changed)?));
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_merkle_proof_verifies_member() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(5)?;
+    let set = validator_set_from(&validators)?;
+    let root = validator_merkle_root(&set);
+
+    // Index 4 is the odd leaf promoted without a sibling on the first level
+    for member in [&validators[1], &validators[4]] {
+        let proof = validator_merkle_proof(&set, &member.validator_key)
+            .expect("member should have a proof");
+        assert!(verify_validator_proof(root, validator_leaf(member), &proof));
+        assert!(!verify_validator_proof(
+            root,
+            validator_leaf(&validators[0]),
+            &proof
+        ));
+    }
+
+    let outsider = generate_validators_from_mnemonic(6)?.remove(5);
+    assert!(validator_merkle_proof(&set, &outsider.validator_key).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_set_power_updates_storage() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let mut set = validator_set_from(&validators)?;
+    let key = validators[1].validator_key;
+
+    set.set_power(&key, 5000)?;
+    assert_eq!(set.total_power()?, 1000 + 5000 + 3000);
+
+    let storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    // Power is the third field of the `_validators` entry (mapping at slot 4)
+    let address = validator_address_from_key(&key, &ValidatorManagerLayout::default());
+    let entry_slot = StorageSlotCalculator::mapping_slot(address.into_word(), U256::from(4));
+    let power_slot = StorageSlotCalculator::struct_field_slot(entry_slot, 2);
+    assert_eq!(
+        storage[&power_slot],
+        pack_power_and_fee_recipient(5000, address)
+    );
+
+    let total_power_slot = B256::from(Be32::from_u256(U256::from(5u64)));
+    assert_eq!(
+        storage[&total_power_slot],
+        B256::from(Be32::from_u256(U256::from(9000u64)))
+    );
+
+    assert!(matches!(
+        set.set_power(&key, 0),
+        Err(ValidatroManagerError::InvalidPower { .. })
+    ));
+    assert_eq!(set.total_power()?, 9000);
+
+    Ok(())
+}
+
+#[test]
+fn test_min_power_floor() -> eyre::Result<()> {
+    let mut validators = generate_validators_from_mnemonic(2)?;
+
+    validators[1].power = 50;
+    let err = generate_storage_data_with_min_power(validators.clone(), TEST_OWNER_ADDRESS, 100)
+        .expect_err("power below minimum should be rejected");
+    assert!(matches!(
+        err,
+        ValidatroManagerError::PowerBelowMinimum {
+            key,
+            power: 50,
+            min: 100,
+        } if key == validators[1].validator_key
+    ));
+
+    validators[1].power = 100;
+    generate_storage_data_with_min_power(validators, TEST_OWNER_ADDRESS, 100)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_address_derivation() -> eyre::Result<()> {
+    // Key of Anvil test account #0, the first one derived from the test mnemonic
+    let validators = generate_validators_from_mnemonic(1)?;
+    let key = validators[0].validator_key;
+
+    let default = AddressDerivation::default();
+    assert_eq!(
+        default.derive(&key),
+        address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+    );
+    assert_eq!(
+        default.derive(&key),
+        validator_address_from_key(&key, &ValidatorManagerLayout::default())
+    );
+
+    let custom = AddressDerivation::KeccakWithDomain(b"emerald".to_vec());
+    assert_ne!(custom.derive(&key), default.derive(&key));
+
+    // The generator keys the validator entries by the derived address
+    let set = validator_set_from(&validators)?;
+    let storage = generate_from_validator_set_with_derivation(&set, TEST_OWNER_ADDRESS, &custom)?;
+    let entry_slot =
+        StorageSlotCalculator::mapping_slot(custom.derive(&key).into_word(), U256::from(4));
+    assert_eq!(storage[&entry_slot], B256::from(Be32::from_u256(key.0)));
+    assert_ne!(
+        storage,
+        generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_address_domain_separation() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(1)?;
+    let key = validators[0].validator_key;
+    let with_domain = |domain: &[u8]| ValidatorManagerLayout {
+        address_derivation: AddressDerivation::KeccakWithDomain(domain.to_vec()),
+        ..Default::default()
+    };
+
+    // An empty domain is the bare derivation used by today's contract
+    assert_eq!(
+        validator_address_from_key(&key, &with_domain(b"")),
+        address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+    );
+
+    // Pinned so that changes to the preimage encoding are caught
+    assert_eq!(
+        validator_address_from_key(&key, &with_domain(b"emerald")),
+        address!("0x2f0f21cbe82dcb3be63eb6e1435debfc67114a4a")
+    );
+
+    // Storage generated for a domain-separated contract reads back with its layout
+    let layout = with_domain(b"emerald");
+    let set = validator_set_from(&validators)?;
+    let storage = generate_from_validator_set_with_derivation(
+        &set,
+        TEST_OWNER_ADDRESS,
+        &layout.address_derivation,
+    )?;
+    assert_eq!(
+        read_validator_set(&storage, &layout)?.get_validators(),
+        set.get_validators()
+    );
+    assert!(parse_validator_set_from_storage(&storage).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_slots_match_generator() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+    let storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    let validator = &validators[1];
+    let slots = validator_slots(&validator.validator_key, &ValidatorManagerLayout::default());
+
+    // 1-based index in the EnumerableSet
+    assert_eq!(
+        storage[&slots.position],
+        B256::from(Be32::from_u256(U256::from(2u64)))
+    );
+    assert_eq!(
+        storage[&slots.x],
+        B256::from(Be32::from_u256(validator.validator_key.0))
+    );
+    assert_eq!(
+        storage[&slots.y],
+        B256::from(Be32::from_u256(validator.validator_key.1))
+    );
+    assert_eq!(
+        storage[&slots.power],
+        pack_power_and_fee_recipient(validator.power, validator.address())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_plain_array_backing_omits_positions() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+    let enumerable = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    let layout = ValidatorManagerLayout {
+        set_backing: SetBacking::PlainArray,
+        ..Default::default()
+    };
+    let plain = generate_from_validator_set_with_layout(&set, TEST_OWNER_ADDRESS, &layout)?;
+
+    // Only the positions slots differ
+    let mut expected = enumerable.clone();
+    for validator in &validators {
+        let slots = validator_slots(&validator.validator_key, &layout);
+        assert!(enumerable.contains_key(&slots.position));
+        assert!(!plain.contains_key(&slots.position));
+        expected.remove(&slots.position);
+    }
+    assert_eq!(plain, expected);
+
+    // Array length and elements are kept
+    let length_slot = B256::from(Be32::from_u256(layout.addresses_slot));
+    assert_eq!(
+        plain[&length_slot],
+        B256::from(Be32::from_u256(U256::from(validators.len())))
+    );
+    for index in 0..validators.len() {
+        let element_slot =
+            StorageSlotCalculator::array_element_slot(layout.addresses_slot, U256::from(index));
+        assert_eq!(plain[&element_slot], enumerable[&element_slot]);
+    }
+    assert_eq!(
+        read_validator_set(&plain, &layout)?.get_validators(),
+        set.get_validators()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_total_power_slot_follows_layout() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+    let default = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    let layout = ValidatorManagerLayout {
+        total_power_slot: U256::from(7),
+        ..Default::default()
+    };
+    let moved = generate_from_validator_set_with_layout(&set, TEST_OWNER_ADDRESS, &layout)?;
+
+    let default_slot = B256::from(Be32::from_u256(U256::from(5)));
+    let moved_slot = B256::from(Be32::from_u256(layout.total_power_slot));
+    assert!(!moved.contains_key(&default_slot));
+    assert_eq!(moved[&moved_slot], default[&default_slot]);
+
+    Ok(())
+}
+
+#[test]
+fn test_streamed_storage_matches_batch() -> eyre::Result<()> {
+    let validators: Vec<Validator> = (1..=1000u64)
+        .map(|i| Validator::from_public_key((U256::from(i), U256::from(i + 1000)), i))
+        .collect();
+    let set = validator_set_from(&validators)?;
+    let batch = generate_storage_data(validators, TEST_OWNER_ADDRESS)?;
+
+    let streamed = stream_storage_data(
+        &set,
+        TEST_OWNER_ADDRESS,
+        &ValidatorManagerLayout::default(),
+        Vec::new(),
+    )?;
+    let streamed = String::from_utf8(streamed)?;
+
+    // Same slots, rendered the same way, once sorted
+    let mut expected = JsonLinesSink::new(Vec::new());
+    for (slot, value) in &batch {
+        expected.write_slot(*slot, *value)?;
+    }
+    let expected = String::from_utf8(expected.into_inner()?)?;
+
+    let mut lines: Vec<&str> = streamed.lines().collect();
+    lines.sort_unstable();
+    assert_eq!(lines.len(), batch.len());
+    assert_eq!(lines.join("\n") + "\n", expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_fee_recipient_storage() -> eyre::Result<()> {
+    let fee_recipient = address!("0xfee0000000000000000000000000000000000fee");
+    let mut validators = generate_validators_from_mnemonic(2)?;
+    validators[1] = validators[1].clone().with_fee_recipient(fee_recipient);
+    let set = validator_set_from(&validators)?;
+    let layout = ValidatorManagerLayout::default();
+    let storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    // `uint64 power` in the low 8 bytes, `address feeRecipient` in the next 20
+    let power_word =
+        |validator: &Validator| storage[&validator_slots(&validator.validator_key, &layout).power];
+    let mut expected = [0u8; 32];
+    expected[4..24].copy_from_slice(fee_recipient.as_slice());
+    expected[24..].copy_from_slice(&validators[1].power.to_be_bytes());
+    assert_eq!(power_word(&validators[1]), B256::from(expected));
+
+    // Defaults to the validator's own address
+    let mut expected = [0u8; 32];
+    expected[4..24].copy_from_slice(validators[0].address().as_slice());
+    expected[24..].copy_from_slice(&validators[0].power.to_be_bytes());
+    assert_eq!(power_word(&validators[0]), B256::from(expected));
+
+    let parsed = parse_validator_set_from_storage(&storage)?;
+    assert_eq!(parsed.get_validators(), set.get_validators());
+    assert_eq!(
+        parsed
+            .get_validators()
+            .iter()
+            .map(|validator| validator.fee_recipient)
+            .filter(Option::is_some)
+            .collect::<Vec<_>>(),
+        vec![Some(fee_recipient)]
+    );
+
+    let info: ValidatorManager::ValidatorInfo = validators[0].clone().into();
+    assert_eq!(info.feeRecipient, validators[0].address());
+    assert_eq!(Validator::from(info), validators[0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_address_only_validator_set() -> eyre::Result<()> {
+    let entries = [
+        (address!("0x1000000000000000000000000000000000000001"), 10),
+        (address!("0x2000000000000000000000000000000000000002"), 20),
+        (address!("0x3000000000000000000000000000000000000003"), 30),
+    ];
+    let set = ValidatorSet::from_addresses(&entries)?;
+    let storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+
+    let word = |value: u64| B256::from(Be32::from_u256(U256::from(value)));
+
+    // EnumerableSet length, values and 1-based positions
+    assert_eq!(storage[&word(2)], word(3));
+    let positions_slot = U256::from(3);
+    for (index, (address, power)) in entries.iter().enumerate() {
+        let element_slot =
+            StorageSlotCalculator::array_element_slot(U256::from(2), U256::from(index));
+        assert_eq!(storage[&element_slot], address.into_word());
+
+        let position_slot =
+            StorageSlotCalculator::mapping_slot(address.into_word(), positions_slot);
+        assert_eq!(storage[&position_slot], word(index as u64 + 1));
+
+        // Key limbs stay zero, power and fee recipient are written
+        let entry_slot = StorageSlotCalculator::mapping_slot(address.into_word(), U256::from(4));
+        assert!(!storage.contains_key(&entry_slot));
+        assert!(!storage.contains_key(&StorageSlotCalculator::struct_field_slot(entry_slot, 1)));
+        assert_eq!(
+            storage[&StorageSlotCalculator::struct_field_slot(entry_slot, 2)],
+            pack_power_and_fee_recipient(*power, *address)
+        );
+    }
+
+    // _totalPower
+    assert_eq!(storage[&word(5)], word(60));
+
+    assert!(matches!(
+        ValidatorSet::from_addresses(&[entries[0], entries[0]]),
+        Err(ValidatroManagerError::DuplicateValidator { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_set_page() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(5)?;
+    let set = validator_set_from(&validators)?;
+
+    let keys = |page: Vec<&Validator>| -> Vec<ValidatorKey> {
+        page.into_iter().map(|v| v.validator_key).collect()
+    };
+    let expected = |range: core::ops::Range<usize>| -> Vec<ValidatorKey> {
+        validators[range].iter().map(|v| v.validator_key).collect()
+    };
+
+    // Full page
+    assert_eq!(keys(set.page(0, 2)), expected(0..2));
+    assert_eq!(keys(set.page(2, 2)), expected(2..4));
+
+    // Partial last page
+    assert_eq!(keys(set.page(4, 2)), expected(4..5));
+
+    // Out-of-range offset
+    assert!(set.page(5, 2).is_empty());
+    assert!(set.page(usize::MAX, 2).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_sec1_uncompressed_pubkey_roundtrip() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(1)?;
+    let key = validators[0].validator_key;
+
+    let encoded = Sec1UncompressedPubKey::from_validator_key(&key);
+    assert_eq!(encoded.as_bytes()[0], 0x04);
+
+    let decoded = Sec1UncompressedPubKey::try_from(&encoded.as_bytes()[..])?;
+    assert_eq!(decoded.validator_key(), key);
+
+    let mut bad_prefix = *encoded.as_bytes();
+    bad_prefix[0] = 0x02;
+    assert!(matches!(
+        Sec1UncompressedPubKey::try_from(&bad_prefix[..]),
+        Err(ValidatroManagerError::InvalidSec1Prefix { prefix: 0x02 })
+    ));
+    assert!(matches!(
+        Sec1UncompressedPubKey::try_from(&encoded.as_bytes()[1..]),
+        Err(ValidatroManagerError::InvalidSec1Length { len: 64 })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_storage_commitment_hash_algo() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let storage = generate_storage_data(validators, TEST_OWNER_ADDRESS)?;
+
+    let keccak = storage_commitment(&storage, HashAlgo::Keccak256);
+    let sha256 = storage_commitment(&storage, HashAlgo::Sha256);
+    assert_ne!(keccak, sha256);
+
+    assert_eq!(storage_commitment(&storage, HashAlgo::default()), keccak);
+    assert_eq!(storage_commitment(&storage, HashAlgo::Sha256), sha256);
+
+    Ok(())
+}
+
+/// Random validator sets with distinct keys, and powers bounded so that the
+/// total of 500 validators cannot overflow
+fn arb_validators() -> impl Strategy<Value = Vec<Validator>> {
+    let key = (any::<[u8; 32]>(), any::<[u8; 32]>())
+        .prop_map(|(x, y)| (U256::from_be_bytes(x), U256::from_be_bytes(y)));
+    let power = 1..=u64::MAX / 500;
+    prop::collection::hash_map(key, power, 1..=500).prop_map(|validators| {
+        validators
+            .into_iter()
+            .map(|(key, power)| Validator::from_public_key(key, power))
+            .collect()
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_storage_roundtrip_random_sets(validators in arb_validators()) {
+        let set = validator_set_from(&validators).unwrap();
+        let storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS).unwrap();
+        let parsed = parse_validator_set_from_storage(&storage).unwrap();
+
+        prop_assert_eq!(parsed.get_validators(), set.get_validators());
+
+        let total_power: u64 = validators.iter().map(|v| v.power).sum();
+        let total_power_slot = B256::from(Be32::from_u256(U256::from(5u64)));
+        prop_assert_eq!(
+            storage[&total_power_slot],
+            B256::from(Be32::from_u256(U256::from(total_power)))
+        );
+        prop_assert_eq!(parsed.total_power().unwrap(), total_power);
+    }
+}
+
 /// Deploy ValidatorManager contract on Anvil and compare storage values
 ///
 /// This test attempts to deploy a ValidatorManager contract on a local Anvil node
@@ -104,7 +608,7 @@ async fn test_anvil_storage_comparison() -> eyre::Result<()> {
             .get_storage_at(contract_address, (*slot).into())
             .await?;
         assert_eq!(
-            actual_value.to_be_bytes::<32>(),
+            Be32::from_u256(actual_value).0,
             (*expected_value),
             "Storage mismatch at slot {slot}",
         );
@@ -157,8 +661,8 @@ async fn deploy_and_register_validators(
         // Encode the public key as uncompressed format (65 bytes: 0x04 + x + y)
         let mut pubkey_bytes = Vec::with_capacity(65);
         pubkey_bytes.push(0x04);
-        pubkey_bytes.extend_from_slice(&info.validatorKey.x.to_be_bytes::<32>());
-        pubkey_bytes.extend_from_slice(&info.validatorKey.y.to_be_bytes::<32>());
+        pubkey_bytes.extend_from_slice(&Be32::from_u256(info.validatorKey.x).0);
+        pubkey_bytes.extend_from_slice(&Be32::from_u256(info.validatorKey.y).0);
 
         let pending_tx = owner_contract
             .register(pubkey_bytes.into(), info.power)
@@ -181,3 +685,431 @@ async fn deploy_and_register_validators(
 
     Ok(contract_address)
 }
+
+#[test]
+fn test_export_validators_csv() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+
+    let mut csv = Vec::new();
+    export_validators_csv(&set, &mut csv)?;
+    let csv = String::from_utf8(csv)?;
+    let mut lines = csv.lines();
+
+    assert_eq!(lines.next(), Some("index,key,address,power"));
+    for (index, validator) in validators.iter().enumerate() {
+        let row = lines.next().expect("one row per validator");
+        let fields: Vec<&str> = row.split(',').collect();
+        let key = Sec1UncompressedPubKey::from_validator_key(&validator.validator_key);
+
+        assert_eq!(fields[0], index.to_string());
+        assert_eq!(fields[1], Bytes::from(key).to_string());
+        assert_eq!(Address::from_str(fields[2])?, validator.address());
+        assert_eq!(fields[3], validator.power.to_string());
+    }
+    assert_eq!(lines.next(), None);
+
+    // First row is Anvil test account #0
+    assert!(csv.contains(",0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266,1000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_addresses_length_mismatch_is_detected() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+    let layout = ValidatorManagerLayout::default();
+
+    let mut storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+    check_addresses_length(&storage, &set, layout.addresses_slot)?;
+
+    // A generator bug writing the wrong length desyncs the array from the mapping
+    let length_slot = B256::from(Be32::from_u256(layout.addresses_slot));
+    storage.insert(length_slot, B256::from(Be32::from_u256(U256::from(4u64))));
+
+    let err = check_addresses_length(&storage, &set, layout.addresses_slot).unwrap_err();
+    assert!(matches!(
+        err,
+        ValidatroManagerError::LengthMismatch {
+            elements: 3,
+            validators: 3,
+            ..
+        }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_reentrancy_sentinel_per_oz_version() -> eyre::Result<()> {
+    assert_eq!(OzVersion::V4.reentrancy_not_entered(), U256::from(1));
+    assert_eq!(OzVersion::V5.reentrancy_not_entered(), U256::from(1));
+    for version in OzVersion::SUPPORTED {
+        verify_reentrancy_sentinel(version)?;
+    }
+
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+    let mut storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+    check_reentrancy_status(&storage, OzVersion::default())?;
+
+    // ENTERED, or any other value, is rejected
+    let status_slot = B256::with_last_byte(1);
+    storage.insert(status_slot, B256::with_last_byte(2));
+    assert!(matches!(
+        check_reentrancy_status(&storage, OzVersion::V5).unwrap_err(),
+        ValidatroManagerError::InvalidStorageValue { slot, .. } if slot == status_slot
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_deployment_slots_are_checked() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let set = validator_set_from(&validators)?;
+
+    let mut storage = generate_from_validator_set(&set, TEST_OWNER_ADDRESS)?;
+    check_deployment_slots(&storage, TEST_OWNER_ADDRESS)?;
+
+    let other_owner = Address::repeat_byte(0x42);
+    assert!(matches!(
+        check_deployment_slots(&storage, other_owner).unwrap_err(),
+        ValidatroManagerError::InvalidStorageValue { slot, .. } if slot == B256::ZERO
+    ));
+
+    // A guard left entered would make every nonReentrant call revert
+    let status_slot = B256::from(Be32::from_u256(U256::from(1u64)));
+    storage.insert(status_slot, B256::from(Be32::from_u256(U256::from(2u64))));
+    assert!(matches!(
+        check_deployment_slots(&storage, TEST_OWNER_ADDRESS).unwrap_err(),
+        ValidatroManagerError::InvalidStorageValue { slot, .. } if slot == status_slot
+    ));
+
+    storage.remove(&status_slot);
+    assert!(matches!(
+        check_deployment_slots(&storage, TEST_OWNER_ADDRESS).unwrap_err(),
+        ValidatroManagerError::MissingStorageSlot { slot } if slot == status_slot
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_manager_initialized() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let mut storage =
+        generate_from_validator_set(&validator_set_from(&validators)?, TEST_OWNER_ADDRESS)?;
+    assert!(is_validator_manager_initialized(&storage));
+
+    assert!(!is_validator_manager_initialized(&BTreeMap::new()));
+    let zeroed: BTreeMap<B256, B256> = storage.keys().map(|slot| (*slot, B256::ZERO)).collect();
+    assert!(!is_validator_manager_initialized(&zeroed));
+
+    // Upgradeable layout: `_initialized` must be set and `_initializing` clear
+    let initializable = OzModule::Initializable.slot();
+    storage.insert(initializable, B256::with_last_byte(1));
+    assert!(is_validator_manager_initialized(&storage));
+
+    let mut initializing = B256::with_last_byte(1);
+    initializing.0[23] = 1;
+    storage.insert(initializable, initializing);
+    assert!(!is_validator_manager_initialized(&storage));
+
+    storage.insert(initializable, B256::with_last_byte(1));
+    storage.remove(&B256::ZERO);
+    assert!(!is_validator_manager_initialized(&storage));
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_oz_modules() -> eyre::Result<()> {
+    assert_eq!(
+        OzModule::Ownable.slot(),
+        b256!("0x9016d09d72d40fdae2fd8ceac6b6234c7706214fd39c1cd1e609a0528c199300")
+    );
+
+    // The genesis contract uses the sequential, non-upgradeable OZ layout
+    let validators = generate_validators_from_mnemonic(3)?;
+    let mut storage =
+        generate_from_validator_set(&validator_set_from(&validators)?, TEST_OWNER_ADDRESS)?;
+    assert!(detect_oz_modules(&storage).is_empty());
+
+    storage.insert(OzModule::Initializable.slot(), B256::with_last_byte(1));
+    storage.insert(OzModule::Ownable.slot(), TEST_OWNER_ADDRESS.into_word());
+    storage.insert(OzModule::Pausable.slot(), B256::ZERO);
+    assert_eq!(
+        detect_oz_modules(&storage),
+        vec![OzModule::Ownable, OzModule::Initializable]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_set_test_set() -> eyre::Result<()> {
+    let set = ValidatorSet::test_set(3);
+
+    let validators = set.get_validators();
+    let keys: std::collections::HashSet<_> = validators.iter().map(|v| v.validator_key).collect();
+    assert_eq!(keys.len(), 3);
+    assert_eq!(
+        validators.iter().map(|v| v.power).collect::<Vec<_>>(),
+        vec![100, 200, 300]
+    );
+
+    let storage = generate_storage_data(
+        validators.into_iter().cloned().collect(),
+        TEST_OWNER_ADDRESS,
+    )?;
+    assert_eq!(
+        parse_validator_set_from_storage(&storage)?.get_validators(),
+        set.get_validators()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_desynced_iteration_is_detected() -> eyre::Result<()> {
+    let validators = ValidatorSet::test_set(3)
+        .get_validators()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    let keys: Vec<ValidatorKey> = validators.iter().map(|v| v.validator_key).collect();
+
+    let consistent = ValidatorSet::with_order(validators.clone(), keys.clone());
+    consistent.check_iteration_consistency()?;
+
+    // The order misses a validator that the mapping still holds
+    let missing = ValidatorSet::with_order(validators.clone(), keys[..2].to_vec());
+    assert!(matches!(
+        missing.check_iteration_consistency(),
+        Err(ValidatroManagerError::InconsistentIteration {
+            ordered: 2,
+            validators: 3
+        })
+    ));
+    assert!(generate_from_validator_set(&missing, TEST_OWNER_ADDRESS).is_err());
+
+    // The order lists a key the mapping does not hold
+    let mut unknown = keys;
+    unknown[2] = (U256::from(99), U256::from(100));
+    let unknown = ValidatorSet::with_order(validators, unknown);
+    assert!(unknown.check_iteration_consistency().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_set_find_by_address() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(3)?;
+    let mut set = validator_set_from(&validators)?;
+
+    let address = validators[1].address();
+    assert_eq!(set.find_by_address(&address), Some(&validators[1]));
+    assert_eq!(set.find_by_address(&Address::repeat_byte(0x42)), None);
+
+    let removed = set.remove_validator(&validators[1].validator_key)?;
+    assert_eq!(removed, validators[1]);
+    assert_eq!(set.find_by_address(&address), None);
+    assert_eq!(
+        set.find_by_address(&validators[2].address()),
+        Some(&validators[2])
+    );
+    assert_eq!(set.count(), 2);
+    assert_eq!(
+        set.total_power()?,
+        validators[0].power + validators[2].power
+    );
+    set.check_iteration_consistency()?;
+
+    // Validators without a public key are found by their given address
+    let address_only = address!("0x2000000000000000000000000000000000000002");
+    let set = ValidatorSet::from_addresses(&[(address_only, 20)])?;
+    assert_eq!(
+        set.find_by_address(&address_only).map(|v| v.power),
+        Some(20)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_validator_set_iter_with_address() -> eyre::Result<()> {
+    let validators = generate_validators_from_mnemonic(4)?;
+    let set = validator_set_from(&validators)?;
+    let layout = ValidatorManagerLayout::default();
+
+    let pairs: Vec<(Address, &Validator)> = set.iter_with_address().collect();
+    assert_eq!(pairs.len(), validators.len());
+    for (address, validator) in &pairs {
+        assert_eq!(
+            *address,
+            validator_address_from_key(&validator.validator_key, &layout)
+        );
+    }
+    let keys: Vec<ValidatorKey> = pairs
+        .iter()
+        .map(|(_, validator)| validator.validator_key)
+        .collect();
+    assert_eq!(keys, set.ordered_validator_keys());
+
+    // Addresses given for address-only validators are yielded as is
+    let entries = [
+        (address!("0x1000000000000000000000000000000000000001"), 10),
+        (address!("0x2000000000000000000000000000000000000002"), 20),
+    ];
+    let set = ValidatorSet::from_addresses(&entries)?;
+    let addresses: Vec<(Address, u64)> = set
+        .iter_with_address()
+        .map(|(address, validator)| (address, validator.power))
+        .collect();
+    assert_eq!(addresses, entries);
+
+    Ok(())
+}
+
+#[test]
+fn test_be32_encoding() {
+    let value = U256::from_limbs([0xff, 0, 0x0102_0304_0506_0708, 0]);
+    let word = Be32::from_u256(value);
+
+    let mut expected = [0u8; 32];
+    expected[8..16].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    expected[31] = 0xff;
+    assert_eq!(word.0, expected);
+    assert_eq!(word.to_u256(), value);
+    assert_eq!(Be32::from(B256::from(word)), word);
+
+    for value in [U256::ZERO, U256::from(1u64), U256::MAX] {
+        assert_eq!(Be32::from_u256(value).to_u256(), value);
+    }
+    assert_eq!(Be32::from_u256(U256::from(1u64)).0[31], 1);
+
+    let owner = address!("0x00000000000000000000000000000000000000aa");
+    let word = Be32::from_address(owner);
+    assert_eq!(B256::from(word), owner.into_word());
+    assert_eq!(word.0[31], 0xaa);
+    assert!(word.0[..12].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn test_error_exit_codes() {
+    use super::error::exit_code;
+
+    let (x, y) = (U256::from(1), U256::from(2));
+    let cases = [
+        (ValidatroManagerError::EmptyValidatorSet, exit_code::GENESIS),
+        (
+            ValidatroManagerError::InvalidPower { x, y },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::PowerBelowMinimum {
+                key: (x, y),
+                power: 1,
+                min: 2,
+            },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::DuplicateValidator { x, y },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::TotalPowerOverflow,
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::UnknownValidator { x, y },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::LengthMismatch {
+                length: U256::from(1),
+                elements: 2,
+                validators: 2,
+            },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::InconsistentIteration {
+                ordered: 1,
+                validators: 2,
+            },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::ValidatorCountMismatch {
+                expected: 1,
+                got: 2,
+            },
+            exit_code::GENESIS,
+        ),
+        (
+            ValidatroManagerError::MissingStorageSlot { slot: B256::ZERO },
+            exit_code::STORAGE,
+        ),
+        (
+            ValidatroManagerError::InvalidStorageValue {
+                slot: B256::ZERO,
+                value: B256::ZERO,
+            },
+            exit_code::STORAGE,
+        ),
+        (
+            ValidatroManagerError::InvalidSec1Length { len: 64 },
+            exit_code::CRYPTO,
+        ),
+        (
+            ValidatroManagerError::InvalidSec1Prefix { prefix: 0x02 },
+            exit_code::CRYPTO,
+        ),
+        (
+            ValidatroManagerError::Io(std::io::ErrorKind::NotFound.into()),
+            exit_code::IO,
+        ),
+    ];
+    for (error, code) in &cases {
+        assert_eq!(error.exit_code(), *code, "{error}");
+    }
+
+    let codes = [
+        exit_code::GENESIS,
+        exit_code::STORAGE,
+        exit_code::CRYPTO,
+        exit_code::IO,
+    ];
+    let distinct: std::collections::BTreeSet<u8> = codes.into();
+    assert_eq!(distinct.len(), codes.len());
+    assert!(!distinct.contains(&0) && !distinct.contains(&1));
+}
+
+#[test]
+fn test_validator_set_with_capacity() -> eyre::Result<()> {
+    let validators = crate::test_util::deterministic_validators(1000, 7);
+
+    let mut presized = ValidatorSet::with_capacity(validators.len());
+    let mut grown = ValidatorSet::default();
+    for validator in &validators {
+        presized.add_validator(validator.clone())?;
+        grown.add_validator(validator.clone())?;
+    }
+
+    assert_eq!(presized.get_validators(), grown.get_validators());
+    assert_eq!(presized.total_power()?, grown.total_power()?);
+    assert_eq!(
+        generate_from_validator_set(&presized, TEST_OWNER_ADDRESS)?,
+        generate_from_validator_set(&grown, TEST_OWNER_ADDRESS)?
+    );
+    assert_eq!(
+        generate_storage_data(validators, TEST_OWNER_ADDRESS)?,
+        generate_from_validator_set(&grown, TEST_OWNER_ADDRESS)?
+    );
+
+    Ok(())
+}
@@ -3,9 +3,10 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
-use alloy_primitives::U256;
+use alloy_primitives::{Address, Bytes, U256};
 
 use crate::validator_manager::error::{Error as ValidatorManagerError, Result};
+use crate::validator_manager::storage::{validator_address_from_key, Be32, ValidatorManagerLayout};
 use crate::validator_manager::ValidatorManager;
 
 /// Tuple wrapper for an uncompressed secp256k1 public key (x, y limbs)
@@ -18,6 +19,9 @@ pub struct Validator {
     pub validator_key: ValidatorKey,
     /// Voting power for the validator
     pub power: u64,
+    /// Address receiving the rewards of blocks proposed by the validator, its
+    /// own address when unset
+    pub fee_recipient: Option<Address>,
 }
 
 impl Validator {
@@ -26,27 +30,93 @@ impl Validator {
         Self {
             validator_key: secp256k1_key,
             power,
+            fee_recipient: None,
         }
     }
+
+    /// Send the validator's block rewards to `fee_recipient`
+    pub fn with_fee_recipient(mut self, fee_recipient: Address) -> Self {
+        self.fee_recipient = Some(fee_recipient);
+        self
+    }
+
+    /// Address the ValidatorManager contract derives from the validator key
+    pub fn address(&self) -> Address {
+        validator_address_from_key(&self.validator_key, &ValidatorManagerLayout::default())
+    }
+}
+
+/// Uncompressed SEC1 encoding `0x04 || x || y` of a secp256k1 public key, as
+/// expected by the `register` call of the validator manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sec1UncompressedPubKey([u8; 65]);
+
+impl Sec1UncompressedPubKey {
+    /// Encode the `(x, y)` limbs of a validator key
+    pub fn from_validator_key(key: &ValidatorKey) -> Self {
+        let mut bytes = [0u8; 65];
+        bytes[0] = 0x04;
+        bytes[1..33].copy_from_slice(&Be32::from_u256(key.0).0);
+        bytes[33..].copy_from_slice(&Be32::from_u256(key.1).0);
+        Self(bytes)
+    }
+
+    /// The `(x, y)` limbs of the encoded key
+    pub fn validator_key(&self) -> ValidatorKey {
+        (
+            U256::from_be_slice(&self.0[1..33]),
+            U256::from_be_slice(&self.0[33..]),
+        )
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 65] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Sec1UncompressedPubKey {
+    type Error = ValidatorManagerError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 65] = bytes
+            .try_into()
+            .map_err(|_| ValidatorManagerError::InvalidSec1Length { len: bytes.len() })?;
+        if bytes[0] != 0x04 {
+            return Err(ValidatorManagerError::InvalidSec1Prefix { prefix: bytes[0] });
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl From<Sec1UncompressedPubKey> for Bytes {
+    fn from(key: Sec1UncompressedPubKey) -> Self {
+        Self::copy_from_slice(&key.0)
+    }
 }
 
 impl From<ValidatorManager::ValidatorInfo> for Validator {
     fn from(info: ValidatorManager::ValidatorInfo) -> Self {
-        Self {
-            validator_key: (info.validatorKey.x, info.validatorKey.y),
-            power: info.power,
+        let mut validator =
+            Self::from_public_key((info.validatorKey.x, info.validatorKey.y), info.power);
+        if !info.feeRecipient.is_zero() && info.feeRecipient != validator.address() {
+            validator.fee_recipient = Some(info.feeRecipient);
         }
+        validator
     }
 }
 
 impl From<Validator> for ValidatorManager::ValidatorInfo {
     fn from(validator: Validator) -> Self {
+        let fee_recipient = validator
+            .fee_recipient
+            .unwrap_or_else(|| validator.address());
         Self {
             validatorKey: ValidatorManager::Secp256k1Key {
                 x: validator.validator_key.0,
                 y: validator.validator_key.1,
             },
             power: validator.power,
+            feeRecipient: fee_recipient,
         }
     }
 }
@@ -60,9 +130,70 @@ pub struct ValidatorSet {
     validator_order: Vec<ValidatorKey>,
     /// Aggregate voting power across validators
     total_power: u64,
+    /// Addresses of validators added without a public key, by placeholder key
+    address_only: HashMap<ValidatorKey, Address>,
+    /// Validator keys by address, for address-keyed lookups
+    by_address: HashMap<Address, ValidatorKey>,
 }
 
+#[cfg(any(test, feature = "test-util"))]
 impl ValidatorSet {
+    /// Set of `n` validators with placeholder keys `(i, i + 1)` and powers `100 * (i + 1)`.
+    ///
+    /// The keys are not valid curve points, which is enough for storage layout tests
+    /// but not for signatures; see [`crate::test_util::deterministic_validators`].
+    pub fn test_set(n: usize) -> Self {
+        let mut set = Self::default();
+        for i in 0..n as u64 {
+            set.add_validator(Validator::from_public_key(
+                (U256::from(i), U256::from(i + 1)),
+                100 * (i + 1),
+            ))
+            .expect("test set power fits in u64");
+        }
+        set
+    }
+}
+
+impl ValidatorSet {
+    /// Empty set with room for `capacity` validators, to avoid reallocating while
+    /// building large sets
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            validators: HashMap::with_capacity(capacity),
+            validator_order: Vec::with_capacity(capacity),
+            total_power: 0,
+            address_only: HashMap::new(),
+            by_address: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Build a set from validator addresses and powers, when the public keys are unknown.
+    ///
+    /// Each validator gets the placeholder key `(address, 0)`, and its key limbs are
+    /// left zero in the generated storage. Such a set can regenerate the address set
+    /// and total power of the contract, but cannot be used to verify signatures.
+    pub fn from_addresses(entries: &[(Address, u64)]) -> Result<Self> {
+        let mut set = Self::default();
+        for (address, power) in entries {
+            let key = (U256::from_be_slice(address.as_slice()), U256::ZERO);
+            let (x, y) = key;
+            if *power == 0 {
+                return Err(ValidatorManagerError::InvalidPower { x, y });
+            }
+            if set.address_only.insert(key, *address).is_some() {
+                return Err(ValidatorManagerError::DuplicateValidator { x, y });
+            }
+            set.add_validator(Validator::from_public_key(key, *power))?;
+        }
+        Ok(set)
+    }
+
+    /// Address of a validator added by [`Self::from_addresses`], which has no public key
+    pub fn address_only(&self, key: &ValidatorKey) -> Option<Address> {
+        self.address_only.get(key).copied()
+    }
+
     /// Add a validator to the set
     pub fn add_validator(&mut self, validator: Validator) -> Result<()> {
         let key = validator.validator_key;
@@ -76,11 +207,67 @@ impl ValidatorSet {
             .total_power
             .checked_add(validator.power)
             .ok_or(ValidatorManagerError::TotalPowerOverflow)?;
+        let address = self.address_of(&validator);
         self.validator_order.push(key);
+        self.by_address.insert(address, key);
         self.validators.insert(key, validator);
         Ok(())
     }
 
+    /// Remove a validator from the set, returning it
+    pub fn remove_validator(&mut self, key: &ValidatorKey) -> Result<Validator> {
+        let (x, y) = *key;
+        let validator = self
+            .validators
+            .remove(key)
+            .ok_or(ValidatorManagerError::UnknownValidator { x, y })?;
+
+        let address = self.address_of(&validator);
+        self.by_address.remove(&address);
+        self.address_only.remove(key);
+        self.validator_order.retain(|k| k != key);
+        self.total_power -= validator.power;
+        Ok(validator)
+    }
+
+    /// Find the validator registered under `address`, such as one observed in a
+    /// contract event
+    pub fn find_by_address(&self, address: &Address) -> Option<&Validator> {
+        self.by_address
+            .get(address)
+            .and_then(|key| self.validators.get(key))
+    }
+
+    /// Address of a validator in the set, given by [`Self::from_addresses`] or
+    /// derived from its key
+    fn address_of(&self, validator: &Validator) -> Address {
+        self.address_only(&validator.validator_key)
+            .unwrap_or_else(|| validator.address())
+    }
+
+    /// Update the voting power of an existing validator and recompute the total power
+    pub fn set_power(&mut self, key: &ValidatorKey, power: u64) -> Result<()> {
+        let (x, y) = *key;
+        if power == 0 {
+            return Err(ValidatorManagerError::InvalidPower { x, y });
+        }
+
+        let current = self
+            .validators
+            .get(key)
+            .ok_or(ValidatorManagerError::UnknownValidator { x, y })?
+            .power;
+
+        self.total_power = (self.total_power - current)
+            .checked_add(power)
+            .ok_or(ValidatorManagerError::TotalPowerOverflow)?;
+
+        if let Some(validator) = self.validators.get_mut(key) {
+            validator.power = power;
+        }
+        Ok(())
+    }
+
     /// Get the number of validators
     pub fn count(&self) -> usize {
         self.validators.len()
@@ -94,6 +281,26 @@ impl ValidatorSet {
             .collect()
     }
 
+    /// Get up to `limit` validators in registration order, starting at `offset`.
+    /// Returns an empty page when `offset` is past the end of the set.
+    pub fn page(&self, offset: usize, limit: usize) -> Vec<&Validator> {
+        self.validator_order
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .filter_map(|key| self.validators.get(key))
+            .collect()
+    }
+
+    /// Validators in registration order, each with its address, given by
+    /// [`Self::from_addresses`] or derived once from its key
+    pub fn iter_with_address(&self) -> impl Iterator<Item = (Address, &Validator)> {
+        self.validator_order
+            .iter()
+            .filter_map(|key| self.validators.get(key))
+            .map(|validator| (self.address_of(validator), validator))
+    }
+
     /// Compute the total voting power across all validators
     pub fn total_power(&self) -> Result<u64> {
         Ok(self.total_power)
@@ -103,4 +310,45 @@ impl ValidatorSet {
     pub fn ordered_validator_keys(&self) -> &[ValidatorKey] {
         &self.validator_order
     }
+
+    /// Check that [`Self::ordered_validator_keys`] and [`Self::get_validators`]
+    /// describe the same validators in the same order, since the storage generator
+    /// writes the address set from the former and the entries mapping from the latter
+    pub fn check_iteration_consistency(&self) -> Result<()> {
+        let ordered = self.ordered_validator_keys();
+        let validators = self.get_validators();
+        let consistent = ordered.len() == validators.len()
+            && ordered.len() == self.validators.len()
+            && ordered
+                .iter()
+                .zip(&validators)
+                .all(|(key, validator)| *key == validator.validator_key);
+
+        if !consistent {
+            return Err(ValidatorManagerError::InconsistentIteration {
+                ordered: ordered.len(),
+                validators: self.validators.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Set whose registration order lists `order` regardless of the validators it
+    /// holds, to exercise [`Self::check_iteration_consistency`]
+    #[cfg(test)]
+    pub(crate) fn with_order(validators: Vec<Validator>, order: Vec<ValidatorKey>) -> Self {
+        Self {
+            by_address: validators
+                .iter()
+                .map(|v| (v.address(), v.validator_key))
+                .collect(),
+            total_power: validators.iter().map(|v| v.power).sum(),
+            validators: validators
+                .into_iter()
+                .map(|v| (v.validator_key, v))
+                .collect(),
+            validator_order: order,
+            address_only: HashMap::new(),
+        }
+    }
 }